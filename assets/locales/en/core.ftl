@@ -41,10 +41,22 @@ mips-all = All
 mips-none = None
 hint-mips-all = Enable all mip levels
 hint-mips-none = Disable all mip levels
+holes-summary = Holes: { $count } ({ $bytes } B)
+hint-holes = Bytes not covered by the header or any mip. Inspect or dump them with `blp holes`.
+metadata-summary = Metadata: { $count } entries
+hint-metadata = Metadata chunk written by the encoder after the JPEG header. Also printed by `blp <PATH>`.
+jpeg-quality = JPEG quality: { $quality }
+hint-jpeg-quality = Estimated from the quantization tables (IJG scale, same as the export quality). "≈" means the tables are not standard IJG ones.
 
 blp-quality = BLP quality: { $val }
 blp-quality-hint = Compression quality for BLP export. 0 = worst, 100 = best.
 
+fill-mips-on = Rebuild missing mips
+fill-mips-hint-on = Missing and disabled mips are rebuilt from the level above, down to 1×1. Click to toggle.
+
+fill-mips-off = Skip missing mips
+fill-mips-hint-off = Missing and disabled mips are left out of the saved BLP. Click to toggle.
+
 no-visible-mip-hint = No mip level is selected.\nUse the right panel to choose one.
 
 blp-texture = BLP texture
@@ -68,4 +80,102 @@ error-image-empty = Image has invalid size (width={ $width }, height={ $height }
 error-clipboard-init-failed = Failed to access clipboard.
 error-clipboard-no-image = No image found in clipboard.
 error-clipboard-invalid-buffer = Invalid clipboard image buffer (width={ $width }, height={ $height }).
-error-clipboard-encode-png-failed = Failed to encode PNG from clipboard image.
+
+error-blp-not-blp = Not a BLP file.
+error-blp-mip-missing = Mip level { $mip } is missing.
+error-limits-exceeded = Decode limit exceeded: { $what } = { $value } (max { $max }).
+
+error-io = I/O error: { $msg }
+error-io-read-file = Failed to read file: { $path } ({ $msg })
+error-truncated = Unexpected end of data: { $msg }
+error-enum-value = Unsupported { $name } value: { $value }
+error-buffer-too-small = Output buffer is too small ({ $got } bytes, need { $need }).
+error-decode-thread-disconnected = Decoder thread stopped unexpectedly.
+error-ui-run-native = Failed to start the UI: { $msg }
+
+error-blp-decode-failed = Failed to decode BLP.
+error-alpha-bits-unsupported = Unsupported alpha depth: { $bits } bits.
+error-direct-palette-oob = Palette is out of file bounds.
+error-direct-indices-truncated = Palette indices are truncated.
+error-direct-alpha-truncated = Alpha data is truncated.
+error-direct-buffer-size = Internal error: pixel buffer size mismatch.
+
+error-image = Image error: { $msg }
+error-image-decode = Failed to decode image: { $msg }
+error-export-png = Failed to export PNG: { $msg }
+error-export-jpg-not-jpeg = Not a JPEG-compressed BLP.
+error-export-jpg-header-oob = JPEG header is out of file bounds (offset={ $offset }, length={ $length }, file={ $buf_len }).
+error-export-jpg-mip-oob = Mip data is out of file bounds (offset={ $offset }, length={ $length }, file={ $buf_len }).
+
+error-jpeg-decoder = JPEG decoding failed: { $msg }
+error-jpeg-encoder = JPEG encoding failed: { $msg }
+error-jpeg-header-oob = Shared JPEG header is out of file bounds.
+error-jpeg-header-size-truncated = File is too short to contain the JPEG header size.
+error-jpeg-meta-missing = JPEG stream has no frame information.
+error-jpeg-size-mismatch = Decoded JPEG size does not match (mip { $mip }).
+error-jpeg-too-large = Image is too large for JPEG ({ $width }×{ $height }).
+error-jpeg-bad-soi = JPEG stream does not start with SOI.
+error-jpeg-truncated = JPEG stream is truncated.
+error-jpeg-segment-len = Invalid JPEG segment length.
+error-jpeg-marker-expected = JPEG marker expected.
+error-jpeg-eoi-before-sos = JPEG stream ends before scan data.
+error-jpeg-eoi-not-found = JPEG end marker not found.
+error-jpeg-sof-missing = JPEG frame header (SOF) not found.
+error-jpeg-sos-missing = JPEG scan header (SOS) not found.
+
+error-turbojpeg = TurboJPEG error: { $msg }
+error-turbojpeg-init = Failed to initialize TurboJPEG.
+error-turbojpeg-header = TurboJPEG could not read the header: { $msg }
+error-turbojpeg-decompress = TurboJPEG decompression failed: { $msg }
+error-turbojpeg-compress = TurboJPEG compression failed: { $msg }
+error-turbojpeg-param = TurboJPEG rejected parameter { $param }: { $msg }
+
+error-encode-no-visible-mips = No mip levels left to encode after applying the mask.
+error-encode-first-visible-missing = First visible mip level has no pixels.
+error-encode-mip-size-mismatch = Mip size mismatch: expected { $want_w }×{ $want_h }, got { $got_w }×{ $got_h }.
+error-encode-no-heads = Encoder produced no JPEG headers.
+error-encode-common-header = Failed to build a common JPEG header.
+error-encode-head-prefix-mismatch = JPEG header of mip { $mip } does not match the common header.
+error-encode-head-too-short = JPEG header of mip { $mip } is shorter than the common header ({ $head_len } < { $common_len }).
+error-encode-header-too-large = JPEG header is too large for BLP.
+error-encode-offset-too-large = Mip offset does not fit into BLP.
+error-encode-payload-too-large = Mip data does not fit into BLP.
+
+error-cli-usage = { $msg }
+error-cli-path-required = A PATH or a subcommand is required. Use --help for more information.
+
+error-mpq-no-header = Not an MPQ archive: header not found.
+error-mpq-sector-size = MPQ sector size shift { $shift } is invalid.
+error-mpq-open = Failed to open MPQ archive { $path }.
+error-mpq-import = Failed to write files into MPQ archive { $path }.
+error-mpq-file-not-found = File { $name } is not in the archive.
+error-mpq-read-file = Failed to read { $name } from the archive.
+error-mpq-sector-table = MPQ sector offset table is corrupt.
+error-mpq-out-of-bounds = MPQ data at offset { $offset } ({ $size } bytes) is past the end of the file.
+error-mpq-decompress = Failed to decompress MPQ data ({ $method }).
+error-mpq-compression = Unsupported MPQ compression (mask { $mask }).
+error-mpq-sector-size-mismatch = MPQ sector unpacked to { $actual } bytes instead of { $expected }.
+error-mpq-hash-table-full = MPQ hash table is full ({ $size } entries); the file cannot be added.
+error-mpq-hash-table-size = MPQ hash table size { $size } is not a power of two or is too large; files cannot be added.
+error-mpq-too-large = MPQ archive would exceed 4 GiB ({ $size } bytes).
+
+error-scan-not-dir = { $path } is not a directory or an MPQ archive.
+error-inspect-not-jpeg = Texture is not JPEG-compressed; there is no JPEG header to inspect.
+error-repair-not-blp = Input is not a BLP file.
+error-repair-not-jpeg = Only JPEG textures can be repaired.
+error-repair-blp0 = BLP0 keeps mips in separate files and cannot be repaired.
+error-repair-mip = Mip { $mip } has no valid JPEG header.
+error-repair-no-mips = No mip has JPEG data to repair.
+error-metadata-too-long = Metadata entry { $key } is too long.
+error-metadata-truncated = Metadata chunk is truncated.
+error-metadata-magic = Metadata chunk has no RAFT signature.
+error-metadata-version = Unsupported metadata chunk version { $version }.
+error-metadata-utf8 = Metadata chunk contains invalid UTF-8.
+error-convert-target = Cannot convert to { $version }: only BLP1 and BLP2 are supported.
+error-convert-compression = BLP2 compression { $compression } has no BLP1 equivalent (only JPEG and palette).
+error-transform-not-jpeg = Only JPEG textures can be rotated or flipped.
+error-transform-mip = Mip { $mip } could not be decoded for re-encoding.
+error-optimize-not-jpeg = Only JPEG textures can be optimized.
+error-optimize-unavailable = Huffman optimization needs the turbojpeg feature.
+error-optimize-mismatch = Optimized mip { $mip } does not decode to the same pixels; the file was not written.
+error-quality-no-dqt = No JPEG quantization tables found; the texture is not JPEG or its header is damaged.
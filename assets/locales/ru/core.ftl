@@ -41,10 +41,22 @@ mips-all = Все
 mips-none = Никто
 hint-mips-all = Включить все уровни мипов
 hint-mips-none = Отключить все уровни мипов
+holes-summary = Дыры: { $count } ({ $bytes } Б)
+hint-holes = Байты вне заголовка и мипов. Посмотреть или выгрузить — `blp holes`.
+metadata-summary = Метаданные: { $count } записей
+hint-metadata = Чанк метаданных, записанный кодировщиком после JPEG-заголовка. Также выводится командой `blp <PATH>`.
+jpeg-quality = Качество JPEG: { $quality }
+hint-jpeg-quality = Оценка по таблицам квантования (шкала IJG, как у качества экспорта). «≈» — таблицы не стандартные IJG.
 
 blp-quality = Качество BLP: { $val }
 blp-quality-hint = Качество сжатия при экспорте BLP. 0 — худшее, 100 — лучшее.
 
+fill-mips-on = Достраивать мипы
+fill-mips-hint-on = Отсутствующие и отключённые мипы пересчитываются из уровня выше, до 1×1. Нажмите, чтобы переключить режим.
+
+fill-mips-off = Не достраивать мипы
+fill-mips-hint-off = Отсутствующие и отключённые мипы не попадут в сохранённый BLP. Нажмите, чтобы переключить режим.
+
 no-visible-mip-hint = Ни один мип не выбран.\nИспользуйте панель справа, чтобы выбрать уровни.
 
 blp-texture = BLP текстура
@@ -68,4 +80,52 @@ error-image-empty = Некорректный размер изображения
 error-clipboard-init-failed = Не удалось получить доступ к буферу обмена.
 error-clipboard-no-image = В буфере обмена нет изображения.
 error-clipboard-invalid-buffer = Некорректный буфер изображения (ширина={ $width }, высота={ $height }).
-error-clipboard-encode-png-failed = Не удалось закодировать PNG из изображения буфера обмена.
+
+error-blp-not-blp = Файл не является BLP.
+error-blp-mip-missing = Мип-уровень { $mip } отсутствует.
+error-limits-exceeded = Превышен лимит декодирования: { $what } = { $value } (максимум { $max }).
+
+error-io = Ошибка ввода-вывода: { $msg }
+error-io-read-file = Не удалось прочитать файл: { $path } ({ $msg })
+error-truncated = Данные неожиданно закончились: { $msg }
+error-enum-value = Неподдерживаемое значение { $name }: { $value }
+error-blp-decode-failed = Не удалось декодировать BLP.
+error-alpha-bits-unsupported = Неподдерживаемая глубина альфы: { $bits } бит.
+error-direct-indices-truncated = Индексы палитры обрезаны.
+error-direct-alpha-truncated = Данные альфы обрезаны.
+error-jpeg-header-oob = Общий JPEG-заголовок выходит за пределы файла.
+error-image = Ошибка изображения: { $msg }
+error-image-decode = Не удалось декодировать изображение: { $msg }
+error-jpeg-decoder = Ошибка декодирования JPEG: { $msg }
+error-buffer-too-small = Выходной буфер слишком мал ({ $got } байт, нужно { $need }).
+
+error-cli-usage = { $msg }
+error-cli-path-required = Нужен PATH или подкоманда. Подробнее: --help.
+
+error-mpq-no-header = Это не MPQ-архив: заголовок не найден.
+error-mpq-open = Не удалось открыть MPQ-архив { $path }.
+error-mpq-import = Не удалось записать файлы в MPQ-архив { $path }.
+error-mpq-file-not-found = Файла { $name } нет в архиве.
+error-mpq-read-file = Не удалось прочитать { $name } из архива.
+error-mpq-hash-table-full = Хэш-таблица MPQ заполнена ({ $size } записей); файл не добавить.
+error-mpq-hash-table-size = Размер хэш-таблицы MPQ { $size } — не степень двойки или слишком велик; добавить файлы нельзя.
+error-scan-not-dir = { $path } — не каталог и не MPQ-архив.
+error-inspect-not-jpeg = Текстура не в JPEG — разбирать нечего.
+error-repair-not-blp = Входной файл — не BLP.
+error-repair-not-jpeg = Исправлять можно только JPEG-текстуры.
+error-repair-blp0 = BLP0 хранит мипы в отдельных файлах — исправление не поддерживается.
+error-repair-mip = У мипа { $mip } нет корректного JPEG-заголовка.
+error-repair-no-mips = Ни у одного мипа нет JPEG-данных для исправления.
+error-metadata-too-long = Запись метаданных { $key } слишком длинная.
+error-metadata-truncated = Чанк метаданных обрезан.
+error-metadata-magic = У чанка метаданных нет сигнатуры RAFT.
+error-metadata-version = Неподдерживаемая версия чанка метаданных { $version }.
+error-metadata-utf8 = В чанке метаданных некорректный UTF-8.
+error-convert-target = Преобразование в { $version } не поддерживается: только BLP1 и BLP2.
+error-convert-compression = Для сжатия BLP2 { $compression } нет аналога в BLP1 (только JPEG и палитра).
+error-transform-not-jpeg = Поворачивать и отражать можно только JPEG-текстуры.
+error-transform-mip = Мип { $mip } не декодируется — перекодировать нечего.
+error-optimize-not-jpeg = Оптимизировать можно только JPEG-текстуры.
+error-optimize-unavailable = Для оптимизации Хаффмана нужна сборка с feature turbojpeg.
+error-optimize-mismatch = Оптимизированный мип { $mip } декодируется в другие пиксели; файл не записан.
+error-quality-no-dqt = Таблицы квантования JPEG не найдены: текстура не в JPEG или заголовок повреждён.
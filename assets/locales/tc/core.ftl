@@ -41,8 +41,20 @@ mips-all = 全部
 mips-none = 無
 hint-mips-all = 啟用所有 mip 級別
 hint-mips-none = 停用所有 mip 級別
+holes-summary = 空洞：{ $count }（{ $bytes } B）
+hint-holes = 未被檔頭或任何 mip 涵蓋的位元組。可用 `blp holes` 檢視或匯出。
+metadata-summary = 中繼資料：{ $count } 項
+hint-metadata = 編碼器寫在 JPEG 檔頭之後的中繼資料區塊。`blp <PATH>` 也會輸出。
+jpeg-quality = JPEG 品質：{ $quality }
+hint-jpeg-quality = 依量化表估算（IJG 標度，與匯出品質相同）。「≈」表示不是標準 IJG 量化表。
 blp-quality = BLP 匯出品質
 blp-quality-hint = 匯出 BLP 時使用的 JPEG 品質
+
+fill-mips-on = 補全缺失的 mip
+fill-mips-hint-on = 缺失和已停用的 mip 將由上一級重新產生，直到 1×1。點擊切換模式。
+
+fill-mips-off = 不補全 mip
+fill-mips-hint-off = 缺失和已停用的 mip 不會寫入儲存的 BLP。點擊切換模式。
 no-visible-mip-hint = 目前沒有選擇任何 mip 級別。請使用右側面板進行選擇。
 
 blp-texture = BLP 紋理
@@ -66,4 +78,21 @@ error-image-empty = 影像尺寸無效 (寬度={ $width }, 高度={ $height })
 error-clipboard-init-failed = 無法存取剪貼簿。
 error-clipboard-no-image = 剪貼簿中未找到影像。
 error-clipboard-invalid-buffer = 剪貼簿影像緩衝區無效 (寬度={ $width }, 高度={ $height })。
-error-clipboard-encode-png-failed = 從剪貼簿影像編碼 PNG 失敗。
+
+error-blp-not-blp = 不是 BLP 檔案。
+error-blp-mip-missing = 缺少 Mip 層級 { $mip }。
+error-limits-exceeded = 超出解碼限制: { $what } = { $value } (最大 { $max })。
+
+error-io = I/O 錯誤: { $msg }
+error-io-read-file = 無法讀取檔案: { $path } ({ $msg })
+error-truncated = 資料意外結束: { $msg }
+error-enum-value = 不支援的 { $name } 值: { $value }
+error-blp-decode-failed = BLP 解碼失敗。
+error-alpha-bits-unsupported = 不支援的 Alpha 位元深度: { $bits } 位元。
+error-direct-indices-truncated = 調色盤索引資料不完整。
+error-direct-alpha-truncated = Alpha 資料不完整。
+error-jpeg-header-oob = 共用 JPEG 標頭超出檔案範圍。
+error-image = 影像錯誤: { $msg }
+error-image-decode = 影像解碼失敗: { $msg }
+error-jpeg-decoder = JPEG 解碼失敗: { $msg }
+error-buffer-too-small = 輸出緩衝區太小 ({ $got } 位元組, 需要 { $need })。
@@ -41,10 +41,22 @@ mips-all = Усі
 mips-none = Жоден
 hint-mips-all = Увімкнути всі рівні міпів
 hint-mips-none = Вимкнути всі рівні міпів
+holes-summary = Дірки: { $count } ({ $bytes } Б)
+hint-holes = Байти поза заголовком і міпами. Переглянути або вивантажити — `blp holes`.
+metadata-summary = Метадані: { $count } записів
+hint-metadata = Чанк метаданих, записаний кодувальником після JPEG-заголовка. Також виводиться командою `blp <PATH>`.
+jpeg-quality = Якість JPEG: { $quality }
+hint-jpeg-quality = Оцінка за таблицями квантування (шкала IJG, як у якості експорту). «≈» — таблиці не стандартні IJG.
 
 blp-quality = Якість BLP: { $val }
 blp-quality-hint = Якість стискання при експорті BLP. 0 — найгірша, 100 — найкраща.
 
+fill-mips-on = Добудовувати міпи
+fill-mips-hint-on = Відсутні та вимкнені міпи перераховуються з рівня вище, до 1×1. Натисніть, щоб перемкнути режим.
+
+fill-mips-off = Не добудовувати міпи
+fill-mips-hint-off = Відсутні та вимкнені міпи не потраплять до збереженого BLP. Натисніть, щоб перемкнути режим.
+
 no-visible-mip-hint = Жоден міп не вибрано.\nСкористайтеся правою панеллю, щоб обрати рівні.
 
 blp-texture = BLP текстура
@@ -68,4 +80,52 @@ error-image-empty = Некоректний розмір зображення (ш
 error-clipboard-init-failed = Не вдалося отримати доступ до буфера обміну.
 error-clipboard-no-image = У буфері обміну немає зображення.
 error-clipboard-invalid-buffer = Некоректний буфер зображення (ширина={ $width }, висота={ $height }).
-error-clipboard-encode-png-failed = Не вдалося закодувати PNG із зображення буфера обміну.
+
+error-blp-not-blp = Файл не є BLP.
+error-blp-mip-missing = Міп-рівень { $mip } відсутній.
+error-limits-exceeded = Перевищено ліміт декодування: { $what } = { $value } (максимум { $max }).
+
+error-io = Помилка вводу-виводу: { $msg }
+error-io-read-file = Не вдалося прочитати файл: { $path } ({ $msg })
+error-truncated = Дані несподівано закінчилися: { $msg }
+error-enum-value = Непідтримуване значення { $name }: { $value }
+error-blp-decode-failed = Не вдалося декодувати BLP.
+error-alpha-bits-unsupported = Непідтримувана глибина альфи: { $bits } біт.
+error-direct-indices-truncated = Індекси палітри обрізано.
+error-direct-alpha-truncated = Дані альфи обрізано.
+error-jpeg-header-oob = Спільний JPEG-заголовок виходить за межі файлу.
+error-image = Помилка зображення: { $msg }
+error-image-decode = Не вдалося декодувати зображення: { $msg }
+error-jpeg-decoder = Помилка декодування JPEG: { $msg }
+error-buffer-too-small = Вихідний буфер замалий ({ $got } байт, потрібно { $need }).
+
+error-cli-usage = { $msg }
+error-cli-path-required = Потрібен PATH або підкоманда. Докладніше: --help.
+
+error-mpq-no-header = Це не MPQ-архів: заголовок не знайдено.
+error-mpq-open = Не вдалося відкрити MPQ-архів { $path }.
+error-mpq-import = Не вдалося записати файли в MPQ-архів { $path }.
+error-mpq-file-not-found = Файлу { $name } немає в архіві.
+error-mpq-read-file = Не вдалося прочитати { $name } з архіву.
+error-mpq-hash-table-full = Хеш-таблиця MPQ заповнена ({ $size } записів); файл не додати.
+error-mpq-hash-table-size = Розмір хеш-таблиці MPQ { $size } — не степінь двійки або завеликий; додати файли неможливо.
+error-scan-not-dir = { $path } — не каталог і не MPQ-архів.
+error-inspect-not-jpeg = Текстура не в JPEG — аналізувати нічого.
+error-repair-not-blp = Вхідний файл — не BLP.
+error-repair-not-jpeg = Виправляти можна лише JPEG-текстури.
+error-repair-blp0 = BLP0 зберігає міпи в окремих файлах — виправлення не підтримується.
+error-repair-mip = Міп { $mip } не має коректного JPEG-заголовка.
+error-repair-no-mips = Жоден міп не має JPEG-даних для виправлення.
+error-metadata-too-long = Запис метаданих { $key } задовгий.
+error-metadata-truncated = Чанк метаданих обрізаний.
+error-metadata-magic = Чанк метаданих не має сигнатури RAFT.
+error-metadata-version = Непідтримувана версія чанка метаданих { $version }.
+error-metadata-utf8 = Чанк метаданих містить некоректний UTF-8.
+error-convert-target = Перетворення в { $version } не підтримується: лише BLP1 і BLP2.
+error-convert-compression = Для стиснення BLP2 { $compression } немає відповідника в BLP1 (лише JPEG і палітра).
+error-transform-not-jpeg = Повертати й віддзеркалювати можна лише JPEG-текстури.
+error-transform-mip = Міп { $mip } не декодується — перекодувати нічого.
+error-optimize-not-jpeg = Оптимізувати можна лише JPEG-текстури.
+error-optimize-unavailable = Для оптимізації Гаффмана потрібна збірка з feature turbojpeg.
+error-optimize-mismatch = Оптимізований міп { $mip } декодується в інші пікселі; файл не записано.
+error-quality-no-dqt = Таблиці квантування JPEG не знайдено: текстура не в JPEG або заголовок пошкоджений.
@@ -41,8 +41,20 @@ mips-all = 全部
 mips-none = 无
 hint-mips-all = 启用所有 mip 级别
 hint-mips-none = 禁用所有 mip 级别
+holes-summary = 空洞：{ $count }（{ $bytes } B）
+hint-holes = 未被文件头或任何 mip 覆盖的字节。可用 `blp holes` 查看或导出。
+metadata-summary = 元数据：{ $count } 项
+hint-metadata = 编码器写在 JPEG 头之后的元数据块。`blp <PATH>` 也会输出。
+jpeg-quality = JPEG 质量：{ $quality }
+hint-jpeg-quality = 根据量化表估算（IJG 标度，与导出质量相同）。“≈”表示不是标准 IJG 量化表。
 blp-quality = BLP 导出质量
 blp-quality-hint = 导出 BLP 时使用的 JPEG 质量
+
+fill-mips-on = 补全缺失的 mip
+fill-mips-hint-on = 缺失和已禁用的 mip 将由上一级重新生成，直到 1×1。点击切换模式。
+
+fill-mips-off = 不补全 mip
+fill-mips-hint-off = 缺失和已禁用的 mip 不会写入保存的 BLP。点击切换模式。
 no-visible-mip-hint = 未选择任何 mip 级别。请使用右侧面板进行选择。
 
 blp-texture = BLP 纹理
@@ -66,4 +78,21 @@ error-image-empty = 图像尺寸无效 (宽度={ $width }, 高度={ $height })
 error-clipboard-init-failed = 无法访问剪贴板。
 error-clipboard-no-image = 剪贴板中未找到图像。
 error-clipboard-invalid-buffer = 剪贴板图像缓冲区无效 (宽度={ $width }, 高度={ $height })。
-error-clipboard-encode-png-failed = 从剪贴板图像编码 PNG 失败。
+
+error-blp-not-blp = 不是 BLP 文件。
+error-blp-mip-missing = 缺少 Mip 级别 { $mip }。
+error-limits-exceeded = 超出解码限制: { $what } = { $value } (最大 { $max })。
+
+error-io = I/O 错误: { $msg }
+error-io-read-file = 无法读取文件: { $path } ({ $msg })
+error-truncated = 数据意外结束: { $msg }
+error-enum-value = 不支持的 { $name } 值: { $value }
+error-blp-decode-failed = BLP 解码失败。
+error-alpha-bits-unsupported = 不支持的 Alpha 位深: { $bits } 位。
+error-direct-indices-truncated = 调色板索引数据不完整。
+error-direct-alpha-truncated = Alpha 数据不完整。
+error-jpeg-header-oob = 共享 JPEG 头超出文件范围。
+error-image = 图像错误: { $msg }
+error-image-decode = 图像解码失败: { $msg }
+error-jpeg-decoder = JPEG 解码失败: { $msg }
+error-buffer-too-small = 输出缓冲区太小 ({ $got } 字节, 需要 { $need })。
@@ -0,0 +1,92 @@
+use blp::core::decode::decode_options::DecodeOptions;
+use blp::core::image::ImageBlp;
+use criterion::{Criterion, criterion_group, criterion_main};
+use image::{Rgba, RgbaImage};
+use std::hint::black_box;
+
+const SIZE: u32 = 2048;
+
+/// Синтетический DIRECT BLP1 (палитра + индексы + альфа), все мипы.
+fn direct_blp(alpha_bits: u32) -> Vec<u8> {
+    let mips: Vec<(u32, u32)> = (0..12)
+        .map(|i| ((SIZE >> i).max(1), (SIZE >> i).max(1)))
+        .collect();
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"BLP1");
+    out.extend_from_slice(&1u32.to_le_bytes()); // DIRECT
+    out.extend_from_slice(&alpha_bits.to_le_bytes());
+    out.extend_from_slice(&SIZE.to_le_bytes());
+    out.extend_from_slice(&SIZE.to_le_bytes());
+    out.extend_from_slice(&5u32.to_le_bytes()); // extra
+    out.extend_from_slice(&1u32.to_le_bytes()); // has_mipmaps
+
+    let mut offsets = [0u32; 16];
+    let mut sizes = [0u32; 16];
+    let mut payload = Vec::new();
+    let base = 156 + 1024;
+    for (i, &(w, h)) in mips.iter().enumerate() {
+        let n = (w * h) as usize;
+        let alpha_len = match alpha_bits {
+            1 => n.div_ceil(8),
+            4 => n.div_ceil(2),
+            8 => n,
+            _ => 0,
+        };
+        offsets[i] = (base + payload.len()) as u32;
+        sizes[i] = (n + alpha_len) as u32;
+        payload.extend((0..n).map(|p| (p * 31 % 251) as u8));
+        payload.extend((0..alpha_len).map(|p| (p * 7) as u8));
+    }
+    offsets
+        .iter()
+        .chain(&sizes)
+        .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+    for c in 0..256u32 {
+        out.extend_from_slice(&(c | ((255 - c) << 8) | (((c * 3) & 0xFF) << 16)).to_le_bytes());
+    }
+    out.extend_from_slice(&payload);
+    out
+}
+
+fn jpeg_blp(alpha: bool) -> Vec<u8> {
+    let src = RgbaImage::from_fn(SIZE, SIZE, |x, y| Rgba([x as u8, y as u8, ((x * y) >> 4) as u8, if alpha { (x ^ y) as u8 } else { 255 }]));
+    ImageBlp::from_rgba(src, &[])
+        .unwrap()
+        .encode_blp(90, &[])
+        .unwrap()
+        .bytes
+}
+
+fn bench_decode(c: &mut Criterion) {
+    // один поток — меряем сами конверсии, а не параллелизм
+    let opts = DecodeOptions { threads: 1, ..Default::default() };
+    let mut group = c.benchmark_group("decode_2048");
+    group.sample_size(10);
+
+    let cases = [
+        ("direct_a0", direct_blp(0)),
+        ("direct_a1", direct_blp(1)),
+        ("direct_a4", direct_blp(4)),
+        ("direct_a8", direct_blp(8)),
+        ("jpeg_rgb", jpeg_blp(false)),
+        ("jpeg_cmyk", jpeg_blp(true)),
+    ];
+    for (name, buf) in &cases {
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                let mut img = ImageBlp::from_buf(buf).unwrap();
+                img.decode_with(black_box(buf), &[], &opts)
+                    .unwrap();
+                img
+            })
+        });
+    }
+
+    let src = RgbaImage::from_fn(SIZE, SIZE, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+    group.bench_function("from_rgba", |b| b.iter(|| ImageBlp::from_rgba(black_box(src.clone()), &[]).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
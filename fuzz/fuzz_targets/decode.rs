@@ -0,0 +1,22 @@
+#![no_main]
+
+use blp::core::decode::decode_limits::DecodeLimits;
+use blp::core::decode::decode_options::DecodeOptions;
+use blp::core::image::ImageBlp;
+use libfuzzer_sys::fuzz_target;
+
+// Полный путь: заголовок → все мипы. Лимиты ужаты под rss-лимит libFuzzer (2 ГБ по умолчанию).
+fuzz_target!(|data: &[u8]| {
+    let limits = DecodeLimits {
+        max_width: 2048,
+        max_height: 2048,
+        max_pixels: 8 << 20,
+        max_alloc: 64 << 20,
+        max_mips: 16,
+    };
+    let Ok(mut img) = ImageBlp::from_buf_with(data, &limits) else {
+        return;
+    };
+    let opts = DecodeOptions { threads: 1, limits, ..Default::default() };
+    let _ = img.decode_with(data, &[], &opts);
+});
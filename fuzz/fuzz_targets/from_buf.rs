@@ -0,0 +1,9 @@
+#![no_main]
+
+use blp::core::image::ImageBlp;
+use libfuzzer_sys::fuzz_target;
+
+// Только заголовок: разбор BLP и размеры внешних картинок — без паник и выделений под пиксели.
+fuzz_target!(|data: &[u8]| {
+    let _ = ImageBlp::from_buf(data);
+});
@@ -0,0 +1,29 @@
+use crate::cli::command::default_output::default_output;
+use crate::cli::command::target_version::TargetVersion;
+use crate::cli::command::write_output::write_output;
+use crate::core::image::ImageBlp;
+use crate::error::error::BlpError;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use std::path::{Path, PathBuf};
+
+/// BLP1 ⇄ BLP2 без перекодирования мипов.
+pub fn convert_version(input: &Path, output: Option<&PathBuf>, to: TargetVersion) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+    let target = to.version();
+    let bytes = img.convert_version(&data, target)?;
+    println!("{:?} → {:?}: {} → {} bytes", img.version, target, data.len(), bytes.len());
+
+    let out_path: PathBuf = match output {
+        Some(p) => p.clone(),
+        None => default_output(
+            input,
+            match to {
+                TargetVersion::Blp1 => "blp1.blp",
+                TargetVersion::Blp2 => "blp2.blp",
+            },
+        ),
+    };
+    write_output(&out_path, &bytes)
+}
@@ -0,0 +1,11 @@
+use crate::mpq::archive_path::ArchivePath;
+use std::path::{Path, PathBuf};
+
+/// Выходной путь по умолчанию: рядом с входом с новым расширением;
+/// для файла из архива (`War3.mpq:Textures\Foo.blp`) — `Foo.<ext>` в текущей папке.
+pub fn default_output(input: &Path, ext: &str) -> PathBuf {
+    match ArchivePath::parse(input) {
+        Some(ap) => PathBuf::from(ap.file_name()).with_extension(ext),
+        None => input.with_extension(ext),
+    }
+}
@@ -0,0 +1,11 @@
+/// Номера мипов для вывода: `0, 3, 4` или `none`.
+pub fn fmt_mips(mips: &[usize]) -> String {
+    match mips {
+        [] => "none".to_string(),
+        _ => mips
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
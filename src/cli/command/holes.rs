@@ -0,0 +1,99 @@
+use crate::cli::command::report_format::ReportFormat;
+use crate::cli::command::write_output::write_output;
+use crate::core::hole::Hole;
+use crate::core::image::ImageBlp;
+use crate::error::error::BlpError;
+use crate::ext::json_str::write_json_str;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use crate::mpq::archive_path::ArchivePath;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Дыры BLP: список, выгрузка в `extract` (по файлу на дыру), копия без дыр в `strip`.
+pub fn holes(input: &Path, format: ReportFormat, extract: Option<&PathBuf>, strip: Option<&PathBuf>) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+    let name = input.display().to_string();
+
+    let text = match format {
+        ReportFormat::Text => to_text(&name, &img, &data),
+        ReportFormat::Csv => {
+            let mut s = String::from("offset,length,raft\n");
+            for h in &img.hole_ranges {
+                let _ = writeln!(s, "{},{},{}", h.offset, h.length, h.is_raft(&data) as u8);
+            }
+            s
+        }
+        ReportFormat::Json => {
+            let mut s = String::from("{\"file\":");
+            write_json_str(&name, &mut s);
+            let _ = write!(s, ",\"total\":{},\"holes\":[", img.holes);
+            for (i, h) in img.hole_ranges.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                let _ = write!(s, "{{\"offset\":{},\"length\":{},\"raft\":{}}}", h.offset, h.length, h.is_raft(&data));
+            }
+            s.push_str("]}\n");
+            s
+        }
+    };
+    print!("{text}");
+
+    if let Some(dir) = extract {
+        fs::create_dir_all(dir)?;
+        let stem = match ArchivePath::parse(input) {
+            Some(ap) => PathBuf::from(ap.file_name()),
+            None => input.to_path_buf(),
+        };
+        let stem = stem
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "blp".into());
+        for h in &img.hole_ranges {
+            let path = dir.join(format!("{stem}.hole-{}.bin", h.offset));
+            fs::write(&path, h.bytes(&data))?;
+            eprintln!("Extracted {} bytes → {}", h.length, path.display());
+        }
+    }
+
+    if let Some(out) = strip {
+        let bytes = img.strip_holes(&data)?;
+        println!("Holes stripped ({} → {} bytes)", data.len(), bytes.len());
+        write_output(out, &bytes)?;
+    }
+    Ok(())
+}
+
+fn to_text(name: &str, img: &ImageBlp, data: &[u8]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{name}: {} hole(s), {} bytes", img.hole_ranges.len(), img.holes);
+    if img.hole_ranges.is_empty() {
+        return out;
+    }
+    let _ = writeln!(out, "  {:>10}  {:>8}  preview", "offset", "length");
+    for h in &img.hole_ranges {
+        let _ = writeln!(out, "  {:>10}  {:>8}  {}", h.offset, h.length, preview(h, data));
+    }
+    out
+}
+
+/// Первые 16 байт: hex и ASCII; маркер RAFT подписывается.
+fn preview(h: &Hole, data: &[u8]) -> String {
+    let bytes = h.bytes(data);
+    let head = &bytes[..bytes.len().min(16)];
+    let hex = head
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ascii: String = head
+        .iter()
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+        .collect();
+    let more = if bytes.len() > head.len() { "…" } else { "" };
+    let raft = if h.is_raft(data) { "  [RAFT marker]" } else { "" };
+    format!("{hex:<47}  {ascii}{more}{raft}")
+}
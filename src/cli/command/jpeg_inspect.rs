@@ -0,0 +1,164 @@
+use crate::cli::command::report_format::ReportFormat;
+use crate::core::image::ImageBlp;
+use crate::core::inspect::jpeg_inspect::JpegInspect;
+use crate::core::types::TextureType;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::ext::json_str::write_json_str;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use crate::mpq::archive_path::ArchivePath;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Разбор JPEG-заголовков: файлы (в т. ч. `archive.mpq:path`) и каталоги рекурсивно.
+///
+/// Явно указанный файл, который не разбирается, — ошибка команды. В каталогах берутся только JPEG-BLP,
+/// битые попадают в отчёт. Текстовый отчёт по каталогу показывает только отклоняющиеся файлы и итог.
+pub fn jpeg_inspect(inputs: &[PathBuf], format: ReportFormat, out: Option<&PathBuf>) -> Result<(), BlpError> {
+    let mut done: Vec<(String, JpegInspect)> = Vec::new();
+    let mut failed: Vec<(String, BlpError)> = Vec::new();
+    let mut any_dir = false;
+
+    for input in inputs {
+        if ArchivePath::parse(input).is_none() && input.is_dir() {
+            any_dir = true;
+            for entry in WalkDir::new(input)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(Result::ok)
+            {
+                let path = entry.path();
+                let is_blp = path
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("blp"));
+                if !entry.file_type().is_file() || !is_blp {
+                    continue;
+                }
+                let name = path.display().to_string();
+                match inspect_file(path) {
+                    Ok(Some(r)) => done.push((name, r)),
+                    Ok(None) => {}
+                    Err(e) => failed.push((name, e)),
+                }
+            }
+        } else {
+            let r = inspect_file(input)?.ok_or_else(|| BlpError::new(ErrorKind::InvalidArgument, "error-inspect-not-jpeg"))?;
+            done.push((input.display().to_string(), r));
+        }
+    }
+
+    let text = match format {
+        ReportFormat::Text => to_text(&done, &failed, any_dir),
+        ReportFormat::Csv => {
+            let mut s = JpegInspect::CSV_HEADER.to_string();
+            for (name, r) in &done {
+                s.push_str(&r.to_csv(name));
+            }
+            s
+        }
+        ReportFormat::Json => {
+            let mut s = String::from("{\"files\":[");
+            for (i, (name, r)) in done.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                s.push_str(&r.to_json(name));
+            }
+            s.push_str("],\"failed\":[");
+            for (i, (name, e)) in failed.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
+                }
+                s.push_str("{\"file\":");
+                write_json_str(name, &mut s);
+                let _ = write!(s, ",\"kind\":\"{}\",\"key\":\"{}\"}}", e.kind.as_str(), e.key);
+            }
+            s.push_str("]}\n");
+            s
+        }
+    };
+    match out {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/// `None` — BLP без JPEG (DIRECT или обычная картинка).
+fn inspect_file(path: &Path) -> Result<Option<JpegInspect>, BlpError> {
+    let data = path.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+    if img.texture_type != TextureType::JPEG {
+        return Ok(None);
+    }
+    img.jpeg_inspect(&data).map(Some)
+}
+
+fn deviates(r: &JpegInspect) -> bool {
+    // своя SOF у каждого мипа — обычная раскладка, отклонением не считается
+    !r.all_equal_except_dims() || r.sof_mismatches() > 0
+}
+
+fn to_text(done: &[(String, JpegInspect)], failed: &[(String, BlpError)], summary_only: bool) -> String {
+    let mut out = String::new();
+    for (name, r) in done {
+        if summary_only && !deviates(r) {
+            continue;
+        }
+        let yes = |b: bool| if b { "yes" } else { "no" };
+        let _ = writeln!(out, "{name}");
+        let _ = writeln!(
+            out,
+            "  shared header: {} bytes, mips: {}, headers equal: {}, equal except SOF dims: {}, SOF mismatches: {}",
+            r.shared_header_len,
+            r.mips.len(),
+            yes(r.all_equal()),
+            yes(r.all_equal_except_dims()),
+            r.sof_mismatches()
+        );
+        let _ = writeln!(out, "  {:>3}  {:>11}  {:>11}  {:>6}  {:>7}  {:<8}  {:>7}  mip0", "mip", "blp", "sof", "sof@", "hdr len", "sha1", "lcp");
+        for m in &r.mips {
+            let sof = m.header.as_ref().ok().and_then(|h| h.sof);
+            let eq = match (m.header_eq_mip0, m.header_eq_mip0_except_dims) {
+                (true, _) => "equal",
+                (false, true) => "dims differ",
+                (false, false) => "differs",
+            };
+            let _ = writeln!(
+                out,
+                "  {:>3}  {:>11}  {:>11}  {:>6}  {:>7}  {:<8}  {:>7}  {}{}",
+                m.mip,
+                format!("{}×{}", m.width, m.height),
+                sof.map(|s| format!("{}×{}", s.width, s.height))
+                    .unwrap_or_else(|| "-".into()),
+                sof.map(|s| s.pos.to_string())
+                    .unwrap_or_else(|| "-".into()),
+                m.header_len,
+                if m.header.is_ok() { &m.header_sha1[..8] } else { "-" },
+                m.lcp_to_mip0
+                    .map(|l| l.to_string())
+                    .unwrap_or_else(|| "-".into()),
+                eq,
+                m.header
+                    .as_ref()
+                    .err()
+                    .map(|e| format!(" ({})", e.key))
+                    .unwrap_or_default()
+            );
+        }
+    }
+    if summary_only {
+        let _ = writeln!(out, "\n===== JPEG header summary =====");
+        let _ = writeln!(out, "Inspected files:          {}", done.len());
+        let _ = writeln!(out, "All headers equal:        {}", done.iter().filter(|(_, r)| r.all_equal()).count());
+        let _ = writeln!(out, "Equal except SOF dims:    {}", done.iter().filter(|(_, r)| r.all_equal_except_dims()).count());
+        let _ = writeln!(out, "SOF dims mismatch (any):  {}", done.iter().filter(|(_, r)| r.sof_mismatches() > 0).count());
+        let _ = writeln!(out, "Failed:                   {}", failed.len());
+        for (name, e) in failed {
+            let _ = writeln!(out, "  {name}: {e}");
+        }
+    }
+    out
+}
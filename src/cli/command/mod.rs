@@ -1,2 +1,16 @@
+pub mod convert_version;
+pub mod default_output;
+pub mod fmt_mips;
+pub mod holes;
+pub mod jpeg_inspect;
+pub mod optimize;
+pub mod orientation_arg;
+pub mod repair;
+pub mod quality;
+pub mod report_format;
+pub mod scan;
+pub mod target_version;
 pub mod to_blp;
 pub mod to_png;
+pub mod transform;
+pub mod write_output;
@@ -0,0 +1,37 @@
+use crate::cli::command::default_output::default_output;
+use crate::cli::command::fmt_mips::fmt_mips;
+use crate::cli::command::write_output::write_output;
+use crate::core::image::ImageBlp;
+use crate::error::error::BlpError;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use std::path::{Path, PathBuf};
+
+/// JPEG-BLP с оптимальными таблицами Хаффмана и без дыр; пиксели сверяются побайтно.
+pub fn optimize(input: &Path, output: Option<&PathBuf>) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+    let o = img.optimize_jpeg(&data)?;
+
+    println!("Optimized mips: {}", fmt_mips(&o.optimized));
+    println!("Kept as is: {}", fmt_mips(&o.kept));
+    if !o.dropped.is_empty() {
+        eprintln!("Warning: mips {} lie outside the file and were dropped", fmt_mips(&o.dropped));
+    }
+    let saved = data.len().saturating_sub(o.bytes.len());
+    println!(
+        "{} → {} bytes, saved {saved} ({:.1}%), pixels identical",
+        data.len(),
+        o.bytes.len(),
+        saved as f64 * 100.0 / data.len().max(1) as f64
+    );
+    if o.metadata > 0 {
+        println!("Metadata chunk kept ({} bytes)", o.metadata);
+    }
+
+    let out_path: PathBuf = match output {
+        Some(p) => p.clone(),
+        None => default_output(input, "optimized.blp"),
+    };
+    write_output(&out_path, &o.bytes)
+}
@@ -0,0 +1,38 @@
+use crate::core::transform::orientation::{Flip, Rotation};
+
+/// `--rotate` для `transform`: градусы по часовой стрелке.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RotateArg {
+    #[value(name = "90")]
+    R90,
+    #[value(name = "180")]
+    R180,
+    #[value(name = "270")]
+    R270,
+}
+
+impl RotateArg {
+    pub fn rotation(self) -> Rotation {
+        match self {
+            RotateArg::R90 => Rotation::Rot90,
+            RotateArg::R180 => Rotation::Rot180,
+            RotateArg::R270 => Rotation::Rot270,
+        }
+    }
+}
+
+/// `--flip` для `transform`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FlipArg {
+    H,
+    V,
+}
+
+impl FlipArg {
+    pub fn flip(self) -> Flip {
+        match self {
+            FlipArg::H => Flip::Horizontal,
+            FlipArg::V => Flip::Vertical,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::core::image::ImageBlp;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use std::path::Path;
+
+/// Качество IJG по таблицам DQT; `tables` — ещё и сами таблицы 8×8.
+pub fn quality(input: &Path, tables: bool) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+    let e = img
+        .quality_estimate
+        .ok_or_else(|| BlpError::new(ErrorKind::InvalidArgument, "error-quality-no-dqt"))?;
+
+    if e.exact {
+        println!("{}: quality {} (exact IJG tables)", input.display(), e.quality);
+    } else {
+        println!("{}: quality ≈ {} (deviation {})", input.display(), e.quality, e.error);
+    }
+    if tables {
+        for t in &e.tables {
+            println!("DQT {} ({}-bit):", t.id, if t.wide { 16 } else { 8 });
+            for row in t.values.chunks(8) {
+                let line: Vec<String> = row
+                    .iter()
+                    .map(|v| format!("{v:>4}"))
+                    .collect();
+                println!("  {}", line.join(""));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::cli::command::default_output::default_output;
+use crate::cli::command::fmt_mips::fmt_mips;
+use crate::cli::command::write_output::write_output;
+use crate::core::image::ImageBlp;
+use crate::core::repair::fixup::Fixup;
+use crate::error::error::BlpError;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use std::path::{Path, PathBuf};
+
+/// BLP → нормализованный BLP. Без `salvage` — только раскладка JPEG (SOF по размерам мипов, общий
+/// заголовок, мипы подряд); с `salvage` — эвристики для битых и «защищённых» файлов, в т. ч. DIRECT.
+pub fn repair(input: &Path, output: Option<&PathBuf>, salvage: bool) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+
+    let bytes = if salvage {
+        let s = img.salvage(&data)?;
+        for f in &s.fixups {
+            println!("Fixup: {}", describe(f));
+        }
+        if s.fixups.is_empty() {
+            println!("Fixup: none needed");
+        }
+        println!("Recovered mips: {}", fmt_mips(&s.recovered));
+        s.bytes
+    } else {
+        let r = img.repair_jpeg(&data)?;
+        println!("SOF dimensions fixed in mips: {}", fmt_mips(&r.sof_fixed));
+        println!("Dropped mips: {}", fmt_mips(&r.dropped));
+        println!("Shared JPEG header: {} → {} bytes", r.shared_header_len.0, r.shared_header_len.1);
+        r.bytes
+    };
+
+    let out_path: PathBuf = match output {
+        Some(p) => p.clone(),
+        None => default_output(input, "repaired.blp"),
+    };
+    write_output(&out_path, &bytes)
+}
+
+fn describe(f: &Fixup) -> String {
+    match f {
+        Fixup::EntryDropped { mip } => format!("mip {mip}: entry outside the file or the mip chain, dropped"),
+        Fixup::RangeClamped { mip, length, clamped } => format!("mip {mip}: length {length} runs past the end of file, clamped to {clamped}"),
+        Fixup::Overlap { mip, other } => format!("mip {mip}: overlaps mip {other}"),
+        Fixup::JunkTrimmed { mip, bytes } => format!("mip {mip}: {bytes} trailing junk bytes trimmed"),
+        Fixup::TruncatedPadded { mip, bytes } => format!("mip {mip}: truncated, padded with {bytes} bytes"),
+        Fixup::SofDims { mip } => format!("mip {mip}: SOF dimensions rewritten"),
+        Fixup::Undecodable { mip, error } => format!("mip {mip}: not decodable ({}), dropped", error.key),
+        Fixup::Promoted { from } => format!("mip {from} promoted to mip 0"),
+        Fixup::MipmapFlag { was, now } => format!("mipmap flag {was} → {now}"),
+        Fixup::HolesDropped { bytes } => format!("{bytes} bytes between and after mips dropped"),
+    }
+}
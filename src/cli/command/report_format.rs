@@ -0,0 +1,7 @@
+/// Формат отчёта команд `scan` и `jpeg-inspect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+    Json,
+}
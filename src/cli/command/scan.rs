@@ -0,0 +1,51 @@
+use crate::cli::command::report_format::ReportFormat;
+use crate::core::scan::scan_dir::{ScanOptions, scan_archive, scan_dir};
+use crate::core::scan::scan_report::ScanReport;
+use crate::error::error::BlpError;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Каталог или MPQ-архив → отчёт по паспортам (в stdout или `out`).
+pub fn scan(input: &Path, format: ReportFormat, out: Option<&PathBuf>, samples: Option<&PathBuf>, examples: usize) -> Result<(), BlpError> {
+    let opts = ScanOptions { max_examples: examples, samples_dir: samples.cloned() };
+    let report = if input.is_file() { scan_archive(input, &opts)? } else { scan_dir(input, &opts)? };
+
+    let text = match format {
+        ReportFormat::Text => to_text(&report),
+        ReportFormat::Csv => report.to_csv(),
+        ReportFormat::Json => report.to_json() + "\n",
+    };
+    match out {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn to_text(report: &ScanReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Scanned {} BLP files: {} passports, {} failed.", report.files, report.groups.len(), report.failed.len());
+    if !report.groups.is_empty() {
+        let width = report
+            .groups
+            .iter()
+            .map(|g| g.passport.key().len())
+            .max()
+            .unwrap_or(0);
+        let _ = writeln!(out, "\n{:<width$}  {:>7}  Examples", "Passport", "Count");
+        for g in &report.groups {
+            let _ = writeln!(out, "{:<width$}  {:>7}  {}", g.passport.key(), g.count, g.examples.first().map(String::as_str).unwrap_or(""));
+            for e in g.examples.iter().skip(1) {
+                let _ = writeln!(out, "{:<width$}  {:>7}  {e}", "", "");
+            }
+        }
+    }
+    if !report.failed.is_empty() {
+        let _ = writeln!(out, "\nFailed:");
+        for f in &report.failed {
+            let _ = writeln!(out, "  {}: {}", f.name, f.error);
+        }
+    }
+    out
+}
@@ -0,0 +1,17 @@
+use crate::core::types::Version;
+
+/// Версия контейнера для `convert-version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TargetVersion {
+    Blp1,
+    Blp2,
+}
+
+impl TargetVersion {
+    pub fn version(self) -> Version {
+        match self {
+            TargetVersion::Blp1 => Version::BLP1,
+            TargetVersion::Blp2 => Version::BLP2,
+        }
+    }
+}
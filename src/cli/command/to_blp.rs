@@ -1,20 +1,59 @@
+use crate::cli::command::default_output::default_output;
+use crate::cli::command::write_output::write_output;
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::encode::blp::EncodeOptions;
 use crate::core::image::ImageBlp;
+use crate::core::metadata::BlpMetadata;
 use crate::error::error::BlpError;
-use std::fs;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use crate::mpq::archive_path::ArchivePath;
+use sha1::{Digest, Sha1};
 use std::path::{Path, PathBuf};
 
-pub fn to_blp(input: &Path, output: Option<&PathBuf>, quality: u8, mip_visible: &[bool]) -> Result<(), BlpError> {
-    input.try_exists()?;
-    let data = fs::read(input)?;
+pub fn to_blp(input: &Path, output: Option<&PathBuf>, quality: u8, keep_quality: bool, fill_mips: bool, mip_visible: &[bool], author: Option<&str>) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
     let mut img = ImageBlp::from_buf(&data)?;
-    img.decode(&data, mip_visible)?;
+
+    // качество исходного JPEG-BLP: ни раздувания, ни лишней потери
+    let quality = match (keep_quality, &img.quality_estimate) {
+        (true, Some(e)) => {
+            println!("Using source quality {}{}", if e.exact { "" } else { "≈ " }, e.quality);
+            e.quality
+        }
+        (true, None) => {
+            eprintln!("Warning: input has no JPEG quantization tables; using quality {quality}");
+            quality
+        }
+        (false, _) => quality,
+    };
+    img.decode_with(&data, mip_visible, &DecodeOptions { keep_jpeg: keep_quality, ..DecodeOptions::with_fallback() })?;
 
     let out_path: PathBuf = match output {
         Some(p) => p.clone(),
-        None => input.with_extension("blp"),
+        None => default_output(input, "blp"),
+    };
+
+    // метаданные: откуда текстура и когда собрана
+    let mut metadata = BlpMetadata::new();
+    let source = match ArchivePath::parse(input) {
+        Some(ap) => ap.file_name().to_string(),
+        None => input
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
     };
+    metadata
+        .set(BlpMetadata::SOURCE, source)
+        .set(BlpMetadata::SOURCE_SHA1, hex::encode(Sha1::digest(&data)))
+        .set(BlpMetadata::TIMESTAMP, chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    if let Some(author) = author {
+        metadata.set(BlpMetadata::AUTHOR, author);
+    }
+    let ctx = img.encode_blp_with(&EncodeOptions { quality, metadata, reuse_source: keep_quality, fill_mips, ..Default::default() }, mip_visible)?;
+    if ctx.source_tables_lost {
+        eprintln!("Warning: the JPEG backend cannot reproduce the source quantization tables; re-encoded mips use quality {quality}");
+    }
 
-    img.export_blp(&out_path, quality, mip_visible)?;
-    println!("Saved BLP → {}", out_path.display());
-    Ok(())
+    write_output(&out_path, &ctx.bytes)
 }
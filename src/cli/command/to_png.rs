@@ -1,17 +1,19 @@
+use crate::cli::command::default_output::default_output;
+use crate::core::decode::decode_options::DecodeOptions;
 use crate::core::image::ImageBlp;
 use crate::error::error::BlpError;
-use std::fs;
+use crate::ext::path::ensure_readable::EnsureReadable;
 use std::path::{Path, PathBuf};
 
 pub fn to_png(input: &Path, output: Option<&PathBuf>) -> Result<(), BlpError> {
-    input.try_exists()?;
-    let data = fs::read(input)?;
-    let mut img = ImageBlp::from_buf(&data).map_err(|e| e.ctx("blp.decode-failed"))?;
-    img.decode(&data, &[true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false])?;
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let mut img = ImageBlp::from_buf(&data).map_err(|e| e.ctx("error-blp-decode-failed"))?;
+    img.decode_with(&data, &[true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, false], &DecodeOptions::with_fallback())?;
 
     let out_path: PathBuf = match output {
         Some(p) => p.clone(),
-        None => input.with_extension("png"),
+        None => default_output(input, "png"),
     };
 
     img.export_png(img.mipmaps.get(0).unwrap(), &out_path)?;
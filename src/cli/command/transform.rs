@@ -0,0 +1,34 @@
+use crate::cli::command::default_output::default_output;
+use crate::cli::command::fmt_mips::fmt_mips;
+use crate::cli::command::orientation_arg::{FlipArg, RotateArg};
+use crate::cli::command::write_output::write_output;
+use crate::core::encode::blp::EncodeOptions;
+use crate::core::image::ImageBlp;
+use crate::core::transform::orientation::Orientation;
+use crate::error::error::BlpError;
+use crate::ext::path::ensure_readable::EnsureReadable;
+use std::path::{Path, PathBuf};
+
+/// Поворот/отражение JPEG-BLP без перекодирования; мипы с неполными MCU перекодируются с `quality`.
+pub fn transform(input: &Path, output: Option<&PathBuf>, rotate: Option<RotateArg>, flip: Option<FlipArg>, quality: u8) -> Result<(), BlpError> {
+    input.ensure_readable()?;
+    let data = input.read_all()?;
+    let img = ImageBlp::from_buf(&data)?;
+
+    let orientation = Orientation { rotate: rotate.map(RotateArg::rotation), flip: flip.map(FlipArg::flip) };
+    let t = img.transform_jpeg(&data, orientation, &EncodeOptions { quality, ..Default::default() })?;
+
+    println!("Lossless mips: {}", fmt_mips(&t.lossless));
+    if !t.reencoded.is_empty() {
+        eprintln!("Warning: mips {} are not MCU-aligned for this transform and were re-encoded (quality {quality})", fmt_mips(&t.reencoded));
+    }
+    if !t.dropped.is_empty() {
+        eprintln!("Warning: mips {} lie outside the file and were dropped", fmt_mips(&t.dropped));
+    }
+
+    let out_path: PathBuf = match output {
+        Some(p) => p.clone(),
+        None => default_output(input, "transformed.blp"),
+    };
+    write_output(&out_path, &t.bytes)
+}
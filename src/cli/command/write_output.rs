@@ -0,0 +1,22 @@
+use crate::error::error::BlpError;
+use crate::mpq::archive_path::ArchivePath;
+use std::fs;
+use std::path::Path;
+
+/// Записать готовый BLP: в файл (с созданием папок) или, для `map.w3x:war3mapImported\foo.blp`, в архив.
+pub fn write_output(out_path: &Path, bytes: &[u8]) -> Result<(), BlpError> {
+    if let Some(ap) = ArchivePath::parse(out_path) {
+        ap.write(bytes)?;
+        println!("Saved BLP → {ap}");
+        return Ok(());
+    }
+    if let Some(parent) = out_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out_path, bytes)?;
+    println!("Saved BLP → {}", out_path.display());
+    Ok(())
+}
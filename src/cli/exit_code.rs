@@ -0,0 +1,39 @@
+use crate::error::error_kind::ErrorKind;
+
+/// Коды выхода CLI. Таблица продублирована в README («Exit codes») — менять только вместе.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ExitCode {
+    // В UI-сборке успешный выход — обычный возврат из `main`.
+    #[cfg_attr(feature = "ui", allow(dead_code))]
+    Ok = 0,
+    /// Внутренняя ошибка, UI, буфер обмена.
+    Internal = 1,
+    /// Неверные аргументы командной строки.
+    Usage = 2,
+    /// Файл не найден, нет прав, ошибка чтения/записи.
+    Io = 3,
+    /// Не BLP / неизвестная версия или тип сжатия.
+    Unsupported = 4,
+    /// Файл повреждён: обрезан, битый заголовок, JPEG или MPQ-архив, превышены лимиты.
+    Corrupt = 5,
+    /// Не удалось закодировать BLP.
+    Encode = 6,
+}
+
+impl ExitCode {
+    pub fn from_kind(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::Io => ExitCode::Io,
+            ErrorKind::UnsupportedVersion | ErrorKind::UnsupportedCompression => ExitCode::Unsupported,
+            ErrorKind::Truncated | ErrorKind::InvalidHeader | ErrorKind::InvalidJpeg | ErrorKind::InvalidImage | ErrorKind::InvalidArchive | ErrorKind::MipMissing | ErrorKind::LimitExceeded => ExitCode::Corrupt,
+            ErrorKind::InvalidArgument => ExitCode::Usage,
+            ErrorKind::Encode => ExitCode::Encode,
+            ErrorKind::Platform | ErrorKind::Internal => ExitCode::Internal,
+        }
+    }
+
+    pub fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
+}
@@ -0,0 +1,101 @@
+use crate::core::decode::decode_limits::DecodeLimits;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use image::error::{DecodingError, ImageFormatHint};
+use image::{ColorType, ImageDecoder, ImageError, ImageResult};
+use std::io::Read;
+
+/// `image::ImageDecoder` over a BLP container.
+///
+/// By default yields mip 0 as RGBA8; [`BlpDecoder::set_mip`] selects another level.
+/// Pixels are produced by the regular `ImageBlp::decode` (JPEG / DIRECT paths).
+pub struct BlpDecoder {
+    buf: Vec<u8>,
+    blp: ImageBlp,
+    mip: usize,
+}
+
+impl BlpDecoder {
+    /// Reads the whole stream and parses the BLP header. No pixels are decoded yet.
+    pub fn new<R: Read>(mut reader: R) -> Result<Self, BlpError> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        if buf.len() < 3 || &buf[..3] != b"BLP" {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-blp-not-blp"));
+        }
+        let blp = ImageBlp::from_buf_blp(&buf, &DecodeLimits::default())?;
+        Ok(Self { buf, blp, mip: 0 })
+    }
+
+    /// Parsed header and mip table.
+    pub fn blp(&self) -> &ImageBlp {
+        &self.blp
+    }
+
+    /// Number of mip levels that carry data.
+    pub fn mip_count(&self) -> usize {
+        self.blp
+            .mipmaps
+            .iter()
+            .take_while(|m| m.length > 0)
+            .count()
+    }
+
+    /// Selects the mip level returned by `dimensions` / `read_image`.
+    pub fn set_mip(&mut self, level: usize) -> Result<(), BlpError> {
+        let present = self
+            .blp
+            .mipmaps
+            .get(level)
+            .map(|m| m.length > 0 && m.width > 0 && m.height > 0)
+            .unwrap_or(false);
+        if !present {
+            return Err(BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", level));
+        }
+        self.mip = level;
+        Ok(())
+    }
+
+    /// Builder-style variant of [`BlpDecoder::set_mip`].
+    pub fn with_mip(mut self, level: usize) -> Result<Self, BlpError> {
+        self.set_mip(level)?;
+        Ok(self)
+    }
+}
+
+impl ImageDecoder for BlpDecoder {
+    fn dimensions(&self) -> (u32, u32) {
+        let m = &self.blp.mipmaps[self.mip];
+        (m.width, m.height)
+    }
+
+    fn color_type(&self) -> ColorType {
+        ColorType::Rgba8
+    }
+
+    fn read_image(mut self, buf: &mut [u8]) -> ImageResult<()> {
+        let mut mip_visible = [false; MAX_MIPS];
+        mip_visible[self.mip] = true;
+
+        self.blp
+            .decode(&self.buf, &mip_visible)
+            .map_err(decoding_error)?;
+
+        let img = self.blp.mipmaps[self.mip]
+            .image
+            .take()
+            .ok_or_else(|| decoding_error(BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", self.mip)))?;
+
+        buf.copy_from_slice(img.as_raw());
+        Ok(())
+    }
+
+    fn read_image_boxed(self: Box<Self>, buf: &mut [u8]) -> ImageResult<()> {
+        (*self).read_image(buf)
+    }
+}
+
+pub(crate) fn decoding_error(e: BlpError) -> ImageError {
+    ImageError::Decoding(DecodingError::new(ImageFormatHint::Name("BLP".to_string()), e))
+}
@@ -0,0 +1,69 @@
+use crate::core::image::ImageBlp;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use image::error::{EncodingError, ImageFormatHint, UnsupportedError, UnsupportedErrorKind};
+use image::{DynamicImage, ExtendedColorType, ImageBuffer, ImageEncoder, ImageError, ImageResult};
+use std::io::Write;
+
+/// `image::ImageEncoder` that writes a JPEG-BLP1 container.
+///
+/// Accepts 8-bit L / LA / RGB / RGBA; the mip chain is built like `from_rgba`.
+pub struct BlpEncoder<W: Write> {
+    w: W,
+    quality: u8,
+    mip_visible: Vec<bool>,
+}
+
+impl<W: Write> BlpEncoder<W> {
+    /// Quality 100, all mips.
+    pub fn new(w: W) -> Self {
+        Self { w, quality: 100, mip_visible: Vec::new() }
+    }
+
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// `mip_visible[i] == false` → mip `i` is not written. Missing indices are treated as `true`.
+    pub fn with_mip_visible(mut self, mip_visible: &[bool]) -> Self {
+        self.mip_visible = mip_visible.to_vec();
+        self
+    }
+}
+
+impl<W: Write> ImageEncoder for BlpEncoder<W> {
+    fn write_image(mut self, buf: &[u8], width: u32, height: u32, color_type: ExtendedColorType) -> ImageResult<()> {
+        let img = match color_type {
+            ExtendedColorType::Rgba8 => ImageBuffer::from_raw(width, height, buf.to_vec()).map(DynamicImage::ImageRgba8),
+            ExtendedColorType::Rgb8 => ImageBuffer::from_raw(width, height, buf.to_vec()).map(DynamicImage::ImageRgb8),
+            ExtendedColorType::La8 => ImageBuffer::from_raw(width, height, buf.to_vec()).map(DynamicImage::ImageLumaA8),
+            ExtendedColorType::L8 => ImageBuffer::from_raw(width, height, buf.to_vec()).map(DynamicImage::ImageLuma8),
+            other => {
+                return Err(ImageError::Unsupported(UnsupportedError::from_format_and_kind(
+                    ImageFormatHint::Name("BLP".to_string()),
+                    UnsupportedErrorKind::Color(other),
+                )));
+            }
+        };
+        let img = img.ok_or_else(|| {
+            encoding_error(
+                BlpError::new(ErrorKind::InvalidImage, "error-image-empty")
+                    .with_arg("width", width)
+                    .with_arg("height", height),
+            )
+        })?;
+
+        let blp = ImageBlp::from_rgba(img.into_rgba8(), &self.mip_visible).map_err(encoding_error)?;
+        let ctx = blp
+            .encode_blp(self.quality, &self.mip_visible)
+            .map_err(encoding_error)?;
+        self.w.write_all(&ctx.bytes)?;
+        Ok(())
+    }
+}
+
+fn encoding_error(e: BlpError) -> ImageError {
+    ImageError::Encoding(EncodingError::new(ImageFormatHint::Name("BLP".to_string()), e))
+}
+
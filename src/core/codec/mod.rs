@@ -0,0 +1,3 @@
+pub mod blp_decoder;
+pub mod blp_encoder;
+pub mod register_blp_format;
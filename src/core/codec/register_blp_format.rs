@@ -0,0 +1,55 @@
+use crate::core::codec::blp_decoder::{BlpDecoder, decoding_error};
+use crate::core::codec::blp_encoder::BlpEncoder;
+use crate::error::error::BlpError;
+use image::{DynamicImage, ImageDecoder, ImageEncoder};
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+
+/// Подключает BLP к `image::open` / `image::load_from_memory` / `ImageReader`.
+///
+/// Регистрирует декодер для расширения `.blp` и сигнатуру `BLP` для угадывания формата.
+/// Возвращает `false`, если хук для `.blp` уже был зарегистрирован (повторный вызов безвреден).
+///
+/// В `image` нет хуков кодирования, поэтому `DynamicImage::save("x.blp")` по-прежнему
+/// вернёт `Unsupported` — для записи используйте [`SaveBlp::save_blp`] или [`BlpEncoder`].
+pub fn register_blp_format() -> bool {
+    let ext = OsString::from("blp");
+    if image::hooks::decoding_hook_registered(&ext) {
+        return false;
+    }
+    let fresh = image::hooks::register_decoding_hook(
+        ext.clone(),
+        Box::new(|r| {
+            let dec = BlpDecoder::new(r).map_err(decoding_error)?;
+            Ok(Box::new(dec) as Box<dyn ImageDecoder>)
+        }),
+    );
+    if fresh {
+        image::hooks::register_format_detection_hook(ext, b"BLP", None);
+    }
+    fresh
+}
+
+/// Запись `DynamicImage` в BLP — замена `DynamicImage::save` для `.blp`.
+pub trait SaveBlp {
+    fn save_blp(&self, path: impl AsRef<Path>, quality: u8) -> Result<(), BlpError>;
+}
+
+impl SaveBlp for DynamicImage {
+    fn save_blp(&self, path: impl AsRef<Path>, quality: u8) -> Result<(), BlpError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let rgba = self.to_rgba8();
+        let mut bytes = Vec::new();
+        BlpEncoder::new(&mut bytes)
+            .with_quality(quality)
+            .write_image(rgba.as_raw(), rgba.width(), rgba.height(), image::ExtendedColorType::Rgba8)?;
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+}
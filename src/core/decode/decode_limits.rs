@@ -0,0 +1,89 @@
+use crate::core::mipmap::Mipmap;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use std::io::Cursor;
+
+/// Лимиты для недоверенного ввода (карты из интернета).
+///
+/// Проверяются по заголовкам — до любого выделения памяти под пиксели:
+/// в `ImageBlp::from_buf_with`, `decode_with` (через `DecodeOptions::limits`) и перед JPEG-декодером (по SOF).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Сумма пикселей по всем мипам с данными.
+    pub max_pixels: u64,
+    /// Наибольшее единичное выделение под пиксели, в байтах.
+    pub max_alloc: u64,
+    /// Наибольшее число мипов с данными.
+    pub max_mips: usize,
+}
+
+impl Default for DecodeLimits {
+    /// Хватает на 8192×8192 с полной цепочкой мипов.
+    fn default() -> Self {
+        Self {
+            max_width: 8192,
+            max_height: 8192,
+            max_pixels: 96 << 20,
+            max_alloc: 512 << 20,
+            max_mips: 16,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Без ограничений — только для доверенных файлов.
+    pub fn unlimited() -> Self {
+        Self {
+            max_width: u32::MAX,
+            max_height: u32::MAX,
+            max_pixels: u64::MAX,
+            max_alloc: u64::MAX,
+            max_mips: usize::MAX,
+        }
+    }
+
+    /// Размеры кадра и RGBA-буфер под него.
+    pub(crate) fn check_dims(&self, w: u32, h: u32) -> Result<(), BlpError> {
+        check("width", w as u64, self.max_width as u64)?;
+        check("height", h as u64, self.max_height as u64)?;
+        self.check_alloc(w as u64 * h as u64 * 4)
+    }
+
+    pub(crate) fn check_alloc(&self, bytes: u64) -> Result<(), BlpError> {
+        check("alloc", bytes, self.max_alloc)
+    }
+
+    /// `ImageReader` для внешних картинок: формат угадан, лимиты переданы в `image`.
+    pub(crate) fn image_reader<'a>(&self, buf: &'a [u8]) -> Result<image::ImageReader<Cursor<&'a [u8]>>, BlpError> {
+        let mut reader = image::ImageReader::new(Cursor::new(buf)).with_guessed_format()?;
+        let mut l = image::Limits::default();
+        l.max_image_width = Some(self.max_width);
+        l.max_image_height = Some(self.max_height);
+        l.max_alloc = Some(self.max_alloc);
+        reader.limits(l);
+        Ok(reader)
+    }
+
+    /// Заголовок BLP: размеры, число мипов с данными и их суммарная площадь.
+    pub(crate) fn check_mips(&self, width: u32, height: u32, mipmaps: &[Mipmap]) -> Result<(), BlpError> {
+        self.check_dims(width, height)?;
+        let present = mipmaps.iter().filter(|m| m.length > 0);
+        check("mips", present.clone().count() as u64, self.max_mips as u64)?;
+        let pixels = present
+            .map(|m| m.width as u64 * m.height as u64)
+            .sum();
+        check("pixels", pixels, self.max_pixels)
+    }
+}
+
+fn check(what: &'static str, value: u64, max: u64) -> Result<(), BlpError> {
+    if value > max {
+        return Err(BlpError::new(ErrorKind::LimitExceeded, "error-limits-exceeded")
+            .with_arg("what", what)
+            .with_arg("value", value)
+            .with_arg("max", max));
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::decode::direct::{check_alpha_bits, write_direct_mip};
+use crate::core::decode::jpeg::{decode_jpeg_raw, write_jpeg_pixels};
+use crate::core::decode::pixel_layout::{PixelLayout, with_store};
+use crate::core::decode::utils::rgba_to_pixels::rgba_to;
+use crate::core::image::ImageBlp;
+use crate::core::types::{SourceKind, TextureType};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+impl ImageBlp {
+    /// Decodes one mip straight into `out` in the requested `layout` — no intermediate `RgbaImage`.
+    ///
+    /// - `out` must hold at least `layout.buffer_len(w, h)` bytes; only that prefix is written,
+    ///   rows are tightly packed (stride = `w * layout.bytes_per_pixel()`).
+    /// - Returns the mip dimensions `(w, h)`.
+    /// - For BLP sources pixels come from `buf`; for external images (`SourceKind::Image`)
+    ///   from `Mipmap::image`, which must have been decoded already.
+    pub fn decode_mip_into(&self, buf: &[u8], mip: usize, layout: PixelLayout, out: &mut [u8], opts: &DecodeOptions) -> Result<(u32, u32), BlpError> {
+        let m = self
+            .mipmaps
+            .get(mip)
+            .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", mip as u32))?;
+
+        match self.source {
+            SourceKind::Image => {
+                let img = m
+                    .image
+                    .as_ref()
+                    .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", mip as u32))?;
+                let (w, h) = img.dimensions();
+                let out = out_prefix(out, layout, w, h)?;
+                with_store!(layout, rgba_to(img.as_raw(), out));
+                Ok((w, h))
+            }
+            SourceKind::Blp => {
+                let data = m
+                    .offset
+                    .checked_add(m.length)
+                    .filter(|&end| m.length > 0 && end <= buf.len())
+                    .map(|end| &buf[m.offset..end])
+                    .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", mip as u32))?;
+
+                match self.texture_type {
+                    TextureType::DIRECT => {
+                        check_alpha_bits(self.alpha_bits)?;
+                        let palette = self.read_palette(buf)?;
+                        let (w, h) = (m.width, m.height);
+                        let out = out_prefix(out, layout, w, h)?;
+                        with_store!(layout, write_direct_mip(data, w, h, &palette, self.alpha_bits, out))?;
+                        Ok((w, h))
+                    }
+                    TextureType::JPEG => {
+                        let jpeg = decode_jpeg_raw(self.jpeg_header(buf)?, data, mip, (m.width, m.height), opts)?;
+                        // The stream's own SOF is authoritative, as in `decode_jpeg`.
+                        let (w, h) = (jpeg.width, jpeg.height);
+                        let out = out_prefix(out, layout, w, h)?;
+                        with_store!(layout, write_jpeg_pixels(&jpeg, mip, self.alpha_bits == 0, out))?;
+                        Ok((w, h))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `out[..layout.buffer_len(w, h)]`, or an error if the caller's buffer is too small.
+fn out_prefix(out: &mut [u8], layout: PixelLayout, w: u32, h: u32) -> Result<&mut [u8], BlpError> {
+    let need = layout.buffer_len(w, h);
+    let got = out.len();
+    out.get_mut(..need).ok_or_else(|| {
+        BlpError::new(ErrorKind::InvalidArgument, "error-buffer-too-small")
+            .with_arg("need", need as u64)
+            .with_arg("got", got as u64)
+    })
+}
@@ -0,0 +1,52 @@
+use crate::core::decode::decode_limits::DecodeLimits;
+use crate::core::decode::jpeg_backend::{JpegDecode, JpegDecoderBackend};
+use std::sync::Arc;
+
+/// Параметры декодирования BLP.
+#[derive(Clone)]
+pub struct DecodeOptions {
+    /// Основной JPEG-декодер.
+    pub jpeg: Arc<dyn JpegDecode>,
+    /// Запасной: пробуется для мипа, который основной не смог прочитать.
+    pub jpeg_fallback: Option<Arc<dyn JpegDecode>>,
+    /// Потоки для декодирования мипов: `0` — по числу ядер, `1` — последовательно.
+    pub threads: usize,
+    /// Лимиты для недоверенного ввода.
+    pub limits: DecodeLimits,
+    /// Переписывать размеры SOF в JPEG каждого мипа по таблице BLP. У Blizzard-BLP1 общий заголовок
+    /// хранит размеры мипа 0, и без правки малые мипы раскладываются по уровням по факту декодирования.
+    pub fix_sof_dims: bool,
+    /// Хранить JPEG-поток каждого мипа для копирования без перекодирования (`EncodeOptions::reuse_source`).
+    /// Без флага память на исходные потоки не тратится.
+    pub keep_jpeg: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self { jpeg: Arc::new(JpegDecoderBackend), jpeg_fallback: None, threads: 0, limits: DecodeLimits::default(), fix_sof_dims: false, keep_jpeg: false }
+    }
+}
+
+impl DecodeOptions {
+    /// `jpeg_decoder`, а при ошибке — TurboJPEG (если собран).
+    pub fn with_fallback() -> Self {
+        #[cfg(feature = "turbojpeg")]
+        let jpeg_fallback: Option<Arc<dyn JpegDecode>> = Some(Arc::new(crate::core::decode::turbojpeg_backend::TurboJpegBackend));
+        #[cfg(not(feature = "turbojpeg"))]
+        let jpeg_fallback = None;
+        Self { jpeg_fallback, ..Default::default() }
+    }
+}
+
+impl std::fmt::Debug for DecodeOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeOptions")
+            .field("jpeg", &self.jpeg.name())
+            .field("jpeg_fallback", &self.jpeg_fallback.as_ref().map(|b| b.name()))
+            .field("threads", &self.threads)
+            .field("limits", &self.limits)
+            .field("fix_sof_dims", &self.fix_sof_dims)
+            .field("keep_jpeg", &self.keep_jpeg)
+            .finish()
+    }
+}
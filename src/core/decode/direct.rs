@@ -1,8 +1,13 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::decode::pixel_layout::{PixelStore, Rgba8};
+use crate::core::decode::utils::palette_to_pixels::palette_to;
 use crate::core::image::ImageBlp;
+use crate::core::par_map::par_map;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use byteorder::{LittleEndian, ReadBytesExt};
 use image::RgbaImage;
-use std::io::{Cursor, Read};
+use std::io::Cursor;
 
 impl ImageBlp {
     /// DIRECT (paletted) decoding.
@@ -12,29 +17,15 @@ impl ImageBlp {
     ///
     /// - `mip_visible[i] == false` → skip decoding for that mipmap (image stays `None`).
     /// - If `mip_visible` has no entry for index `i`, we treat it as `true`.
-    pub(crate) fn decode_direct(&mut self, buf: &[u8], mip_visible: &[bool]) -> Result<(), BlpError> {
-        // --- Read palette ---
-        // Palette is located at `self.header_offset` with expected length = 256 * 4.
-        if self.header_offset + self.header_length > buf.len() {
-            return Err(BlpError::new("direct.header.oob"));
-        }
-        let mut cur = Cursor::new(&buf[..]);
-        cur.set_position(self.header_offset as u64);
-
-        let mut palette = [[0u8; 3]; 256];
-        for i in 0..256 {
-            let color = cur.read_u32::<LittleEndian>()?;
-            // Format: R = bits 16..23, G = bits 8..15, B = bits 0..7
-            let r = ((color >> 16) & 0xFF) as u8;
-            let g = ((color >> 8) & 0xFF) as u8;
-            let b = (color & 0xFF) as u8;
-            palette[i] = [r, g, b];
-        }
+    /// - Mipmaps are independent and decoded on `opts.threads` threads.
+    pub(crate) fn decode_direct(&mut self, buf: &[u8], mip_visible: &[bool], opts: &DecodeOptions) -> Result<(), BlpError> {
+        let palette = self.read_palette(buf)?;
 
         let buf_len = buf.len();
         let alpha_bits = self.alpha_bits;
 
-        // --- Process mipmaps ---
+        // --- Pick mipmaps to decode ---
+        let mut jobs: Vec<usize> = Vec::with_capacity(self.mipmaps.len());
         for i in 0..self.mipmaps.len() {
             // Check if this mipmap should be decoded
             let visible = mip_visible
@@ -54,56 +45,103 @@ impl ImageBlp {
             if off.checked_add(len).is_none() || off + len > buf_len {
                 continue; // invalid offset/length
             }
+            jobs.push(i);
+        }
 
-            cur.set_position(off as u64);
-
-            let (w, h) = (self.mipmaps[i].width, self.mipmaps[i].height);
-            let pixel_count = (w as usize) * (h as usize);
+        if !jobs.is_empty() {
+            check_alpha_bits(alpha_bits)?;
+        }
 
-            // --- Read indices (one byte per pixel) ---
-            let mut indices = vec![0u8; pixel_count];
-            cur.read_exact(&mut indices)
-                .map_err(|_| BlpError::new("direct.indices.truncated"))?;
+        // --- Process mipmaps in parallel ---
+        let mipmaps = &self.mipmaps;
+        let palette = &palette;
+        let results = par_map(
+            jobs.len(),
+            opts.threads,
+            || (),
+            |_, j| {
+                let m = &mipmaps[jobs[j]];
+                decode_direct_mip(&buf[m.offset..], m.width, m.height, palette, alpha_bits)
+            },
+        );
+        for (i, r) in jobs.into_iter().zip(results) {
+            self.mipmaps[i].image = Some(r?);
+        }
+        Ok(())
+    }
 
-            // --- Read alpha data depending on alpha_bits ---
-            let alpha_bytes = match alpha_bits {
-                0 => 0,
-                1 => (pixel_count + 7) / 8, // 1 bit per pixel
-                4 => (pixel_count + 1) / 2, // 4 bits per pixel
-                8 => pixel_count,           // 1 byte per pixel
-                _ => return Err(BlpError::new("blp.version.invalid").with_arg("msg", "unsupported alpha bits")),
-            };
-            let mut alpha_raw = vec![0u8; alpha_bytes];
-            if alpha_bytes > 0 {
-                cur.read_exact(&mut alpha_raw)
-                    .map_err(|_| BlpError::new("direct.alpha.truncated"))?;
-            }
+    /// Palette is located at `self.header_offset` with expected length = 256 * 4.
+    /// Expanded to RGBA up front (alpha = 255, overwritten per pixel if alpha_bits > 0).
+    pub(crate) fn read_palette(&self, buf: &[u8]) -> Result<[[u8; 4]; 256], BlpError> {
+        if self
+            .header_offset
+            .checked_add(self.header_length)
+            .is_none_or(|end| end > buf.len())
+        {
+            return Err(BlpError::new(ErrorKind::Truncated, "error-direct-palette-oob"));
+        }
+        let mut cur = Cursor::new(buf);
+        cur.set_position(self.header_offset as u64);
 
-            // --- Assemble RGBA image ---
-            let mut img = RgbaImage::new(w, h);
-            for p in 0..pixel_count {
-                let idx = indices[p] as usize;
-                let [r, g, b] = palette[idx];
-                let a = match alpha_bits {
-                    0 => 255,
-                    1 => {
-                        let byte = alpha_raw[p / 8];
-                        let bit = (byte >> (p % 8)) & 1;
-                        if bit == 1 { 255 } else { 0 }
-                    }
-                    4 => {
-                        let byte = alpha_raw[p / 2];
-                        let nibble = if (p & 1) == 0 { byte & 0x0F } else { byte >> 4 };
-                        (nibble << 4) | nibble
-                    }
-                    8 => alpha_raw[p],
-                    _ => 255,
-                };
-                img.get_pixel_mut((p as u32) % w, (p as u32) / w)
-                    .0 = [r, g, b, a];
-            }
-            self.mipmaps[i].image = Some(img);
+        let mut palette = [[0u8; 4]; 256];
+        for entry in palette.iter_mut() {
+            let color = cur.read_u32::<LittleEndian>()?;
+            // Format: R = bits 16..23, G = bits 8..15, B = bits 0..7
+            let r = ((color >> 16) & 0xFF) as u8;
+            let g = ((color >> 8) & 0xFF) as u8;
+            let b = (color & 0xFF) as u8;
+            *entry = [r, g, b, 255];
         }
-        Ok(())
+        Ok(palette)
+    }
+}
+
+pub(crate) fn check_alpha_bits(alpha_bits: u32) -> Result<(), BlpError> {
+    match alpha_bits {
+        0 | 1 | 4 | 8 => Ok(()),
+        _ => Err(BlpError::new(ErrorKind::InvalidHeader, "error-alpha-bits-unsupported").with_arg("bits", alpha_bits)),
     }
 }
+
+/// One mipmap: indices (+ alpha) starting at `data` → RGBA image.
+fn decode_direct_mip(data: &[u8], w: u32, h: u32, palette: &[[u8; 4]; 256], alpha_bits: u32) -> Result<RgbaImage, BlpError> {
+    // Payload is checked before the output is allocated: a hostile header can't make us allocate more than it carries.
+    let (indices, alpha_raw) = direct_mip_parts(data, w, h, alpha_bits)?;
+    let mut raw = vec![0u8; indices.len() * 4];
+    palette_to::<Rgba8>(indices, alpha_raw, alpha_bits, palette, &mut raw);
+    RgbaImage::from_raw(w, h, raw).ok_or_else(|| BlpError::new(ErrorKind::Internal, "error-direct-buffer-size"))
+}
+
+/// One mipmap: indices (+ alpha) starting at `data` → layout `S`, written straight into `out`
+/// (exactly `w * h * S::BPP` bytes).
+pub(crate) fn write_direct_mip<S: PixelStore>(data: &[u8], w: u32, h: u32, palette: &[[u8; 4]; 256], alpha_bits: u32, out: &mut [u8]) -> Result<(), BlpError> {
+    let (indices, alpha_raw) = direct_mip_parts(data, w, h, alpha_bits)?;
+    palette_to::<S>(indices, alpha_raw, alpha_bits, palette, out);
+    Ok(())
+}
+
+/// Splits the mip payload into indices and alpha, both borrowed from the input.
+fn direct_mip_parts(data: &[u8], w: u32, h: u32, alpha_bits: u32) -> Result<(&[u8], &[u8]), BlpError> {
+    let pixel_count = (w as usize)
+        .checked_mul(h as usize)
+        .ok_or_else(|| BlpError::new(ErrorKind::Truncated, "error-direct-indices-truncated"))?;
+
+    // --- Indices (one byte per pixel) ---
+    let indices = data
+        .get(..pixel_count)
+        .ok_or_else(|| BlpError::new(ErrorKind::Truncated, "error-direct-indices-truncated"))?;
+
+    // --- Alpha data depending on alpha_bits ---
+    let alpha_bytes = match alpha_bits {
+        0 => 0,
+        1 => pixel_count.div_ceil(8), // 1 bit per pixel
+        4 => pixel_count.div_ceil(2), // 4 bits per pixel
+        _ => pixel_count,             // 8: 1 byte per pixel
+    };
+    let alpha_raw = data
+        .get(pixel_count..)
+        .and_then(|rest| rest.get(..alpha_bytes))
+        .ok_or_else(|| BlpError::new(ErrorKind::Truncated, "error-direct-alpha-truncated"))?;
+
+    Ok((indices, alpha_raw))
+}
@@ -1,8 +1,16 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::decode::jpeg_backend::DecodedJpeg;
+use crate::core::decode::pixel_layout::{PixelStore, Rgba8};
+use crate::core::decode::utils::jpeg_sof_dims::jpeg_sof_dims;
+use crate::core::decode::utils::jpeg_to_pixels::{bgr_to, cmyk_inv_to, l8_to, l16_to};
 use crate::core::image::ImageBlp;
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::mipmap::pixels_print;
+use crate::core::par_map::par_map;
 use crate::error::error::BlpError;
-use image::{Rgba, RgbaImage};
-use jpeg_decoder::{Decoder, PixelFormat};
-use std::io::Cursor;
+use crate::error::error_kind::ErrorKind;
+use image::RgbaImage;
+use jpeg_decoder::PixelFormat;
 
 impl ImageBlp {
     /// JPEG path:
@@ -10,19 +18,18 @@ impl ImageBlp {
     /// - For each mip, concatenates `[header][tail]` → full JPEG, then decodes it.
     /// - Respects `mip_visible`: if `mip_visible[i] == false`, the mip is skipped (image stays `None`).
     ///   Missing indices in `mip_visible` are treated as `true`.
-    pub(crate) fn decode_jpeg(&mut self, buf: &[u8], mip_visible: &[bool]) -> Result<(), BlpError> {
-        // --- Validate header range and slice it out ---
-        let h_off = self.header_offset;
-        let h_len = self.header_length;
-        if h_off.checked_add(h_len).is_none() || h_off + h_len > buf.len() {
-            return Err(BlpError::new("jpeg.header.oob"));
-        }
-        let header_bytes = &buf[h_off..h_off + h_len];
+    /// - With `opts.fix_sof_dims` the SOF of each mip is rewritten to the mip size from the BLP table first.
+    /// - Each mip goes through `opts.jpeg`; on failure `opts.jpeg_fallback` (if any) gets a try.
+    /// - With `opts.keep_jpeg` the full stream of every mip decoded at its own level is kept in `Mipmap::jpeg`
+    ///   (SOF set to the mip size) with a print of its pixels, so that re-encoding can copy it verbatim.
+    pub(crate) fn decode_jpeg(&mut self, buf: &[u8], mip_visible: &[bool], opts: &DecodeOptions) -> Result<(), BlpError> {
+        let header_bytes = self.jpeg_header(buf)?;
 
         // If alpha_bits == 0 we force opaque alpha channel when reconstructing RGBA.
         let force_opaque = self.alpha_bits == 0;
 
-        // --- Walk over mip chain ---
+        // --- Walk over mip chain: pick visible mips with valid ranges ---
+        let mut jobs: Vec<usize> = Vec::with_capacity(self.mipmaps.len());
         for i in 0..self.mipmaps.len() {
             // Visibility gate: missing entry → treated as `true`.
             let visible = mip_visible
@@ -32,6 +39,7 @@ impl ImageBlp {
             if !visible {
                 // Do not materialize pixels for this mip.
                 self.mipmaps[i].image = None;
+                self.mipmaps[i].jpeg = None;
                 continue;
             }
 
@@ -45,138 +53,125 @@ impl ImageBlp {
             if off.checked_add(len).is_none() || off + len > buf.len() {
                 continue;
             }
+            jobs.push(i);
+        }
 
-            // --- Build a full JPEG stream: [shared header][tail for this mip] ---
-            let tail = &buf[off..off + len];
-            let mut full = Vec::with_capacity(header_bytes.len() + tail.len());
-            full.extend_from_slice(header_bytes);
-            full.extend_from_slice(tail);
-
-            // --- Decode JPEG ---
-            let mut dec = Decoder::new(Cursor::new(&full));
-            dec.read_info().map_err(|e| {
-                BlpError::from(e)
-                    .with_arg("phase", "read_info")
-                    .with_arg("mip", i as u32)
-            })?;
-
-            let info = dec
-                .info()
-                .ok_or_else(|| BlpError::new("jpeg.meta.missing").with_arg("mip", i as u32))?;
-
-            let (w, h) = (info.width as u32, info.height as u32);
-            let pixels = dec.decode().map_err(|e| {
-                BlpError::from(e)
-                    .with_arg("phase", "decode")
-                    .with_arg("mip", i as u32)
-            })?;
-
-            // --- Reconstruct RGBA ---
-            let mut img = RgbaImage::new(w, h);
-            match info.pixel_format {
-                PixelFormat::CMYK32 => {
-                    // Expect 4 bytes per pixel: C, M, Y, K
-                    if pixels.len() != (w as usize * h as usize * 4) {
-                        return Err(BlpError::new("jpeg.size.mismatch")
-                            .with_arg("fmt", "CMYK32")
-                            .with_arg("mip", i as u32));
-                    }
-                    for (p, px) in img.pixels_mut().enumerate() {
-                        let idx = p * 4;
-                        let c = pixels[idx + 0];
-                        let m = pixels[idx + 1];
-                        let y = pixels[idx + 2];
-                        let k = pixels[idx + 3];
-                        // Alpha from K (unless forced opaque). Colors inverted from CMY.
-                        let a = if force_opaque { 255 } else { 255u8.saturating_sub(k) };
-                        *px = Rgba([
-                            255u8.saturating_sub(y), // R
-                            255u8.saturating_sub(m), // G
-                            255u8.saturating_sub(c), // B
-                            a,
-                        ]);
-                    }
-                }
-                PixelFormat::RGB24 => {
-                    // Expect 3 bytes per pixel
-                    if pixels.len() != (w as usize * h as usize * 3) {
-                        return Err(BlpError::new("jpeg.size.mismatch")
-                            .with_arg("fmt", "RGB24")
-                            .with_arg("mip", i as u32));
-                    }
-
-                    // Fast path (no color transform): pixels are B,G,R in this decoder layout
-                    if option_env!("NEVER").is_none() {
-                        for (p, px) in img.pixels_mut().enumerate() {
-                            let idx = p * 3;
-                            *px = Rgba([
-                                pixels[idx + 2], // R
-                                pixels[idx + 1], // G
-                                pixels[idx + 0], // B
-                                255,
-                            ]);
-                        }
-                    } else {
-                        // Alternative path if you want to pack as YCbCr (kept from your code)
-                        for (p, px) in img.pixels_mut().enumerate() {
-                            let idx = p * 3;
-                            let (r, g, b) = (
-                                pixels[idx + 2] as f32, //
-                                pixels[idx + 1] as f32,
-                                pixels[idx + 0] as f32,
-                            );
-                            let y = (0.2990 * r + 0.5870 * g + 0.1140 * b)
-                                .round()
-                                .clamp(0.0, 255.0) as u8;
-                            let cb = (128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b)
-                                .round()
-                                .clamp(0.0, 255.0) as u8;
-                            let cr = (128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b)
-                                .round()
-                                .clamp(0.0, 255.0) as u8;
-
-                            *px = Rgba([cb, cr, y, 255]);
-                        }
-                    }
-                }
-                PixelFormat::L8 => {
-                    // 1 byte per pixel (luminance)
-                    if pixels.len() != (w as usize * h as usize) {
-                        return Err(BlpError::new("jpeg.size.mismatch")
-                            .with_arg("fmt", "L8")
-                            .with_arg("mip", i as u32));
-                    }
-                    for (p, px) in img.pixels_mut().enumerate() {
-                        let l = pixels[p];
-                        *px = Rgba([l, l, l, 255]);
-                    }
-                }
-                PixelFormat::L16 => {
-                    // 2 bytes per pixel (big-endian luminance)
-                    if pixels.len() != (w as usize * h as usize * 2) {
-                        return Err(BlpError::new("jpeg.size.mismatch")
-                            .with_arg("fmt", "L16")
-                            .with_arg("mip", i as u32));
-                    }
-                    for (chunk, px) in pixels
-                        .chunks_exact(2)
-                        .zip(img.pixels_mut())
-                    {
-                        let l16 = u16::from_be_bytes([chunk[0], chunk[1]]);
-                        let l8 = (l16 / 257) as u8; // downscale 16→8
-                        *px = Rgba([l8, l8, l8, 255]);
-                    }
-                }
-            }
+        // --- Decode mips in parallel (each one is a standalone JPEG) ---
+        let mipmaps = &self.mipmaps;
+        let results = par_map(
+            jobs.len(),
+            opts.threads,
+            || (),
+            |_, j| {
+                let i = jobs[j];
+                let m = &mipmaps[i];
+                decode_jpeg_mip(header_bytes, &buf[m.offset..m.offset + m.length], i, (m.width, m.height), force_opaque, opts)
+            },
+        );
 
-            // --- Store image into the matching mip level ---
+        // --- Store images into the matching mip level, in mip order; first error wins ---
+        for (i, r) in jobs.into_iter().zip(results) {
+            let img = r?;
+            let (w, h) = img.dimensions();
             if self.mipmaps[i].width == w && self.mipmaps[i].height == h {
+                let m = &self.mipmaps[i];
+                self.mipmaps[i].jpeg = opts.keep_jpeg.then(|| {
+                    let mut full = [header_bytes, &buf[m.offset..m.offset + m.length]].concat();
+                    let _ = JpegHeaderInfo::fix_sof_dims(&mut full, w, h);
+                    (full, pixels_print(&img))
+                });
                 self.mipmaps[i].image = Some(img);
             } else if let Some(level) = (0..self.mipmaps.len()).find(|&lvl| self.mipmaps[lvl].width == w && self.mipmaps[lvl].height == h) {
                 self.mipmaps[level].image = Some(img);
+                self.mipmaps[level].jpeg = None;
             }
         }
 
         Ok(())
     }
+
+    /// Shared JPEG header `[header_offset .. header_offset+header_length)`, range-checked.
+    pub(crate) fn jpeg_header<'a>(&self, buf: &'a [u8]) -> Result<&'a [u8], BlpError> {
+        let h_off = self.header_offset;
+        let h_len = self.header_length;
+        if h_off.checked_add(h_len).is_none() || h_off + h_len > buf.len() {
+            return Err(BlpError::new(ErrorKind::Truncated, "error-jpeg-header-oob"));
+        }
+        Ok(&buf[h_off..h_off + h_len])
+    }
+}
+
+/// One mip: `[shared header][tail]` → full JPEG → RGBA.
+fn decode_jpeg_mip(header_bytes: &[u8], tail: &[u8], i: usize, dims: (u32, u32), force_opaque: bool, opts: &DecodeOptions) -> Result<RgbaImage, BlpError> {
+    let jpeg = decode_jpeg_raw(header_bytes, tail, i, dims, opts)?;
+    let (w, h) = (jpeg.width, jpeg.height);
+    let mut raw = vec![0u8; w as usize * h as usize * 4];
+    write_jpeg_pixels::<Rgba8>(&jpeg, i, force_opaque, &mut raw)?;
+    RgbaImage::from_raw(w, h, raw).ok_or_else(|| BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-size-mismatch").with_arg("mip", i as u32))
+}
+
+/// One mip: `[shared header][tail]` → full JPEG → decoder output (with optional fallback backend).
+/// `dims` is the mip size from the BLP table, used only with `opts.fix_sof_dims`.
+pub(crate) fn decode_jpeg_raw(header_bytes: &[u8], tail: &[u8], i: usize, dims: (u32, u32), opts: &DecodeOptions) -> Result<DecodedJpeg, BlpError> {
+    // --- Build a full JPEG stream: [shared header][tail for this mip] ---
+    let mut full = Vec::with_capacity(header_bytes.len() + tail.len());
+    full.extend_from_slice(header_bytes);
+    full.extend_from_slice(tail);
+
+    // A stream that doesn't parse is left as is: the decoder reports it.
+    if opts.fix_sof_dims {
+        let _ = JpegHeaderInfo::fix_sof_dims(&mut full, dims.0, dims.1);
+    }
+
+    // Decoders allocate by SOF dimensions: check them first (a missing SOF is left to the decoder to report).
+    if let Some((w, h)) = jpeg_sof_dims(&full) {
+        opts.limits
+            .check_dims(w, h)
+            .map_err(|e| e.with_arg("mip", i as u32))?;
+    }
+
+    match opts.jpeg.decode(&full) {
+        Ok(d) => Ok(d),
+        Err(e) => {
+            let e = e
+                .with_arg("backend", opts.jpeg.name())
+                .with_arg("mip", i as u32);
+            match &opts.jpeg_fallback {
+                Some(fb) => fb.decode(&full).map_err(|fe| {
+                    e.push_blp(
+                        fe.with_arg("backend", fb.name())
+                            .with_arg("mip", i as u32),
+                    )
+                }),
+                None => Err(e),
+            }
+        }
+    }
+}
+
+/// Decoder output → layout `S`, straight into `out` (exactly `width * height * S::BPP` bytes).
+pub(crate) fn write_jpeg_pixels<S: PixelStore>(jpeg: &DecodedJpeg, i: usize, force_opaque: bool, out: &mut [u8]) -> Result<(), BlpError> {
+    let DecodedJpeg { width: w, height: h, format, pixels } = jpeg;
+    let n = *w as usize * *h as usize;
+    let (fmt, bpp) = match format {
+        PixelFormat::CMYK32 => ("CMYK32", 4),
+        PixelFormat::RGB24 => ("RGB24", 3),
+        PixelFormat::L8 => ("L8", 1),
+        PixelFormat::L16 => ("L16", 2),
+    };
+    if pixels.len() != n * bpp || out.len() != n * S::BPP {
+        return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-size-mismatch")
+            .with_arg("fmt", fmt)
+            .with_arg("mip", i as u32));
+    }
+
+    match format {
+        // Alpha from K (unless forced opaque). Colors inverted from CMY.
+        PixelFormat::CMYK32 => cmyk_inv_to::<S>(pixels, out, force_opaque),
+        // No color transform: pixels are B,G,R in this decoder layout
+        PixelFormat::RGB24 => bgr_to::<S>(pixels, out),
+        PixelFormat::L8 => l8_to::<S>(pixels, out),
+        PixelFormat::L16 => l16_to::<S>(pixels, out),
+    }
+    Ok(())
 }
@@ -0,0 +1,49 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use jpeg_decoder::{Decoder, PixelFormat};
+use std::io::Cursor;
+
+/// Результат декодирования одного полного JPEG (SOI..EOI).
+///
+/// Раскладка пикселей — как у `jpeg_decoder`: компоненты в порядке хранения,
+/// 4-компонентные данные в «адобовской» инверсии (`255 - x`).
+pub struct DecodedJpeg {
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormat,
+    pub pixels: Vec<u8>,
+}
+
+/// JPEG-декодер для мипов BLP.
+pub trait JpegDecode: Send + Sync {
+    /// Короткое имя для ошибок и логов.
+    fn name(&self) -> &'static str;
+
+    fn decode(&self, jpeg: &[u8]) -> Result<DecodedJpeg, BlpError>;
+}
+
+/// Чистый Rust, `jpeg_decoder`. Используется по умолчанию.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JpegDecoderBackend;
+
+impl JpegDecode for JpegDecoderBackend {
+    fn name(&self) -> &'static str {
+        "jpeg-decoder"
+    }
+
+    fn decode(&self, jpeg: &[u8]) -> Result<DecodedJpeg, BlpError> {
+        let mut dec = Decoder::new(Cursor::new(jpeg));
+        dec.read_info()
+            .map_err(|e| BlpError::from(e).with_arg("phase", "read_info"))?;
+
+        let info = dec
+            .info()
+            .ok_or_else(|| BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-meta-missing"))?;
+
+        let pixels = dec
+            .decode()
+            .map_err(|e| BlpError::from(e).with_arg("phase", "decode"))?;
+
+        Ok(DecodedJpeg { width: info.width as u32, height: info.height as u32, format: info.pixel_format, pixels })
+    }
+}
@@ -1,3 +1,11 @@
-mod direct;
-mod jpeg;
-mod image;
\ No newline at end of file
+pub mod decode_limits;
+mod decode_mip_into;
+pub mod decode_options;
+pub(crate) mod direct;
+mod image;
+pub(crate) mod jpeg;
+pub mod jpeg_backend;
+pub mod pixel_layout;
+#[cfg(feature = "turbojpeg")]
+pub mod turbojpeg_backend;
+mod utils;
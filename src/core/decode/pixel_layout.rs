@@ -0,0 +1,105 @@
+/// Раскладка пикселей для [`ImageBlp::decode_mip_into`](crate::core::image::ImageBlp::decode_mip_into).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelLayout {
+    /// R, G, B, A — как `Mipmap::image`.
+    #[default]
+    Rgba8,
+    /// B, G, R, A.
+    Bgra8,
+    /// R, G, B, A с цветом, умноженным на альфу (`c * a / 255`, с округлением).
+    Rgba8Premultiplied,
+    /// R, G, B без альфы.
+    Rgb8,
+    /// Яркость (BT.601, целочисленно); у серых JPEG совпадает с исходной.
+    L8,
+}
+
+impl PixelLayout {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelLayout::Rgba8 | PixelLayout::Bgra8 | PixelLayout::Rgba8Premultiplied => 4,
+            PixelLayout::Rgb8 => 3,
+            PixelLayout::L8 => 1,
+        }
+    }
+
+    /// Сколько байт нужно под мип `w×h`.
+    pub fn buffer_len(self, w: u32, h: u32) -> usize {
+        w as usize * h as usize * self.bytes_per_pixel()
+    }
+}
+
+/// Запись одного пикселя в целевую раскладку. Конвертеры обобщены по нему,
+/// так что каждая раскладка получает свой цикл без ветвлений внутри.
+pub(crate) trait PixelStore {
+    const BPP: usize;
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, a: u8);
+}
+
+pub(crate) struct Rgba8;
+pub(crate) struct Bgra8;
+pub(crate) struct Rgba8Premultiplied;
+pub(crate) struct Rgb8;
+pub(crate) struct L8;
+
+impl PixelStore for Rgba8 {
+    const BPP: usize = 4;
+    #[inline(always)]
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, a: u8) {
+        dst.copy_from_slice(&[r, g, b, a]);
+    }
+}
+
+impl PixelStore for Bgra8 {
+    const BPP: usize = 4;
+    #[inline(always)]
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, a: u8) {
+        dst.copy_from_slice(&[b, g, r, a]);
+    }
+}
+
+/// `x * a / 255` с округлением, без деления.
+#[inline(always)]
+fn mul_div_255(x: u8, a: u8) -> u8 {
+    let t = x as u32 * a as u32 + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+impl PixelStore for Rgba8Premultiplied {
+    const BPP: usize = 4;
+    #[inline(always)]
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, a: u8) {
+        dst.copy_from_slice(&[mul_div_255(r, a), mul_div_255(g, a), mul_div_255(b, a), a]);
+    }
+}
+
+impl PixelStore for Rgb8 {
+    const BPP: usize = 3;
+    #[inline(always)]
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, _a: u8) {
+        dst.copy_from_slice(&[r, g, b]);
+    }
+}
+
+impl PixelStore for L8 {
+    const BPP: usize = 1;
+    #[inline(always)]
+    fn put(dst: &mut [u8], r: u8, g: u8, b: u8, _a: u8) {
+        dst[0] = ((77 * r as u32 + 150 * g as u32 + 29 * b as u32 + 128) >> 8) as u8;
+    }
+}
+
+/// Вызывает обобщённую по `PixelStore` функцию для выбранной раскладки.
+macro_rules! with_store {
+    ($layout:expr, $f:ident ( $($arg:expr),* $(,)? )) => {{
+        use $crate::core::decode::pixel_layout as pl;
+        match $layout {
+            pl::PixelLayout::Rgba8 => $f::<pl::Rgba8>($($arg),*),
+            pl::PixelLayout::Bgra8 => $f::<pl::Bgra8>($($arg),*),
+            pl::PixelLayout::Rgba8Premultiplied => $f::<pl::Rgba8Premultiplied>($($arg),*),
+            pl::PixelLayout::Rgb8 => $f::<pl::Rgb8>($($arg),*),
+            pl::PixelLayout::L8 => $f::<pl::L8>($($arg),*),
+        }
+    }};
+}
+pub(crate) use with_store;
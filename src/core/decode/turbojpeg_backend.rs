@@ -0,0 +1,89 @@
+use crate::core::decode::jpeg_backend::{DecodedJpeg, JpegDecode};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use jpeg_decoder::PixelFormat;
+use std::ffi::CStr;
+use turbojpeg::{libc, raw};
+
+/// libjpeg-turbo через FFI (feature `turbojpeg`). Терпимее к нестандартным заголовкам:
+/// предупреждения libjpeg не считаются ошибкой.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TurboJpegBackend;
+
+impl JpegDecode for TurboJpegBackend {
+    fn name(&self) -> &'static str {
+        "turbojpeg"
+    }
+
+    fn decode(&self, jpeg: &[u8]) -> Result<DecodedJpeg, BlpError> {
+        let handle = unsafe { raw::tj3Init(raw::TJINIT_TJINIT_DECOMPRESS as libc::c_int) };
+        if handle.is_null() {
+            return Err(BlpError::new(ErrorKind::Internal, "error-turbojpeg-init"));
+        }
+        unsafe {
+            struct Guard(raw::tjhandle);
+            impl Drop for Guard {
+                fn drop(&mut self) {
+                    if !self.0.is_null() {
+                        unsafe { raw::tj3Destroy(self.0) };
+                    }
+                }
+            }
+            let _g = Guard(handle);
+
+            if raw::tj3DecompressHeader(handle, jpeg.as_ptr(), jpeg.len() as raw::size_t) != 0 {
+                return Err(tj3_err(handle, "error-turbojpeg-header").with_arg("phase", "read_info"));
+            }
+            let w = raw::tj3Get(handle, raw::TJPARAM_TJPARAM_JPEGWIDTH as libc::c_int);
+            let h = raw::tj3Get(handle, raw::TJPARAM_TJPARAM_JPEGHEIGHT as libc::c_int);
+            let cs = raw::tj3Get(handle, raw::TJPARAM_TJPARAM_COLORSPACE as libc::c_int);
+            if w <= 0 || h <= 0 {
+                return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-meta-missing"));
+            }
+
+            // CMYK/YCCK → 4 компоненты, GRAY → 1, остальное → RGB
+            let (format, tjpf, bpp) = match cs as raw::TJCS {
+                raw::TJCS_TJCS_CMYK | raw::TJCS_TJCS_YCCK => (PixelFormat::CMYK32, raw::TJPF_TJPF_CMYK, 4usize),
+                raw::TJCS_TJCS_GRAY => (PixelFormat::L8, raw::TJPF_TJPF_GRAY, 1),
+                _ => (PixelFormat::RGB24, raw::TJPF_TJPF_RGB, 3),
+            };
+
+            let (wz, hz) = (w as usize, h as usize);
+            let mut pixels = vec![0u8; wz * hz * bpp];
+            if raw::tj3Decompress8(
+                handle, //
+                jpeg.as_ptr(),
+                jpeg.len() as raw::size_t,
+                pixels.as_mut_ptr(),
+                (wz * bpp) as libc::c_int,
+                tjpf as libc::c_int,
+            ) != 0
+            {
+                return Err(tj3_err(handle, "error-turbojpeg-decompress").with_arg("phase", "decode"));
+            }
+
+            // libjpeg отдаёт CMYK как есть — приводим к инверсии jpeg_decoder
+            if bpp == 4 {
+                for v in &mut pixels {
+                    *v = 255 - *v;
+                }
+            }
+
+            Ok(DecodedJpeg { width: w as u32, height: h as u32, format, pixels })
+        }
+    }
+}
+
+fn tj3_err(handle: raw::tjhandle, key: &'static str) -> BlpError {
+    let msg = unsafe {
+        let p = raw::tj3GetErrorStr(handle);
+        if p.is_null() {
+            "unknown".to_string()
+        } else {
+            CStr::from_ptr(p)
+                .to_string_lossy()
+                .into_owned()
+        }
+    };
+    BlpError::new(ErrorKind::InvalidJpeg, key).with_arg("msg", msg)
+}
@@ -0,0 +1,39 @@
+/// Размеры кадра из первого SOF-маркера `(width, height)`, без декодирования.
+///
+/// Проходит сегменты от SOI до SOF; `None`, если SOF нет до SOS/EOI или поток обрезан.
+pub fn jpeg_sof_dims(jpeg: &[u8]) -> Option<(u32, u32)> {
+    if jpeg.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+    let mut pos = 2;
+    loop {
+        if *jpeg.get(pos)? != 0xFF {
+            return None;
+        }
+        // заполняющие 0xFF перед маркером допустимы
+        while *jpeg.get(pos + 1)? == 0xFF {
+            pos += 1;
+        }
+        let marker = jpeg[pos + 1];
+        pos += 2;
+        match marker {
+            // без длины: RST0..7, TEM
+            0xD0..=0xD7 | 0x01 => continue,
+            // SOS / EOI до SOF — кадра нет
+            0xDA | 0xD9 => return None,
+            _ => {}
+        }
+        let len = u16::from_be_bytes([*jpeg.get(pos)?, *jpeg.get(pos + 1)?]) as usize;
+        // SOF0..SOF15, кроме DHT (C4), JPG (C8) и DAC (CC): [len:2][P:1][Y:2][X:2]
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let seg = jpeg.get(pos..pos + 7)?;
+            let h = u16::from_be_bytes([seg[3], seg[4]]) as u32;
+            let w = u16::from_be_bytes([seg[5], seg[6]]) as u32;
+            return Some((w, h));
+        }
+        if len < 2 {
+            return None;
+        }
+        pos += len;
+    }
+}
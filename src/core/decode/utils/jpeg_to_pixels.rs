@@ -0,0 +1,51 @@
+//! Выход JPEG-декодера → целевая раскладка. Все функции пишут в сырой буфер `out`
+//! (`w * h * S::BPP`) по чанкам фиксированной длины — без индексации `(x, y)`.
+
+use crate::core::decode::pixel_layout::PixelStore;
+
+/// Компоненты хранятся как B,G,R (без цветового преобразования), альфа 255.
+#[inline]
+pub fn bgr_to<S: PixelStore>(src: &[u8], out: &mut [u8]) {
+    for (d, s) in out
+        .chunks_exact_mut(S::BPP)
+        .zip(src.chunks_exact(3))
+    {
+        S::put(d, s[2], s[1], s[0], 255);
+    }
+}
+
+/// Инвертированный CMYK (как отдаёт `jpeg_decoder`): R=¬Y, G=¬M, B=¬C, A=¬K.
+#[inline]
+pub fn cmyk_inv_to<S: PixelStore>(src: &[u8], out: &mut [u8], force_opaque: bool) {
+    // ¬K при force_opaque даёт 255 через маску: ветка вынесена из цикла
+    let a_mask = if force_opaque { 0xFF } else { 0x00 };
+    for (d, s) in out
+        .chunks_exact_mut(S::BPP)
+        .zip(src.chunks_exact(4))
+    {
+        S::put(d, !s[2], !s[1], !s[0], !s[3] | a_mask);
+    }
+}
+
+/// Яркость → серый.
+#[inline]
+pub fn l8_to<S: PixelStore>(src: &[u8], out: &mut [u8]) {
+    for (d, &l) in out
+        .chunks_exact_mut(S::BPP)
+        .zip(src)
+    {
+        S::put(d, l, l, l, 255);
+    }
+}
+
+/// 16-битная яркость (big-endian) → серый, 16→8 через `/ 257`.
+#[inline]
+pub fn l16_to<S: PixelStore>(src: &[u8], out: &mut [u8]) {
+    for (d, s) in out
+        .chunks_exact_mut(S::BPP)
+        .zip(src.chunks_exact(2))
+    {
+        let l = (u16::from_be_bytes([s[0], s[1]]) / 257) as u8;
+        S::put(d, l, l, l, 255);
+    }
+}
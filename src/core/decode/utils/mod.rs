@@ -0,0 +1,4 @@
+pub mod jpeg_sof_dims;
+pub mod jpeg_to_pixels;
+pub mod palette_to_pixels;
+pub mod rgba_to_pixels;
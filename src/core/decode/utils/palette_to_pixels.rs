@@ -0,0 +1,61 @@
+use crate::core::decode::pixel_layout::PixelStore;
+
+/// Индексы (+ альфа) → целевая раскладка, сразу в сырой буфер (`out.len() == indices.len() * S::BPP`).
+///
+/// Палитра заранее развёрнута в `[r, g, b, 255]`. Альфа распаковывается в том же проходе:
+/// 1 бит (LSB первым), 4 бита (младший ниббл — чётный пиксель, `n → n * 17`) или 8 бит;
+/// при `alpha_bits == 0` берётся 255 из палитры.
+#[inline]
+pub fn palette_to<S: PixelStore>(indices: &[u8], alpha: &[u8], alpha_bits: u32, palette: &[[u8; 4]; 256], out: &mut [u8]) {
+    debug_assert_eq!(out.len(), indices.len() * S::BPP);
+    let px = |d: &mut [u8], idx: u8, a: u8| {
+        let [r, g, b, _] = palette[idx as usize];
+        S::put(d, r, g, b, a);
+    };
+    match alpha_bits {
+        1 => {
+            for ((d8, i8), &byte) in out
+                .chunks_mut(S::BPP * 8)
+                .zip(indices.chunks(8))
+                .zip(alpha)
+            {
+                for (bit, (d, &idx)) in d8
+                    .chunks_exact_mut(S::BPP)
+                    .zip(i8)
+                    .enumerate()
+                {
+                    px(d, idx, 0u8.wrapping_sub((byte >> bit) & 1)); // 0 → 0, 1 → 255
+                }
+            }
+        }
+        4 => {
+            for ((d2, i2), &byte) in out
+                .chunks_mut(S::BPP * 2)
+                .zip(indices.chunks(2))
+                .zip(alpha)
+            {
+                px(&mut d2[..S::BPP], i2[0], (byte & 0x0F) * 17);
+                if let Some(&idx) = i2.get(1) {
+                    px(&mut d2[S::BPP..], idx, (byte >> 4) * 17);
+                }
+            }
+        }
+        8 => {
+            for ((d, &idx), &a) in out
+                .chunks_exact_mut(S::BPP)
+                .zip(indices)
+                .zip(alpha)
+            {
+                px(d, idx, a);
+            }
+        }
+        _ => {
+            for (d, &idx) in out
+                .chunks_exact_mut(S::BPP)
+                .zip(indices)
+            {
+                px(d, idx, 255);
+            }
+        }
+    }
+}
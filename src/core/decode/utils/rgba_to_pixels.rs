@@ -0,0 +1,12 @@
+use crate::core::decode::pixel_layout::PixelStore;
+
+/// Уже готовый RGBA (например, `Mipmap::image`) → целевая раскладка.
+#[inline]
+pub fn rgba_to<S: PixelStore>(src: &[u8], out: &mut [u8]) {
+    for (d, s) in out
+        .chunks_exact_mut(S::BPP)
+        .zip(src.chunks_exact(4))
+    {
+        S::put(d, s[0], s[1], s[2], s[3]);
+    }
+}
@@ -1,11 +1,13 @@
-use crate::core::encode::utils::pack_rgba_to_cmyk_fast::pack_rgba_to_cmyk_fast;
-use crate::core::encode::utils::pack_rgba_to_rgb_fast::pack_rgba_to_rgb_fast;
+use crate::core::encode::jpeg_backend::JpegBackend;
+use crate::core::encode::utils::header_prefix::header_prefix;
 use crate::core::encode::utils::read_be_u16::read_be_u16;
 use crate::core::encode::utils::rebuild_minimal_jpeg_header::rebuild_minimal_jpeg_header;
 use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::metadata::BlpMetadata;
+use crate::core::par_map::par_map;
+use crate::core::types::SourceKind;
 use crate::error::error::BlpError;
-use std::ffi::CStr;
-use turbojpeg::{libc, raw};
+use crate::error::error_kind::ErrorKind;
 
 // === публичные структуры (внешний API) ===
 #[derive(Clone)]
@@ -13,6 +15,8 @@ pub struct Mip {
     pub w: u32,
     pub h: u32,
     pub visible: bool,
+    /// Данные скопированы из исходного BLP как есть, без перекодирования (`EncodeOptions::reuse_source`).
+    pub copied: bool,
     pub encode_ms: f64,
 }
 
@@ -21,12 +25,51 @@ pub struct Ctx {
     pub mips: Vec<Mip>,
     pub has_alpha: bool,
     pub encode_ms_total: f64,
+    /// `reuse_source`: таблицы источника не IJG, а бэкенд их не воспроизводит (TurboJPEG; `Pure` —
+    /// если таблица 0 16-битная) — перекодированные мипы сжаты с `EncodeOptions::quality`.
+    pub source_tables_lost: bool,
+}
+
+/// Параметры кодирования BLP.
+#[derive(Debug, Clone)]
+pub struct EncodeOptions {
+    /// Качество JPEG, 0..=100.
+    pub quality: u8,
+    pub backend: JpegBackend,
+    /// Потоки для кодирования мипов: `0` — по числу ядер, `1` — последовательно.
+    /// Результат побайтно одинаков при любом значении.
+    pub threads: usize,
+    /// Дополнительные записи чанка метаданных (источник, автор, время). Версию кодировщика,
+    /// качество, бэкенд и маску мипов кодировщик пишет сам.
+    pub metadata: BlpMetadata,
+    /// Для JPEG-BLP, декодированного с `DecodeOptions::keep_jpeg`: мипы, чьи пиксели не менялись
+    /// с декодирования, копируются как есть. Остальные кодируются с таблицами источника
+    /// (`ImageBlp::quality_estimate`): таблицы IJG — его качеством на любом бэкенде, сторонние —
+    /// его таблицей 0, если бэкенд её принимает (`JpegBackend::Pure`, 8-битная таблица). TurboJPEG
+    /// своих DQT не принимает: тогда `quality` и `Ctx::source_tables_lost`. Для остальных источников
+    /// ни на что не влияет.
+    pub reuse_source: bool,
+    /// Достроить цепочку до 1×1: отсутствующие и скрытые мипы ниже первого видимого пересчитываются
+    /// из предыдущего уровня тем же Lanczos3, что и `decode_image`.
+    pub fill_mips: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self { quality: 100, backend: JpegBackend::default(), threads: 0, metadata: BlpMetadata::default(), reuse_source: false, fill_mips: false }
+    }
 }
 
 impl ImageBlp {
     pub fn encode_blp(&self, quality: u8, mip_visible: &[bool]) -> Result<Ctx, BlpError> {
+        self.encode_blp_with(&EncodeOptions { quality, ..Default::default() }, mip_visible)
+    }
+
+    pub fn encode_blp_with(&self, opts: &EncodeOptions, mip_visible: &[bool]) -> Result<Ctx, BlpError> {
         use image::RgbaImage;
-        use std::{ptr, time::Instant};
+        use image::imageops::{FilterType, resize};
+        use std::borrow::Cow;
+        use std::time::Instant;
 
         // --- рабочая структура (заимствуем, без клонов) ---
         struct WorkMip<'a> {
@@ -34,6 +77,7 @@ impl ImageBlp {
             h: u32,
             vis: bool,
             img: Option<&'a RgbaImage>, // &RgbaImage, не clone
+            src: Option<&'a [u8]>,      // исходный поток для копирования
             encoded: Vec<u8>,           // полный JPEG (sanitized header + scan + EOI)
             encode_ms: f64,
         }
@@ -48,12 +92,51 @@ impl ImageBlp {
                     .unwrap_or(true)
                     && self.mipmaps[i].image.is_some()
             })
-            .ok_or_else(|| BlpError::new("no_visible_mips_after_mask"))?;
+            .ok_or_else(|| BlpError::new(ErrorKind::InvalidArgument, "error-encode-no-visible-mips"))?;
+
+        // 1.1) fill_mips: недостающие уровни до 1×1, каждый из предыдущего (как в decode_image)
+        let mut filled: Vec<Option<RgbaImage>> = vec![None; total];
+        if opts.fill_mips {
+            let base = &self.mipmaps[start_idx];
+            let (mut w, mut h) = (base.width.max(1), base.height.max(1));
+            let mut prev: Cow<RgbaImage> = Cow::Borrowed(base.image.as_ref().unwrap());
+            for (i, (m, slot)) in self.mipmaps[..total]
+                .iter()
+                .zip(filled.iter_mut())
+                .enumerate()
+                .skip(start_idx + 1)
+            {
+                if w == 1 && h == 1 {
+                    break;
+                }
+                (w, h) = ((w / 2).max(1), (h / 2).max(1));
+                let vis = mip_visible
+                    .get(i)
+                    .copied()
+                    .unwrap_or(true);
+                prev = match m.image.as_ref().filter(|_| vis) {
+                    Some(img) => Cow::Borrowed(img),
+                    None => {
+                        let next = resize(prev.as_ref(), w, h, FilterType::Lanczos3);
+                        *slot = Some(next.clone());
+                        Cow::Owned(next)
+                    }
+                };
+            }
+        }
 
-        // 1.1) собираем work начиная с start_idx (только ссылки)
+        // 1.2) собираем work начиная с start_idx (только ссылки)
         let mut work: Vec<WorkMip> = Vec::with_capacity(total - start_idx);
-        for i in start_idx..total {
-            let m = &self.mipmaps[i];
+        for (i, (m, img)) in self.mipmaps[..total]
+            .iter()
+            .zip(&filled)
+            .enumerate()
+            .skip(start_idx)
+        {
+            if let Some(img) = img {
+                work.push(WorkMip { w: img.width(), h: img.height(), vis: true, img: Some(img), src: None, encoded: Vec::new(), encode_ms: 0.0 });
+                continue;
+            }
             work.push(WorkMip {
                 w: m.width,
                 h: m.height,
@@ -62,6 +145,9 @@ impl ImageBlp {
                     .copied()
                     .unwrap_or(true),
                 img: m.image.as_ref(), // Option<&RgbaImage>
+                src: m
+                    .source_jpeg()
+                    .filter(|_| opts.reuse_source),
                 encoded: Vec::new(),
                 encode_ms: 0.0,
             });
@@ -70,9 +156,9 @@ impl ImageBlp {
         // 2) базовый мип и альфа
         let base_img = work[0]
             .img
-            .ok_or_else(|| BlpError::new("first_visible_slot_missing_src"))?;
+            .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-encode-first-visible-missing"))?;
         if base_img.width() != work[0].w || base_img.height() != work[0].h {
-            return Err(BlpError::new("mip.size_mismatch")
+            return Err(BlpError::new(ErrorKind::InvalidImage, "error-encode-mip-size-mismatch")
                 .with_arg("want_w", work[0].w)
                 .with_arg("want_h", work[0].h)
                 .with_arg("got_w", base_img.width())
@@ -80,102 +166,75 @@ impl ImageBlp {
         }
         let has_alpha = base_img.pixels().any(|p| p.0[3] != 255);
 
+        // JPEG-BLP источник: нетронутые мипы копируются, остальные — с его таблицами и его раскладкой
+        let source = self
+            .quality_estimate
+            .as_ref()
+            .filter(|_| opts.reuse_source && self.source == SourceKind::Blp);
+        let table = source
+            .filter(|e| !e.exact && opts.backend.custom_tables())
+            .and_then(|e| e.main_table());
+        let (quality, tables_lost) = match source {
+            Some(e) if e.exact || table.is_some() => (e.quality, false),
+            Some(_) => (opts.quality, true),
+            None => (opts.quality, false),
+        };
+        let copies = work
+            .iter()
+            .any(|m| m.vis && m.img.is_some() && m.src.is_some());
+        let has_alpha = has_alpha || (copies && self.alpha_bits > 0);
+
         let t0 = Instant::now();
 
         // 3) кодирование мипов → WorkMip.encoded
-        for wm in &mut work {
-            if !(wm.vis && wm.img.is_some()) {
-                wm.encoded.clear();
-                wm.encode_ms = 0.0;
+        // 3.1) отбор и проверка размеров — последовательно, ошибки как раньше
+        let mut jobs: Vec<usize> = Vec::with_capacity(work.len());
+        let mut copied: Vec<usize> = Vec::new();
+        for (k, wm) in work.iter().enumerate() {
+            let Some(rgba) = wm.img.filter(|_| wm.vis) else {
                 continue;
-            }
-            let rgba = wm.img.unwrap();
-            let wz = rgba.width() as usize;
-            let hz = rgba.height() as usize;
-
-            if wz != wm.w as usize || hz != wm.h as usize {
-                return Err(BlpError::new("mip.size_mismatch")
+            };
+            if rgba.width() != wm.w || rgba.height() != wm.h {
+                return Err(BlpError::new(ErrorKind::InvalidImage, "error-encode-mip-size-mismatch")
                     .with_arg("want_w", wm.w)
                     .with_arg("want_h", wm.h)
-                    .with_arg("got_w", wz)
-                    .with_arg("got_h", hz));
+                    .with_arg("got_w", rgba.width())
+                    .with_arg("got_h", rgba.height()));
             }
-
-            // упаковка под TurboJPEG
-            let src = rgba.as_raw();
-            let (packed, pitch) = if has_alpha {
-                pack_rgba_to_cmyk_fast(src, wz, hz) // pitch = wz * 4
-            } else {
-                pack_rgba_to_rgb_fast(src, wz, hz) // pitch = wz * 3
-            };
-
-            let t_mip = Instant::now();
-
-            // TurboJPEG 3
-            let handle = unsafe { raw::tj3Init(raw::TJINIT_TJINIT_COMPRESS as libc::c_int) };
-            if handle.is_null() {
-                return Err(BlpError::new("tj3.init"));
+            if wm.src.is_some() {
+                copied.push(k);
+                continue;
             }
-            let jpeg_raw = unsafe {
-                struct Guard(raw::tjhandle);
-                impl Drop for Guard {
-                    fn drop(&mut self) {
-                        if !self.0.is_null() {
-                            unsafe { raw::tj3Destroy(self.0) };
-                        }
-                    }
-                }
-                let _g = Guard(handle);
-
-                if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_QUALITY as libc::c_int, quality as libc::c_int) != 0 {
-                    return Err(tj3_err(handle, "tj3.quality"));
-                }
-                if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_SUBSAMP as libc::c_int, raw::TJSAMP_TJSAMP_444 as libc::c_int) != 0 {
-                    return Err(tj3_err(handle, "tj3.subsamp"));
-                }
-                if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_OPTIMIZE as libc::c_int, 0) != 0 {
-                    return Err(tj3_err(handle, "tj3.optimize"));
-                }
-                if raw::tj3Set(
-                    handle, //
-                    raw::TJPARAM_TJPARAM_COLORSPACE as libc::c_int,
-                    if has_alpha { raw::TJCS_TJCS_CMYK } else { raw::TJCS_TJCS_RGB } as libc::c_int,
-                ) != 0
-                {
-                    return Err(tj3_err(handle, "tj3.colorspace"));
-                }
-
-                let mut out_ptr: *mut libc::c_uchar = ptr::null_mut();
-                let mut out_size: raw::size_t = 0;
-                let r = raw::tj3Compress8(
-                    handle, //
-                    packed.as_ptr(),
-                    wz as libc::c_int,
-                    pitch as libc::c_int,
-                    hz as libc::c_int,
-                    if has_alpha { raw::TJPF_TJPF_CMYK } else { raw::TJPF_TJPF_BGR } as libc::c_int,
-                    &mut out_ptr,
-                    &mut out_size,
-                );
-                if r != 0 {
-                    return Err(tj3_err(handle, "tj3.compress"));
-                }
-                let slice = std::slice::from_raw_parts(out_ptr, out_size as usize);
-                let vec = slice.to_vec();
-                raw::tj3Free(out_ptr as *mut libc::c_void);
-                vec
-            };
+            jobs.push(k);
+        }
 
-            // sanitize header
-            let (head_len, _scan_len) = split_header_and_scan(&jpeg_raw)?;
-            let header_clean = rebuild_minimal_jpeg_header(&jpeg_raw[..head_len])?;
-            wm.encoded = {
+        // 3.2) сами мипы независимы — кодируем параллельно, компрессор один на поток
+        let source_tables_lost = tables_lost && !jobs.is_empty();
+        let results = par_map(
+            jobs.len(),
+            opts.threads,
+            || opts.backend.compressor(),
+            |comp, j| -> Result<(Vec<u8>, f64), BlpError> {
+                let rgba = work[jobs[j]].img.unwrap();
+                let t_mip = Instant::now();
+                let jpeg_raw = comp.compress(rgba.as_raw(), rgba.width() as usize, rgba.height() as usize, quality, table.as_ref(), has_alpha)?;
+
+                // sanitize header
+                let (head_len, _scan_len) = split_header_and_scan(&jpeg_raw)?;
+                let header_clean = rebuild_minimal_jpeg_header(&jpeg_raw[..head_len])?;
                 let mut v = Vec::with_capacity(jpeg_raw.len());
                 v.extend_from_slice(&header_clean);
                 v.extend_from_slice(&jpeg_raw[head_len..]); // scan + EOI
-                v
-            };
-            wm.encode_ms = t_mip.elapsed().as_secs_f64() * 1000.0;
+                Ok((v, t_mip.elapsed().as_secs_f64() * 1000.0))
+            },
+        );
+        for &k in &copied {
+            work[k].encoded = work[k].src.unwrap_or_default().to_vec();
+        }
+        for (k, r) in jobs.into_iter().zip(results) {
+            let (encoded, ms) = r?;
+            work[k].encoded = encoded;
+            work[k].encode_ms = ms;
         }
 
         let encode_ms_total = t0.elapsed().as_secs_f64() * 1000.0;
@@ -186,7 +245,7 @@ impl ImageBlp {
             .map(|m| m.encoded.is_empty())
             .unwrap_or(true)
         {
-            return Err(BlpError::new("first_visible_slot_missing"));
+            return Err(BlpError::new(ErrorKind::MipMissing, "error-encode-first-visible-missing"));
         }
 
         // 4) общий header как общий префикс
@@ -199,19 +258,19 @@ impl ImageBlp {
             heads.push(&m.encoded[..hlen]);
         }
         if heads.is_empty() {
-            return Err(BlpError::new("no_encoded_heads"));
+            return Err(BlpError::new(ErrorKind::Encode, "error-encode-no-heads"));
         }
 
         let mut common_header = header_prefix(&heads);
         if common_header.len() < 2 || common_header[0] != 0xFF || common_header[1] != 0xD8 {
-            return Err(BlpError::new("bad_common_header"));
+            return Err(BlpError::new(ErrorKind::Encode, "error-encode-common-header"));
         }
         for h in &heads {
             while !h.starts_with(&common_header) && !common_header.is_empty() {
                 common_header.pop();
             }
             if !h.starts_with(&common_header) {
-                return Err(BlpError::new("head_prefix_mismatch"));
+                return Err(BlpError::new(ErrorKind::Encode, "error-encode-common-header"));
             }
         }
 
@@ -245,10 +304,23 @@ impl ImageBlp {
         let jpeg_header_size: u32 = common_header
             .len()
             .try_into()
-            .map_err(|_| BlpError::new("jpeg_header_too_large"))?;
+            .map_err(|_| BlpError::new(ErrorKind::Encode, "error-encode-header-too-large"))?;
         bytes.extend_from_slice(&jpeg_header_size.to_le_bytes());
         bytes.extend_from_slice(&common_header);
-        bytes.extend_from_slice(b"RAFT"); // твой маркер
+        // чанк метаданных: игра его не читает, мипы идут сразу за ним
+        let mut metadata = BlpMetadata::new();
+        let mips: String = (0..total)
+            .map(|i| if i >= start_idx && !work[i - start_idx].encoded.is_empty() { '1' } else { '0' })
+            .collect();
+        metadata
+            .set(BlpMetadata::ENCODER, concat!("blp ", env!("CARGO_PKG_VERSION")))
+            .set(BlpMetadata::QUALITY, quality.to_string())
+            .set(BlpMetadata::BACKEND, format!("{:?}", opts.backend))
+            .set(BlpMetadata::MIPS, mips);
+        for (k, v) in &opts.metadata.entries {
+            metadata.set(k.as_str(), v.as_str());
+        }
+        bytes.extend_from_slice(&metadata.to_bytes()?);
 
         // payload’ы: строгие проверки вместо debug_assert!
         for i in 0..MAX_MIPS.min(work.len()) {
@@ -259,13 +331,13 @@ impl ImageBlp {
 
             let (head_len, _) = split_header_and_scan(&m.encoded)?;
             if head_len < common_header.len() {
-                return Err(BlpError::new("mip.head_too_short")
+                return Err(BlpError::new(ErrorKind::Encode, "error-encode-head-too-short")
                     .with_arg("mip", i)
                     .with_arg("head_len", head_len)
                     .with_arg("common_len", common_header.len()));
             }
             if &m.encoded[..common_header.len()] != &common_header[..] {
-                return Err(BlpError::new("mip.common_header_mismatch")
+                return Err(BlpError::new(ErrorKind::Encode, "error-encode-head-prefix-mismatch")
                     .with_arg("mip", i)
                     .with_arg("head_prefix", hex::encode(&m.encoded[..common_header.len()]))
                     .with_arg("common_prefix", hex::encode(&common_header)));
@@ -276,10 +348,10 @@ impl ImageBlp {
             let sz = payload.len();
 
             if off > u32::MAX as usize {
-                return Err(BlpError::new("offset_too_large"));
+                return Err(BlpError::new(ErrorKind::Encode, "error-encode-offset-too-large"));
             }
             if sz > u32::MAX as usize {
-                return Err(BlpError::new("payload_too_large"));
+                return Err(BlpError::new(ErrorKind::Encode, "error-encode-payload-too-large"));
             }
 
             write_u32_le_at(&mut bytes, pos_offsets + (i << 2), off as u32);
@@ -290,17 +362,17 @@ impl ImageBlp {
 
         // 5) внешний список мипов (без байтов)
         let mut out_mips: Vec<Mip> = Vec::with_capacity(work.len());
-        for wm in &work {
-            out_mips.push(Mip { w: wm.w, h: wm.h, visible: wm.vis, encode_ms: wm.encode_ms });
+        for (k, wm) in work.iter().enumerate() {
+            out_mips.push(Mip { w: wm.w, h: wm.h, visible: wm.vis, copied: copied.contains(&k), encode_ms: wm.encode_ms });
         }
 
-        Ok(Ctx { bytes, mips: out_mips, has_alpha, encode_ms_total })
+        Ok(Ctx { bytes, mips: out_mips, has_alpha, encode_ms_total, source_tables_lost })
     }
 }
 
 fn split_header_and_scan(jpeg: &[u8]) -> Result<(usize, usize), BlpError> {
     if jpeg.len() < 4 || jpeg[0] != 0xFF || jpeg[1] != 0xD8 {
-        return Err(BlpError::new("jpeg.bad_soi"));
+        return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-bad-soi"));
     }
     let mut i = 2usize;
     loop {
@@ -308,21 +380,21 @@ fn split_header_and_scan(jpeg: &[u8]) -> Result<(usize, usize), BlpError> {
             i += 1;
         }
         if i >= jpeg.len() {
-            return Err(BlpError::new("jpeg.truncated"));
+            return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-truncated"));
         }
         let m = jpeg[i];
         i += 1;
         match m {
-            0xD9 => return Err(BlpError::new("jpeg.eoi_before_sos")),
+            0xD9 => return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-eoi-before-sos")),
             0xD0..=0xD7 | 0x01 => {} // no length
             0xDA => {
                 if i + 2 > jpeg.len() {
-                    return Err(BlpError::new("jpeg.sos_len"));
+                    return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-segment-len"));
                 }
                 let seg_len = read_be_u16(&jpeg[i..i + 2])? as usize;
                 let seg_end = i + seg_len;
                 if seg_end > jpeg.len() {
-                    return Err(BlpError::new("jpeg.sos_trunc"));
+                    return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-truncated"));
                 }
                 let head_len = seg_end;
                 let mut j = head_len;
@@ -339,54 +411,19 @@ fn split_header_and_scan(jpeg: &[u8]) -> Result<(usize, usize), BlpError> {
                     }
                     j += 1;
                 }
-                return Err(BlpError::new("jpeg.eoi_not_found"));
+                return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-eoi-not-found"));
             }
             _ => {
                 if i + 2 > jpeg.len() {
-                    return Err(BlpError::new("jpeg.seg_len"));
+                    return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-segment-len"));
                 }
                 let seg_len = read_be_u16(&jpeg[i..i + 2])? as usize;
                 let seg_end = i + seg_len;
                 if seg_end > jpeg.len() {
-                    return Err(BlpError::new("jpeg.seg_trunc"));
+                    return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-truncated"));
                 }
                 i = seg_end;
             }
         }
     }
 }
-
-fn header_prefix(heads: &[&[u8]]) -> Vec<u8> {
-    if heads.is_empty() {
-        return Vec::new();
-    }
-    let min_len = heads
-        .iter()
-        .map(|h| h.len())
-        .min()
-        .unwrap();
-    let mut out = Vec::with_capacity(min_len);
-    for i in 0..min_len {
-        let b = heads[0][i];
-        if heads.iter().all(|h| h[i] == b) {
-            out.push(b);
-        } else {
-            break;
-        }
-    }
-    out
-}
-
-fn tj3_err(handle: raw::tjhandle, key: &'static str) -> BlpError {
-    let msg = unsafe {
-        let p = raw::tj3GetErrorStr(handle);
-        if p.is_null() {
-            "unknown".to_string()
-        } else {
-            CStr::from_ptr(p)
-                .to_string_lossy()
-                .into_owned()
-        }
-    };
-    BlpError::new(key).with_arg("msg", msg)
-}
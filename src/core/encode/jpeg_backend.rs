@@ -0,0 +1,90 @@
+#[cfg(feature = "pure-jpeg")]
+use crate::core::encode::utils::compress_pure::compress_pure;
+#[cfg(feature = "turbojpeg")]
+use crate::core::encode::utils::compress_turbojpeg::TurboCompressor;
+use crate::error::error::BlpError;
+
+#[cfg(not(any(feature = "turbojpeg", feature = "pure-jpeg")))]
+compile_error!("enable at least one JPEG backend feature: `turbojpeg` or `pure-jpeg`");
+
+/// JPEG-кодировщик для мипов. Оба бэкенда пишут одинаковую раскладку
+/// (4:4:4, RGB либо CMYK с альфой в K), так что BLP читается одинаково.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JpegBackend {
+    /// libjpeg-turbo через FFI (feature `turbojpeg`, по умолчанию).
+    #[cfg(feature = "turbojpeg")]
+    TurboJpeg,
+    /// Чистый Rust, `jpeg-encoder` (feature `pure-jpeg`).
+    #[cfg(feature = "pure-jpeg")]
+    Pure,
+}
+
+impl Default for JpegBackend {
+    /// TurboJPEG, если он собран; иначе чистый Rust.
+    fn default() -> Self {
+        #[cfg(feature = "turbojpeg")]
+        return JpegBackend::TurboJpeg;
+        #[cfg(not(feature = "turbojpeg"))]
+        return JpegBackend::Pure;
+    }
+}
+
+impl JpegBackend {
+    /// Все бэкенды, включённые в сборку.
+    pub fn available() -> &'static [JpegBackend] {
+        &[
+            #[cfg(feature = "turbojpeg")]
+            JpegBackend::TurboJpeg,
+            #[cfg(feature = "pure-jpeg")]
+            JpegBackend::Pure,
+        ]
+    }
+
+    /// Принимает свою таблицу квантования (`JpegCompressor::compress`, `table`). TurboJPEG — нет:
+    /// его таблицы задаются только качеством.
+    pub fn custom_tables(self) -> bool {
+        match self {
+            #[cfg(feature = "turbojpeg")]
+            JpegBackend::TurboJpeg => false,
+            #[cfg(feature = "pure-jpeg")]
+            JpegBackend::Pure => true,
+        }
+    }
+
+    /// Компрессор для одного потока; тяжёлые хендлы создаются при первом вызове.
+    pub(crate) fn compressor(self) -> JpegCompressor {
+        match self {
+            #[cfg(feature = "turbojpeg")]
+            JpegBackend::TurboJpeg => JpegCompressor::TurboJpeg(None),
+            #[cfg(feature = "pure-jpeg")]
+            JpegBackend::Pure => JpegCompressor::Pure,
+        }
+    }
+}
+
+pub(crate) enum JpegCompressor {
+    #[cfg(feature = "turbojpeg")]
+    TurboJpeg(Option<TurboCompressor>),
+    #[cfg(feature = "pure-jpeg")]
+    Pure,
+}
+
+impl JpegCompressor {
+    /// RGBA (`w * h * 4` байт) → полный JPEG (SOI..EOI). `table` — одна таблица квантования на все
+    /// компоненты вместо таблицы для `quality`; только для бэкендов с [`JpegBackend::custom_tables`].
+    pub(crate) fn compress(&mut self, rgba: &[u8], w: usize, h: usize, quality: u8, table: Option<&[u16; 64]>, has_alpha: bool) -> Result<Vec<u8>, BlpError> {
+        match self {
+            #[cfg(feature = "turbojpeg")]
+            JpegCompressor::TurboJpeg(slot) => {
+                let tj = match slot {
+                    Some(tj) => tj,
+                    None => slot.insert(TurboCompressor::new()?),
+                };
+                debug_assert!(table.is_none(), "TurboJPEG takes no custom quantization tables");
+                tj.compress(rgba, w, h, quality, has_alpha)
+            }
+            #[cfg(feature = "pure-jpeg")]
+            JpegCompressor::Pure => compress_pure(rgba, w, h, quality, table, has_alpha),
+        }
+    }
+}
@@ -1,2 +1,3 @@
-mod blp;
-mod utils;
\ No newline at end of file
+pub mod blp;
+pub mod jpeg_backend;
+pub(crate) mod utils;
\ No newline at end of file
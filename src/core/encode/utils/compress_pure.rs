@@ -0,0 +1,137 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use jpeg_encoder::{Encoder, ImageBuffer, JpegColorType, QuantizationTableType, SamplingFactor};
+
+/// Annex K, таблица яркости (естественный порядок).
+const ANNEX_K_LUMA: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, //
+    12, 12, 14, 19, 26, 58, 60, 55, //
+    14, 13, 16, 24, 40, 57, 69, 56, //
+    14, 17, 22, 29, 51, 87, 80, 62, //
+    18, 22, 37, 56, 68, 109, 103, 77, //
+    24, 35, 55, 64, 81, 104, 113, 92, //
+    49, 64, 78, 87, 103, 121, 120, 101, //
+    72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+/// RGBA → полный JPEG (SOI..EOI) без C-зависимостей, та же раскладка, что у TurboJPEG:
+/// 4:4:4, одна (яркостная) таблица квантования `Tq = 0` на все компоненты, как делает libjpeg для RGB/CMYK.
+///
+/// С альфой — 4 компоненты (C←B, M←G, Y←R, K←A), без альфы — 3 компоненты B,G,R.
+/// `table` — своя таблица (естественный порядок) вместо таблицы IJG для `quality`.
+///
+/// Идентификаторы компонент переписываются в `R,G,B` / `C,M,Y,K`: по `R,G,B` декодер
+/// понимает, что цветового преобразования нет (APP0/APP14 потом всё равно вырезаются).
+pub fn compress_pure(rgba: &[u8], w: usize, h: usize, quality: u8, table: Option<&[u16; 64]>, has_alpha: bool) -> Result<Vec<u8>, BlpError> {
+    let (w16, h16) = match (u16::try_from(w), u16::try_from(h)) {
+        (Ok(w16), Ok(h16)) => (w16, h16),
+        _ => {
+            return Err(BlpError::new(ErrorKind::Encode, "error-jpeg-too-large")
+                .with_arg("width", w)
+                .with_arg("height", h));
+        }
+    };
+
+    let table = QuantizationTableType::Custom(Box::new(
+        table
+            .copied()
+            .unwrap_or_else(|| scaled_luma_table(quality)),
+    ));
+
+    let mut out = Vec::new();
+    let mut enc = Encoder::new(&mut out, quality.max(1));
+    enc.set_sampling_factor(SamplingFactor::R_4_4_4);
+    enc.set_quantization_tables(table.clone(), table);
+    enc.set_optimized_huffman_tables(false);
+    enc.encode_image(RgbaSource { rgba, w: w16, h: h16, has_alpha })?;
+
+    let ids: &[u8] = if has_alpha { b"CMYK" } else { b"RGB" };
+    patch_components(&mut out, ids)?;
+    Ok(out)
+}
+
+/// Масштабирование по качеству как в libjpeg (`jpeg_quality_scaling`, force_baseline).
+fn scaled_luma_table(quality: u8) -> [u16; 64] {
+    let q = quality.clamp(1, 100) as u32;
+    let scale = if q < 50 { 5000 / q } else { 200 - q * 2 };
+    ANNEX_K_LUMA.map(|v| ((v as u32 * scale + 50) / 100).clamp(1, 255) as u16)
+}
+
+struct RgbaSource<'a> {
+    rgba: &'a [u8],
+    w: u16,
+    h: u16,
+    has_alpha: bool,
+}
+
+impl ImageBuffer for RgbaSource<'_> {
+    fn get_jpeg_color_type(&self) -> JpegColorType {
+        // Данные кладём как есть: тип цвета задаёт только число компонент и раскладку таблиц
+        if self.has_alpha { JpegColorType::Cmyk } else { JpegColorType::Ycbcr }
+    }
+
+    fn width(&self) -> u16 {
+        self.w
+    }
+
+    fn height(&self) -> u16 {
+        self.h
+    }
+
+    fn fill_buffers(&self, y: u16, buffers: &mut [Vec<u8>; 4]) {
+        let stride = self.w as usize * 4;
+        let row = &self.rgba[y as usize * stride..][..stride];
+        for px in row.chunks_exact(4) {
+            buffers[0].push(px[2]); // B
+            buffers[1].push(px[1]); // G
+            buffers[2].push(px[0]); // R
+            if self.has_alpha {
+                buffers[3].push(px[3]); // A
+            }
+        }
+    }
+}
+
+/// Переписывает id компонент в SOF0 и SOS (jpeg-encoder нумерует их с нуля) и сводит квантование
+/// к одной таблице 0, как у TurboJPEG: jpeg-encoder всегда пишет DQT 0 и 1 (у нас они одинаковые),
+/// а компоненты раздаёт по обеим.
+fn patch_components(jpeg: &mut Vec<u8>, ids: &[u8]) -> Result<(), BlpError> {
+    let mut drop_dqt: Option<(usize, usize)> = None;
+    let mut i = 2usize;
+    while i + 4 <= jpeg.len() {
+        if jpeg[i] != 0xFF {
+            return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-marker-expected"));
+        }
+        let m = jpeg[i + 1];
+        let seg_len = u16::from_be_bytes([jpeg[i + 2], jpeg[i + 3]]) as usize;
+        let body = i + 4;
+        match m {
+            // Pq/Tq(1) + 64 — jpeg-encoder пишет каждую таблицу отдельным сегментом
+            0xDB if jpeg[body] & 0x0F == 1 => drop_dqt = Some((i, 2 + seg_len)),
+            0xC0 => {
+                // P(1) Y(2) X(2) Nf(1), затем Nf × (Ci, HiVi, Tqi)
+                let nf = jpeg[body + 5] as usize;
+                for (k, &id) in ids.iter().enumerate().take(nf) {
+                    jpeg[body + 6 + k * 3] = id;
+                }
+                for k in 0..nf {
+                    jpeg[body + 6 + k * 3 + 2] = 0;
+                }
+            }
+            0xDA => {
+                // Ns(1), затем Ns × (Csj, TdjTaj)
+                let ns = jpeg[body] as usize;
+                for (k, &id) in ids.iter().enumerate().take(ns) {
+                    jpeg[body + 1 + k * 2] = id;
+                }
+                if let Some((at, len)) = drop_dqt {
+                    jpeg.drain(at..at + len);
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        i += 2 + seg_len;
+    }
+    Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-sos-missing"))
+}
@@ -0,0 +1,103 @@
+use crate::core::encode::utils::pack_rgba_to_cmyk_fast::pack_rgba_to_cmyk_fast;
+use crate::core::encode::utils::pack_rgba_to_rgb_fast::pack_rgba_to_rgb_fast;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use std::ffi::CStr;
+use std::ptr;
+use turbojpeg::{libc, raw};
+
+/// Хендл компрессора TurboJPEG 3: создаётся один раз и переиспользуется для всех мипов потока.
+pub struct TurboCompressor {
+    handle: raw::tjhandle,
+}
+
+// tjhandle — владеющий указатель без разделяемого состояния; между потоками только перемещается
+unsafe impl Send for TurboCompressor {}
+
+impl Drop for TurboCompressor {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { raw::tj3Destroy(self.handle) };
+        }
+    }
+}
+
+impl TurboCompressor {
+    pub fn new() -> Result<Self, BlpError> {
+        let handle = unsafe { raw::tj3Init(raw::TJINIT_TJINIT_COMPRESS as libc::c_int) };
+        if handle.is_null() {
+            return Err(BlpError::new(ErrorKind::Internal, "error-turbojpeg-init"));
+        }
+        Ok(Self { handle })
+    }
+
+    /// RGBA → полный JPEG (SOI..EOI), 4:4:4, без оптимизации Хаффмана.
+    ///
+    /// С альфой — CMYK (C←B, M←G, Y←R, K←A), без альфы — RGB с компонентами в порядке B,G,R.
+    pub fn compress(&mut self, rgba: &[u8], w: usize, h: usize, quality: u8, has_alpha: bool) -> Result<Vec<u8>, BlpError> {
+        // упаковка под TurboJPEG
+        let (packed, pitch) = if has_alpha {
+            pack_rgba_to_cmyk_fast(rgba, w, h) // pitch = w * 4
+        } else {
+            pack_rgba_to_rgb_fast(rgba, w, h) // pitch = w * 3
+        };
+
+        let handle = self.handle;
+        unsafe {
+            if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_QUALITY as libc::c_int, quality as libc::c_int) != 0 {
+                return Err(tj3_err(handle, "error-turbojpeg-param").with_arg("param", "quality"));
+            }
+            if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_SUBSAMP as libc::c_int, raw::TJSAMP_TJSAMP_444 as libc::c_int) != 0 {
+                return Err(tj3_err(handle, "error-turbojpeg-param").with_arg("param", "subsamp"));
+            }
+            if raw::tj3Set(handle, raw::TJPARAM_TJPARAM_OPTIMIZE as libc::c_int, 0) != 0 {
+                return Err(tj3_err(handle, "error-turbojpeg-param").with_arg("param", "optimize"));
+            }
+            if raw::tj3Set(
+                handle, //
+                raw::TJPARAM_TJPARAM_COLORSPACE as libc::c_int,
+                if has_alpha { raw::TJCS_TJCS_CMYK } else { raw::TJCS_TJCS_RGB } as libc::c_int,
+            ) != 0
+            {
+                return Err(tj3_err(handle, "error-turbojpeg-param").with_arg("param", "colorspace"));
+            }
+
+            let mut out_ptr: *mut libc::c_uchar = ptr::null_mut();
+            let mut out_size: raw::size_t = 0;
+            let r = raw::tj3Compress8(
+                handle, //
+                packed.as_ptr(),
+                w as libc::c_int,
+                pitch as libc::c_int,
+                h as libc::c_int,
+                if has_alpha { raw::TJPF_TJPF_CMYK } else { raw::TJPF_TJPF_BGR } as libc::c_int,
+                &mut out_ptr,
+                &mut out_size,
+            );
+            if r != 0 {
+                if !out_ptr.is_null() {
+                    raw::tj3Free(out_ptr as *mut libc::c_void);
+                }
+                return Err(tj3_err(handle, "error-turbojpeg-compress"));
+            }
+            let slice = std::slice::from_raw_parts(out_ptr, out_size as usize);
+            let vec = slice.to_vec();
+            raw::tj3Free(out_ptr as *mut libc::c_void);
+            Ok(vec)
+        }
+    }
+}
+
+fn tj3_err(handle: raw::tjhandle, key: &'static str) -> BlpError {
+    let msg = unsafe {
+        let p = raw::tj3GetErrorStr(handle);
+        if p.is_null() {
+            "unknown".to_string()
+        } else {
+            CStr::from_ptr(p)
+                .to_string_lossy()
+                .into_owned()
+        }
+    };
+    BlpError::new(ErrorKind::Encode, key).with_arg("msg", msg)
+}
@@ -0,0 +1,21 @@
+/// Общий префикс заголовков мипов — кандидат в общий JPEG-заголовок BLP.
+pub fn header_prefix(heads: &[&[u8]]) -> Vec<u8> {
+    if heads.is_empty() {
+        return Vec::new();
+    }
+    let min_len = heads
+        .iter()
+        .map(|h| h.len())
+        .min()
+        .unwrap();
+    let mut out = Vec::with_capacity(min_len);
+    for i in 0..min_len {
+        let b = heads[0][i];
+        if heads.iter().all(|h| h[i] == b) {
+            out.push(b);
+        } else {
+            break;
+        }
+    }
+    out
+}
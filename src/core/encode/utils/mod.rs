@@ -1,4 +1,11 @@
+#[cfg(feature = "turbojpeg")]
 pub mod pack_rgba_to_rgb_fast;
+#[cfg(feature = "turbojpeg")]
 pub mod pack_rgba_to_cmyk_fast;
+pub mod header_prefix;
 pub mod rebuild_minimal_jpeg_header;
-pub mod read_be_u16;
\ No newline at end of file
+pub mod read_be_u16;
+#[cfg(feature = "turbojpeg")]
+pub mod compress_turbojpeg;
+#[cfg(feature = "pure-jpeg")]
+pub mod compress_pure;
@@ -1,5 +1,6 @@
 use crate::core::encode::utils::read_be_u16::read_be_u16;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 
 /// Принимает JPEG-заголовок от SOI до SOS (включительно) и
 /// возвращает минимальный заголовок: SOI + [без APPn/COM] + первый SOF + SOS.
@@ -8,7 +9,7 @@ use crate::error::error::BlpError;
 pub fn rebuild_minimal_jpeg_header(header: &[u8]) -> Result<Vec<u8>, BlpError> {
     // Проверка SOI (FFD8)
     if header.len() < 4 || header[0] != 0xFF || header[1] != 0xD8 {
-        return Err(BlpError::new("jpeg.bad_soi"));
+        return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-bad-soi"));
     }
 
     let mut pos = 2usize;
@@ -38,12 +39,12 @@ pub fn rebuild_minimal_jpeg_header(header: &[u8]) -> Result<Vec<u8>, BlpError> {
 
         // Маркеры с длиной (2 байта BE сразу после id)
         if pos + 2 > header.len() {
-            return Err(BlpError::new("jpeg.seg_len"));
+            return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-segment-len"));
         }
         let seg_len = read_be_u16(&header[pos..pos + 2])? as usize;
         let end = pos + seg_len;
         if end > header.len() {
-            return Err(BlpError::new("jpeg.seg_trunc"));
+            return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-truncated"));
         }
 
         // Классификация маркера (инлайн вместо is_app/is_com/is_sof)
@@ -67,8 +68,8 @@ pub fn rebuild_minimal_jpeg_header(header: &[u8]) -> Result<Vec<u8>, BlpError> {
         pos = end;
     }
 
-    let (sos_s, sos_e) = sos_seg.ok_or_else(|| BlpError::new("jpeg.sos_missing"))?;
-    let (sof_s, sof_e) = sof_seg.ok_or_else(|| BlpError::new("jpeg.sof_missing"))?;
+    let (sos_s, sos_e) = sos_seg.ok_or_else(|| BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-sos-missing"))?;
+    let (sof_s, sof_e) = sof_seg.ok_or_else(|| BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-sof-missing"))?;
 
     // Сборка минимального заголовка
     let mut out = Vec::with_capacity(header.len());
@@ -1,17 +1,23 @@
 use crate::error::error::BlpError;
+use crate::core::encode::blp::EncodeOptions;
 use crate::core::image::ImageBlp;
 use std::fs;
 use std::path::Path;
 
 impl ImageBlp {
+    /// Кодирует с `quality`, остальное — по умолчанию; прочие настройки — через [`Self::export_blp_with`].
     pub fn export_blp(&self, out_path: &Path, quality: u8, mip_visible: &[bool]) -> Result<(), BlpError> {
+        self.export_blp_with(out_path, &EncodeOptions { quality, ..Default::default() }, mip_visible)
+    }
+
+    pub fn export_blp_with(&self, out_path: &Path, opts: &EncodeOptions, mip_visible: &[bool]) -> Result<(), BlpError> {
         if let Some(parent) = out_path.parent() {
             if !parent.as_os_str().is_empty() {
                 fs::create_dir_all(parent)?;
             }
         }
 
-        let ctx = self.encode_blp(quality, mip_visible)?;
+        let ctx = self.encode_blp_with(opts, mip_visible)?;
 
         // Сохраняем готовый контейнер
         fs::write(out_path, &ctx.bytes)?;
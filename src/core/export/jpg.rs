@@ -2,6 +2,7 @@ use crate::core::image::ImageBlp;
 use crate::core::mipmap::Mipmap;
 use crate::core::types::TextureType;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use std::fs;
 use std::path::Path;
 
@@ -19,14 +20,14 @@ impl ImageBlp {
 
         // Этот метод имеет смысл только для JPEG-BLP
         if self.texture_type != TextureType::JPEG {
-            return Err(BlpError::new("export-jpg.not-jpeg"));
+            return Err(BlpError::new(ErrorKind::UnsupportedCompression, "error-export-jpg-not-jpeg"));
         }
 
         // Общий header
         let h_off = self.header_offset;
         let h_len = self.header_length;
         if h_len == 0 || h_off.checked_add(h_len).is_none() || h_off + h_len > buf.len() {
-            return Err(BlpError::new("export-jpg.header.oob")
+            return Err(BlpError::new(ErrorKind::Truncated, "error-export-jpg-header-oob")
                 .with_arg("offset", h_off as u32)
                 .with_arg("length", h_len as u32)
                 .with_arg("buf_len", buf.len() as u32));
@@ -37,7 +38,7 @@ impl ImageBlp {
         let off = mip.offset;
         let len = mip.length;
         if len == 0 || off.checked_add(len).is_none() || off + len > buf.len() {
-            return Err(BlpError::new("export-jpg.mip.oob")
+            return Err(BlpError::new(ErrorKind::Truncated, "error-export-jpg-mip-oob")
                 .with_arg("offset", off as u32)
                 .with_arg("length", len as u32)
                 .with_arg("buf_len", buf.len() as u32));
@@ -1,6 +1,7 @@
 use crate::core::image::ImageBlp;
 use crate::core::mipmap::Mipmap;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use image::DynamicImage;
 use std::fs;
 use std::path::Path;
@@ -18,7 +19,7 @@ impl ImageBlp {
         let rgba = mip
             .image
             .as_ref()
-            .ok_or_else(|| BlpError::new("error-export_png").with_arg("msg", "no RGBA in mip"))?;
+            .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-export-png").with_arg("msg", "no RGBA in mip"))?;
 
         DynamicImage::ImageRgba8(rgba.clone()).save(out_path)?;
         Ok(())
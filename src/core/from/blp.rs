@@ -1,46 +1,35 @@
-use crate::core::image::{HEADER_SIZE, ImageBlp, MAX_MIPS};
+use crate::core::decode::decode_limits::DecodeLimits;
+use crate::core::hole::Hole;
+use crate::core::inspect::quality_estimate::QualityEstimate;
+use crate::core::inspect::quant_table::QuantTable;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::metadata::BlpMetadata;
 use crate::core::mipmap::Mipmap;
+use crate::core::from::blp_header::BlpHeader;
 use crate::core::types::{SourceKind, TextureType, Version};
 use crate::error::error::BlpError;
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use crate::error::error_kind::ErrorKind;
+use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::Cursor;
 
 impl ImageBlp {
-    pub(crate) fn from_buf_blp(buf: &[u8]) -> Result<Self, BlpError> {
-        let mut cursor = Cursor::new(buf);
-
-        let version_raw = cursor.read_u32::<BigEndian>()?;
-        let version = Version::try_from(version_raw)?;
-
-        let texture_type_raw = cursor.read_u32::<LittleEndian>()?;
-        let texture_type = TextureType::try_from(texture_type_raw)?;
-
-        let (compression, alpha_bits, alpha_type, has_mips) = if version >= Version::BLP2 {
-            (
-                cursor.read_u8()?,        // compression
-                cursor.read_u8()? as u32, // alpha_bits
-                cursor.read_u8()?,        // alpha_type
-                cursor.read_u8()?,        // has_mips flag
-            )
-        } else {
-            (
-                0u8,
-                cursor.read_u32::<LittleEndian>()?, // alpha_bits
-                0u8,
-                0u8,
-            )
-        };
-
-        let width = cursor.read_u32::<LittleEndian>()?;
-        let height = cursor.read_u32::<LittleEndian>()?;
-
-        let (extra, has_mipmaps) = if version <= Version::BLP1 {
-            (cursor.read_u32::<LittleEndian>()?, cursor.read_u32::<LittleEndian>()?)
-        } else {
-            (0u32, has_mips as u32)
-        };
+    pub(crate) fn from_buf_blp(buf: &[u8], limits: &DecodeLimits) -> Result<Self, BlpError> {
+        let BlpHeader {
+            version, //
+            texture_type,
+            compression,
+            alpha_bits,
+            alpha_type,
+            has_mips,
+            width,
+            height,
+            extra,
+            has_mipmaps,
+            offsets,
+            lengths,
+        } = BlpHeader::read(&mut Cursor::new(buf))?;
 
-        // --- читаем таблицы смещений/длин
+        // --- таблицы смещений/длин
         let mut mipmaps: [Mipmap; MAX_MIPS] = std::array::from_fn(|_| Mipmap::default());
         let (mut w, mut h) = (width, height);
 
@@ -48,10 +37,8 @@ impl ImageBlp {
 
         if version >= Version::BLP1 {
             for i in 0..MAX_MIPS {
-                mipmaps[i].offset = cursor.read_u32::<LittleEndian>()? as usize;
-            }
-            for i in 0..MAX_MIPS {
-                mipmaps[i].length = cursor.read_u32::<LittleEndian>()? as usize;
+                mipmaps[i].offset = offsets[i] as usize;
+                mipmaps[i].length = lengths[i] as usize;
                 if i < mi {
                     mipmaps[i].width = w;
                     w = (w / 2).max(1);
@@ -65,24 +52,30 @@ impl ImageBlp {
         // header_offset / header_length
         let (header_offset, header_length) = match texture_type {
             TextureType::JPEG => {
-                let base = HEADER_SIZE as usize;
+                let base = version.header_size() as usize;
                 if buf.len() < base + 4 {
-                    return Err(BlpError::new("truncated: cannot read JPEG header size"));
+                    return Err(BlpError::new(ErrorKind::Truncated, "error-jpeg-header-size-truncated"));
                 }
                 let mut c = Cursor::new(&buf[base..]);
                 let hdr_len = c.read_u32::<LittleEndian>()? as usize;
                 let hdr_off = base + 4;
-                if buf.len() < hdr_off + hdr_len {
-                    return Err(BlpError::new("truncated: JPEG header out of bounds"));
+                if hdr_off
+                    .checked_add(hdr_len)
+                    .is_none_or(|end| end > buf.len())
+                {
+                    return Err(BlpError::new(ErrorKind::Truncated, "error-jpeg-header-oob"));
                 }
                 (hdr_off, hdr_len)
             }
             TextureType::DIRECT => {
-                // палитра сразу после HEADER_SIZE
-                (HEADER_SIZE as usize, 256 * 4)
+                // палитра сразу после заголовка
+                (version.header_size() as usize, 256 * 4)
             }
         };
 
+        // лимиты — до того, как кто-то начнёт выделять память под пиксели
+        limits.check_mips(width, height, &mipmaps)?;
+
         // считаем дырки
         let mut ranges = Vec::new();
         for i in 0..MAX_MIPS {
@@ -100,18 +93,45 @@ impl ImageBlp {
         ranges.sort_by_key(|r| r.0);
 
         let mut prev_end = header_offset + header_length;
-        let mut holes = 0usize;
+        let mut hole_ranges = Vec::new();
         for (start, end) in &ranges {
-            if *start >= prev_end {
-                holes += start - prev_end;
+            if *start > prev_end {
+                hole_ranges.push(Hole { offset: prev_end, length: start - prev_end });
             }
             if *end > prev_end {
                 prev_end = *end;
             }
         }
         if buf.len() > prev_end {
-            holes += buf.len() - prev_end;
+            hole_ranges.push(Hole { offset: prev_end, length: buf.len() - prev_end });
         }
+        // метаданные — только если чанк целиком в дыре сразу за заголовком
+        let metadata = hole_ranges
+            .iter()
+            .find(|h| h.offset == header_offset + header_length)
+            .and_then(|h| BlpMetadata::parse(&buf[h.offset..h.offset + h.length]).ok())
+            .map(|(m, _)| m);
+        // DQT: в общем заголовке, а у «раздельных» раскладок — в потоке мипа 0
+        let quality_estimate = match texture_type {
+            TextureType::JPEG => {
+                let shared = &buf[header_offset..header_offset + header_length];
+                let mip0 = mipmaps[0]
+                    .offset
+                    .checked_add(mipmaps[0].length)
+                    .and_then(|end| buf.get(mipmaps[0].offset..end))
+                    .unwrap_or_default();
+                QuantTable::parse_all(shared)
+                    .ok()
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| QuantTable::parse_all(&[shared, mip0].concat()).ok())
+                    .and_then(QualityEstimate::from_tables)
+            }
+            TextureType::DIRECT => None,
+        };
+        let holes = hole_ranges
+            .iter()
+            .map(|h| h.length)
+            .sum();
 
         Ok(Self {
             version, //
@@ -126,6 +146,9 @@ impl ImageBlp {
             has_mipmaps,
             mipmaps: mipmaps.into_iter().collect(),
             holes,
+            hole_ranges,
+            metadata,
+            quality_estimate,
             header_offset,
             header_length,
             source: SourceKind::Blp,
@@ -0,0 +1,125 @@
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::types::{TextureType, Version};
+use crate::error::error::BlpError;
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use std::io::Read;
+
+/// Фиксированная часть заголовка BLP: от магии до таблиц смещений/длин мипов.
+/// Читается из любого `Read` — ровно столько байт, сколько нужно версии.
+#[derive(Debug, Clone)]
+pub(crate) struct BlpHeader {
+    pub version: Version,
+    pub texture_type: TextureType,
+    pub compression: u8,
+    pub alpha_bits: u32,
+    pub alpha_type: u8,
+    pub has_mips: u8,
+    pub width: u32,
+    pub height: u32,
+    pub extra: u32,
+    pub has_mipmaps: u32,
+    /// Для BLP0 таблиц нет (мипы лежат в отдельных файлах) — нули.
+    pub offsets: [u32; MAX_MIPS],
+    pub lengths: [u32; MAX_MIPS],
+}
+
+impl BlpHeader {
+    /// Поля заголовка из `img`; таблицы мипов пустые.
+    pub fn of(img: &ImageBlp) -> Self {
+        Self {
+            version: img.version,
+            texture_type: img.texture_type,
+            compression: img.compression,
+            alpha_bits: img.alpha_bits,
+            alpha_type: img.alpha_type,
+            has_mips: img.has_mips,
+            width: img.width,
+            height: img.height,
+            extra: img.extra,
+            has_mipmaps: img.has_mipmaps,
+            offsets: [0; MAX_MIPS],
+            lengths: [0; MAX_MIPS],
+        }
+    }
+
+    pub fn read<R: Read>(r: &mut R) -> Result<Self, BlpError> {
+        let version_raw = r.read_u32::<BigEndian>()?;
+        let version = Version::try_from(version_raw)?;
+
+        let texture_type_raw = r.read_u32::<LittleEndian>()?;
+        let texture_type = TextureType::try_from(texture_type_raw)?;
+
+        let (compression, alpha_bits, alpha_type, has_mips) = if version >= Version::BLP2 {
+            (
+                r.read_u8()?,        // compression
+                r.read_u8()? as u32, // alpha_bits
+                r.read_u8()?,        // alpha_type
+                r.read_u8()?,        // has_mips flag
+            )
+        } else {
+            (
+                0u8,
+                r.read_u32::<LittleEndian>()?, // alpha_bits
+                0u8,
+                0u8,
+            )
+        };
+
+        let width = r.read_u32::<LittleEndian>()?;
+        let height = r.read_u32::<LittleEndian>()?;
+
+        let (extra, has_mipmaps) = if version <= Version::BLP1 {
+            (r.read_u32::<LittleEndian>()?, r.read_u32::<LittleEndian>()?)
+        } else {
+            (0u32, has_mips as u32)
+        };
+
+        let mut offsets = [0u32; MAX_MIPS];
+        let mut lengths = [0u32; MAX_MIPS];
+        if version >= Version::BLP1 {
+            for v in offsets.iter_mut() {
+                *v = r.read_u32::<LittleEndian>()?;
+            }
+            for v in lengths.iter_mut() {
+                *v = r.read_u32::<LittleEndian>()?;
+            }
+        }
+
+        Ok(Self {
+            version, //
+            texture_type,
+            compression,
+            alpha_bits,
+            alpha_type,
+            has_mips,
+            width,
+            height,
+            extra,
+            has_mipmaps,
+            offsets,
+            lengths,
+        })
+    }
+
+    /// Обратное к [`BlpHeader::read`]: те же поля в том же порядке для версии `self.version`.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(self.version as u32).to_be_bytes());
+        out.extend_from_slice(&(self.texture_type as u32).to_le_bytes());
+        if self.version >= Version::BLP2 {
+            out.extend_from_slice(&[self.compression, self.alpha_bits as u8, self.alpha_type, self.has_mips]);
+        } else {
+            out.extend_from_slice(&self.alpha_bits.to_le_bytes());
+        }
+        out.extend_from_slice(&self.width.to_le_bytes());
+        out.extend_from_slice(&self.height.to_le_bytes());
+        if self.version <= Version::BLP1 {
+            out.extend_from_slice(&self.extra.to_le_bytes());
+            out.extend_from_slice(&self.has_mipmaps.to_le_bytes());
+        }
+        if self.version >= Version::BLP1 {
+            for v in self.offsets.iter().chain(&self.lengths) {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
+use crate::core::decode::decode_limits::DecodeLimits;
 use crate::core::image::{ImageBlp, MAX_MIPS};
 use crate::core::mipmap::Mipmap;
 use crate::core::types::SourceKind;
 use crate::error::error::BlpError;
-use image;
-use image::GenericImageView;
+use crate::error::error_kind::ErrorKind;
 
 const MAX_POW2: u32 = 8192; // при необходимости скорректируй верхнюю границу
 
@@ -73,10 +73,22 @@ impl ImageBlp {
     /// 3) Формируем цепочку мипов (только width/height), image=None
     ///    Хвост после 1×1 заполняем 0×0 (а не 1×1).
     pub fn from_buf_image(buf: &[u8]) -> Result<Self, BlpError> {
-        let dyn_img = image::load_from_memory(buf)?;
-        let (w0, h0) = dyn_img.dimensions();
+        Self::from_buf_image_with(buf, &DecodeLimits::default())
+    }
+
+    /// Размеры читаются только из заголовка (без декодирования пикселей) и сверяются с `limits`.
+    pub(crate) fn from_buf_image_with(buf: &[u8], limits: &DecodeLimits) -> Result<Self, BlpError> {
+        let (w0, h0) = limits.image_reader(buf)?.into_dimensions()?;
+        let blp = Self::from_dims(w0, h0)?;
+        limits.check_dims(blp.width, blp.height)?;
+        Ok(blp)
+    }
+
+    /// Разметка по исходным размерам (шаги 2–3 из `from_buf_image`).
+    /// Общая для байтового пути и для `from_rgba`.
+    pub(crate) fn from_dims(w0: u32, h0: u32) -> Result<Self, BlpError> {
         if w0 == 0 || h0 == 0 {
-            return Err(BlpError::new("error-image-empty")
+            return Err(BlpError::new(ErrorKind::InvalidImage, "error-image-empty")
                 .with_arg("width", w0)
                 .with_arg("height", h0));
         }
@@ -96,6 +108,7 @@ impl ImageBlp {
                     width: w,
                     height: h,
                     image: None, // НЕ создаём RgbaImage
+                    jpeg: None,
                     offset: 0,
                     length: 0,
                 });
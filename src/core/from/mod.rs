@@ -1,2 +1,4 @@
 mod blp;
-mod image;
\ No newline at end of file
+pub(crate) mod blp_header;
+mod image;
+mod rgba;
\ No newline at end of file
@@ -0,0 +1,24 @@
+use crate::core::image::ImageBlp;
+use crate::core::types::SourceKind;
+use crate::error::error::BlpError;
+use image::{DynamicImage, RgbaImage};
+
+impl ImageBlp {
+    /// Пиксели уже в памяти: та же pow2-разметка, что у `from_buf_image`,
+    /// и та же цепочка мипов, что у `decode_image`, но без сериализации в PNG.
+    ///
+    /// `mip_visible[i] == false` → пиксели мипа `i` не материализуются.
+    /// Отсутствующие индексы считаются `true`.
+    pub fn from_rgba(img: RgbaImage, mip_visible: &[bool]) -> Result<Self, BlpError> {
+        let mut blp = Self::from_dims(img.width(), img.height())?;
+        blp.decode_rgba(img, mip_visible)?;
+        Ok(blp)
+    }
+}
+
+/// Все мипы видимы. Пустое изображение (0×N) даёт `ImageBlp` без уровней.
+impl From<DynamicImage> for ImageBlp {
+    fn from(img: DynamicImage) -> Self {
+        Self::from_rgba(img.into_rgba8(), &[]).unwrap_or_else(|_| ImageBlp { source: SourceKind::Image, ..Default::default() })
+    }
+}
@@ -0,0 +1,23 @@
+/// Участок файла, не покрытый заголовком и ни одним мипом.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hole {
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl Hole {
+    /// Магия чанка метаданных, который наш кодировщик пишет после общего JPEG-заголовка
+    /// (старые версии писали только её).
+    pub const RAFT: &'static [u8] = b"RAFT";
+
+    /// Байты дыры; пусто, если `buf` — не тот файл.
+    pub fn bytes<'a>(&self, buf: &'a [u8]) -> &'a [u8] {
+        buf.get(self.offset..self.offset + self.length)
+            .unwrap_or_default()
+    }
+
+    /// Дыра начинается с `RAFT`: маркер или чанк [`BlpMetadata`](crate::core::metadata::BlpMetadata).
+    pub fn is_raft(&self, buf: &[u8]) -> bool {
+        self.bytes(buf).starts_with(Self::RAFT)
+    }
+}
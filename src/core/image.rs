@@ -1,9 +1,16 @@
+use crate::core::decode::decode_limits::DecodeLimits;
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::hole::Hole;
+use crate::core::inspect::quality_estimate::QualityEstimate;
+use crate::core::metadata::BlpMetadata;
 use crate::core::mipmap::Mipmap;
 use crate::core::types::{SourceKind, TextureType, Version};
 use crate::error::error::BlpError;
 
 pub const MAX_MIPS: usize = 16;
 pub const HEADER_SIZE: u64 = 156;
+/// У BLP2 нет `extra`/`has_mipmaps`, а флаги — по байту: заголовок на 8 байт короче.
+pub const HEADER_SIZE_BLP2: u64 = 148;
 
 #[derive(Debug, Default)]
 pub struct ImageBlp {
@@ -20,7 +27,13 @@ pub struct ImageBlp {
     pub has_mipmaps: u32, // meaningful only if version <= BLP1 or >= BLP2
     //
     pub mipmaps: Vec<Mipmap>,
+    /// Сколько байт не покрыто заголовком и мипами; сами участки — в `hole_ranges`.
     pub holes: usize,
+    pub hole_ranges: Vec<Hole>,
+    /// Чанк метаданных нашего кодировщика, если он лежит в дыре сразу за заголовком.
+    pub metadata: Option<BlpMetadata>,
+    /// Качество IJG по таблицам DQT общего JPEG-заголовка (или мипа 0, если в общем их нет).
+    pub quality_estimate: Option<QualityEstimate>,
     pub header_offset: usize,
     pub header_length: usize,
     //
@@ -29,10 +42,16 @@ pub struct ImageBlp {
 
 impl ImageBlp {
     pub fn from_buf(buf: &[u8]) -> Result<Self, BlpError> {
+        Self::from_buf_with(buf, &DecodeLimits::default())
+    }
+
+    /// Same as [`ImageBlp::from_buf`], with explicit limits: header dimensions and mip table
+    /// are checked before anything is allocated.
+    pub fn from_buf_with(buf: &[u8], limits: &DecodeLimits) -> Result<Self, BlpError> {
         if buf.len() >= 3 && &buf[..3] == b"BLP" {
-            Self::from_buf_blp(buf)
+            Self::from_buf_blp(buf, limits)
         } else {
-            Self::from_buf_image(buf)
+            Self::from_buf_image_with(buf, limits)
         }
     }
 
@@ -41,12 +60,22 @@ impl ImageBlp {
     /// `mip_visible[i] == false` → skip decoding for mip `i`.
     /// Missing indices are treated as `true`.
     pub fn decode(&mut self, buf: &[u8], mip_visible: &[bool]) -> Result<(), BlpError> {
+        self.decode_with(buf, mip_visible, &DecodeOptions::default())
+    }
+
+    /// Same as [`ImageBlp::decode`], with explicit JPEG backend / fallback, threads and limits.
+    pub fn decode_with(&mut self, buf: &[u8], mip_visible: &[bool], opts: &DecodeOptions) -> Result<(), BlpError> {
         match self.source {
-            SourceKind::Blp => match self.texture_type {
-                TextureType::DIRECT => self.decode_direct(buf, mip_visible),
-                TextureType::JPEG => self.decode_jpeg(buf, mip_visible),
-            },
-            SourceKind::Image => self.decode_image(buf, mip_visible),
+            SourceKind::Blp => {
+                // Fields are public: re-check even if `from_buf_with` already did.
+                opts.limits
+                    .check_mips(self.width, self.height, &self.mipmaps)?;
+                match self.texture_type {
+                    TextureType::DIRECT => self.decode_direct(buf, mip_visible, opts),
+                    TextureType::JPEG => self.decode_jpeg(buf, mip_visible, opts),
+                }
+            }
+            SourceKind::Image => self.decode_image(buf, mip_visible, &opts.limits),
         }
     }
 }
@@ -0,0 +1,127 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Кадр JPEG (SOF): где лежат размеры и какие они.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SofInfo {
+    /// Смещение маркера `FF Cx` от начала потока.
+    pub pos: usize,
+    /// `0xC0` — baseline, `0xC2` — progressive, …
+    pub marker: u8,
+    /// Смещения big-endian `u16` высоты и ширины — их и правят «защищённые» текстуры.
+    pub height_off: usize,
+    pub width_off: usize,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl SofInfo {
+    /// Записать размеры в поток, разметку которого описывает этот SOF.
+    pub fn write_dims(&self, buf: &mut [u8], width: u16, height: u16) {
+        buf[self.height_off..self.height_off + 2].copy_from_slice(&height.to_be_bytes());
+        buf[self.width_off..self.width_off + 2].copy_from_slice(&width.to_be_bytes());
+    }
+}
+
+/// Разметка заголовка JPEG от SOI до конца сегмента SOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JpegHeaderInfo {
+    /// Первый байт энтропийных данных (сразу после SOS).
+    pub header_end: usize,
+    /// Первый SOF; `None`, если его нет до SOS.
+    pub sof: Option<SofInfo>,
+}
+
+impl JpegHeaderInfo {
+    /// Пройти сегменты от SOI до SOS включительно.
+    pub fn parse(buf: &[u8]) -> Result<Self, BlpError> {
+        let err = |key: &'static str| BlpError::new(ErrorKind::InvalidJpeg, key);
+        if buf.get(..2) != Some(&[0xFF, 0xD8][..]) {
+            return Err(err("error-jpeg-bad-soi"));
+        }
+        let mut sof = None;
+        let mut pos = 2;
+        loop {
+            match buf.get(pos) {
+                Some(0xFF) => {}
+                Some(_) => return Err(err("error-jpeg-marker-expected")),
+                None => return Err(err("error-jpeg-sos-missing")),
+            }
+            // заполняющие 0xFF перед маркером допустимы
+            while buf.get(pos + 1) == Some(&0xFF) {
+                pos += 1;
+            }
+            let marker = *buf
+                .get(pos + 1)
+                .ok_or_else(|| err("error-jpeg-truncated"))?;
+            let seg = pos;
+            pos += 2;
+            match marker {
+                // без длины: SOI, RST0..7, TEM
+                0xD8 | 0xD0..=0xD7 | 0x01 => continue,
+                0xD9 => return Err(err("error-jpeg-eoi-before-sos")),
+                _ => {}
+            }
+            let len = match buf.get(pos..pos + 2) {
+                Some(b) => u16::from_be_bytes([b[0], b[1]]) as usize,
+                None => return Err(err("error-jpeg-truncated")),
+            };
+            if len < 2 {
+                return Err(err("error-jpeg-segment-len"));
+            }
+            if pos + len > buf.len() {
+                return Err(err("error-jpeg-truncated"));
+            }
+            // SOF0..SOF15, кроме DHT (C4), JPG (C8) и DAC (CC): [len:2][P:1][Y:2][X:2]
+            if sof.is_none() && matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                if len < 7 {
+                    return Err(err("error-jpeg-segment-len"));
+                }
+                sof = Some(SofInfo {
+                    pos: seg,
+                    marker,
+                    height_off: pos + 3,
+                    width_off: pos + 5,
+                    height: u16::from_be_bytes([buf[pos + 3], buf[pos + 4]]),
+                    width: u16::from_be_bytes([buf[pos + 5], buf[pos + 6]]),
+                });
+            }
+            pos += len;
+            if marker == 0xDA {
+                return Ok(Self { header_end: pos, sof });
+            }
+        }
+    }
+
+    /// Переписать размеры в SOF полного потока, если они расходятся с `width`×`height`.
+    /// `Ok(true)` — поток изменён; без SOF или при размерах больше `u16` поток не трогается.
+    pub fn fix_sof_dims(buf: &mut [u8], width: u32, height: u32) -> Result<bool, BlpError> {
+        let Some(sof) = Self::parse(buf)?.sof else {
+            return Ok(false);
+        };
+        let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Ok(false);
+        };
+        if (sof.width, sof.height) == (w, h) {
+            return Ok(false);
+        }
+        sof.write_dims(buf, w, h);
+        Ok(true)
+    }
+
+    /// Равны ли заголовки, не считая четырёх байт размеров в SOF.
+    pub fn eq_except_dims(a: &[u8], b: &[u8], ia: &Self, ib: &Self) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        let dims = |i: &Self| i.sof.map(|s| [s.height_off, s.height_off + 1, s.width_off, s.width_off + 1]);
+        match (dims(ia), dims(ib)) {
+            (Some(da), Some(db)) if da == db => a
+                .iter()
+                .zip(b)
+                .enumerate()
+                .all(|(i, (x, y))| x == y || da.contains(&i)),
+            _ => a == b,
+        }
+    }
+}
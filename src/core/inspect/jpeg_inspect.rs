@@ -0,0 +1,202 @@
+use crate::core::image::ImageBlp;
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::types::TextureType;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::ext::json_str::write_json_str;
+use sha1::{Digest, Sha1};
+use std::fmt::Write;
+
+/// Один мип JPEG-BLP: `[общий заголовок][хвост]` как самостоятельный JPEG.
+#[derive(Debug, Clone)]
+pub struct MipJpegInfo {
+    pub mip: usize,
+    /// Размеры мипа по заголовку BLP.
+    pub width: u32,
+    pub height: u32,
+    /// Разметка собранного JPEG; ошибка — поток не разбирается до SOS.
+    pub header: Result<JpegHeaderInfo, BlpError>,
+    /// Длина JPEG-заголовка (до данных скана, 0 при ошибке разбора) и его SHA-1 (hex).
+    pub header_len: usize,
+    pub header_sha1: String,
+    /// Общий префикс всего потока с потоком базового мипа; у него самого — `None`.
+    /// «mip0» в именах полей — базовый мип: первый мип с данными (обычно 0).
+    pub lcp_to_mip0: Option<usize>,
+    /// Заголовок побайтно равен заголовку базового мипа; `false`, если хоть один из них не разобран
+    /// (у самого базового — разобран ли он).
+    pub header_eq_mip0: bool,
+    /// Заголовок совпадает с базовым мипом, если не считать байт размеров в SOF.
+    pub header_eq_mip0_except_dims: bool,
+}
+
+impl MipJpegInfo {
+    /// Размеры из SOF, если кадр найден.
+    pub fn sof_dims(&self) -> Option<(u16, u16)> {
+        self.header
+            .as_ref()
+            .ok()?
+            .sof
+            .map(|s| (s.width, s.height))
+    }
+
+    /// SOF есть и не совпадает с размерами мипа.
+    pub fn sof_mismatch(&self) -> bool {
+        self.sof_dims()
+            .is_some_and(|(w, h)| (w as u32, h as u32) != (self.width, self.height))
+    }
+}
+
+/// Разбор общего JPEG-заголовка по мипам — для аудита «защищённых» и пропатченных текстур.
+#[derive(Debug, Clone)]
+pub struct JpegInspect {
+    /// Длина общего заголовка из BLP.
+    pub shared_header_len: usize,
+    /// Мипы с данными в границах файла.
+    pub mips: Vec<MipJpegInfo>,
+}
+
+impl JpegInspect {
+    /// Все заголовки мипов разобраны и побайтно равны заголовку базового мипа.
+    pub fn all_equal(&self) -> bool {
+        self.mips.iter().all(|m| m.header_eq_mip0)
+    }
+
+    pub fn all_equal_except_dims(&self) -> bool {
+        self.mips
+            .iter()
+            .all(|m| m.header_eq_mip0_except_dims)
+    }
+
+    /// Сколько мипов с SOF, не совпадающим с размерами мипа.
+    pub fn sof_mismatches(&self) -> usize {
+        self.mips
+            .iter()
+            .filter(|m| m.sof_mismatch())
+            .count()
+    }
+
+    pub const CSV_HEADER: &'static str = "file,mip,blp_w,blp_h,sof_w,sof_h,header_len,header_sha1,lcp_to_mip0,header_eq_mip0,header_eq_mip0_except_dims,error\n";
+
+    /// Строки CSV (без шапки [`JpegInspect::CSV_HEADER`]), по одной на мип.
+    pub fn to_csv(&self, file: &str) -> String {
+        let mut out = String::new();
+        for m in &self.mips {
+            let (sw, sh) = m
+                .sof_dims()
+                .map(|(w, h)| (w.to_string(), h.to_string()))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "\"{}\",{},{},{},{sw},{sh},{},{},{},{},{},{}",
+                file.replace('"', "\"\""),
+                m.mip,
+                m.width,
+                m.height,
+                m.header_len,
+                m.header_sha1,
+                m.lcp_to_mip0
+                    .map(|l| l.to_string())
+                    .unwrap_or_default(),
+                m.header_eq_mip0 as u8,
+                m.header_eq_mip0_except_dims as u8,
+                m.header
+                    .as_ref()
+                    .err()
+                    .map(|e| e.key)
+                    .unwrap_or("")
+            );
+        }
+        out
+    }
+
+    /// `{"file", "shared_header_len", "all_equal", "all_equal_except_dims", "sof_mismatches", "mips": [..]}`.
+    pub fn to_json(&self, file: &str) -> String {
+        let mut out = String::from("{\"file\":");
+        write_json_str(file, &mut out);
+        let _ = write!(out, ",\"shared_header_len\":{},\"all_equal\":{},\"all_equal_except_dims\":{},\"sof_mismatches\":{},\"mips\":[", self.shared_header_len, self.all_equal(), self.all_equal_except_dims(), self.sof_mismatches());
+        for (i, m) in self.mips.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let opt = |v: Option<String>| v.unwrap_or_else(|| "null".into());
+            let sof = m.header.as_ref().ok().and_then(|h| h.sof);
+            let _ = write!(
+                out,
+                "{{\"mip\":{},\"width\":{},\"height\":{},\"sof_width\":{},\"sof_height\":{},\"sof_marker\":{},\"sof_offset\":{},\"header_len\":{},\"header_sha1\":\"{}\",\"lcp_to_mip0\":{},\"header_eq_mip0\":{},\"header_eq_mip0_except_dims\":{},\"error\":{}}}",
+                m.mip,
+                m.width,
+                m.height,
+                opt(sof.map(|s| s.width.to_string())),
+                opt(sof.map(|s| s.height.to_string())),
+                opt(sof.map(|s| s.marker.to_string())),
+                opt(sof.map(|s| s.pos.to_string())),
+                m.header_len,
+                m.header_sha1,
+                opt(m.lcp_to_mip0.map(|l| l.to_string())),
+                m.header_eq_mip0,
+                m.header_eq_mip0_except_dims,
+                opt(m.header.as_ref().err().map(|e| format!("\"{}\"", e.key)))
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl ImageBlp {
+    /// Собрать JPEG каждого мипа и сравнить его заголовок с базовым (первым мипом с данными): SOF
+    /// и его смещения, SHA-1 заголовка, общий префикс. Пиксели не декодируются.
+    pub fn jpeg_inspect(&self, buf: &[u8]) -> Result<JpegInspect, BlpError> {
+        if self.texture_type != TextureType::JPEG {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-inspect-not-jpeg"));
+        }
+        let shared = self.jpeg_header(buf)?;
+
+        let fulls: Vec<(usize, Vec<u8>)> = self
+            .mipmaps
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.length > 0 && m.offset.checked_add(m.length).is_some_and(|end| end <= buf.len()))
+            .map(|(i, m)| (i, [shared, &buf[m.offset..m.offset + m.length]].concat()))
+            .collect();
+
+        let mut mips: Vec<MipJpegInfo> = Vec::with_capacity(fulls.len());
+        for (i, full) in &fulls {
+            let header = JpegHeaderInfo::parse(full);
+            let header_len = header
+                .as_ref()
+                .map(|h| h.header_end)
+                .unwrap_or(0);
+            let head = &full[..header_len];
+            let (lcp, eq, eq_dims) = match mips.first() {
+                None => (None, header.is_ok(), header.is_ok()),
+                Some(m0) => {
+                    let (full0, head0) = (&fulls[0].1, &fulls[0].1[..m0.header_len]);
+                    let lcp = full0
+                        .iter()
+                        .zip(full)
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    // неразобранный заголовок ни с чем не равен
+                    let (eq, eq_dims) = match (&m0.header, &header) {
+                        (Ok(i0), Ok(ii)) => (head == head0, JpegHeaderInfo::eq_except_dims(head, head0, ii, i0)),
+                        _ => (false, false),
+                    };
+                    (Some(lcp), eq, eq_dims)
+                }
+            };
+            mips.push(MipJpegInfo {
+                mip: *i,
+                width: self.mipmaps[*i].width,
+                height: self.mipmaps[*i].height,
+                header,
+                header_len,
+                header_sha1: hex::encode(Sha1::digest(head)),
+                lcp_to_mip0: lcp,
+                header_eq_mip0: eq,
+                header_eq_mip0_except_dims: eq_dims,
+            });
+        }
+        Ok(JpegInspect { shared_header_len: shared.len(), mips })
+    }
+}
@@ -0,0 +1,4 @@
+pub mod jpeg_header_info;
+pub mod jpeg_inspect;
+pub mod quality_estimate;
+pub mod quant_table;
@@ -0,0 +1,74 @@
+use crate::core::inspect::quant_table::QuantTable;
+
+/// Стандартные таблицы IJG (Annex K), естественный порядок.
+const STD_LUMINANCE: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, //
+    14, 13, 16, 24, 40, 57, 69, 56, 14, 17, 22, 29, 51, 87, 80, 62, //
+    18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113, 92, //
+    49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+const STD_CHROMINANCE: [u16; 64] = [
+    17, 18, 24, 47, 99, 99, 99, 99, 18, 21, 26, 66, 99, 99, 99, 99, //
+    24, 26, 56, 99, 99, 99, 99, 99, 47, 66, 99, 99, 99, 99, 99, 99, //
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, //
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+];
+
+/// Оценка качества IJG (`-quality` у cjpeg/libjpeg-turbo, `--quality` у `to-blp`) по таблицам DQT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityEstimate {
+    /// Ближайшее качество 1–100.
+    pub quality: u8,
+    /// Таблицы совпали с таблицами IJG для `quality` точно.
+    pub exact: bool,
+    /// Сумма отклонений от таблиц IJG для `quality` по всем сравниваемым коэффициентам.
+    pub error: u32,
+    /// Разобранные таблицы.
+    pub tables: Vec<QuantTable>,
+}
+
+impl QualityEstimate {
+    /// Таблица с `Tq = 1` сравнивается с таблицей цветности IJG, остальные — с таблицей яркости
+    /// (RGB и CMYK нашего кодировщика квантуются одной таблицей 0). `None` — таблиц нет.
+    pub fn from_tables(tables: Vec<QuantTable>) -> Option<Self> {
+        if tables.is_empty() {
+            return None;
+        }
+        let (quality, error) = (1..=100u8)
+            .map(|q| {
+                let err: u32 = tables
+                    .iter()
+                    .map(|t| {
+                        let base = if t.id == 1 { &STD_CHROMINANCE } else { &STD_LUMINANCE };
+                        let cap = if t.wide { 32767 } else { 255 };
+                        t.values
+                            .iter()
+                            .zip(ijg_table(base, q, cap))
+                            .map(|(&v, s)| v.abs_diff(s) as u32)
+                            .sum::<u32>()
+                    })
+                    .sum();
+                (q, err)
+            })
+            // при равенстве — большее качество: на высоких q таблицы упираются в 1
+            .min_by_key(|&(q, err)| (err, u8::MAX - q))?;
+        Some(Self { quality, exact: error == 0, error, tables })
+    }
+
+    /// Таблица `Tq = 0`, если помещается в 8 бит: ею наш кодировщик квантует все компоненты
+    /// (у файлов Blizzard SOF тоже ссылается только на неё, DQT 1 не используется).
+    pub fn main_table(&self) -> Option<[u16; 64]> {
+        self.tables
+            .iter()
+            .find(|t| t.id == 0)
+            .map(|t| t.values)
+            .filter(|v| v.iter().all(|&v| v <= 255))
+    }
+}
+
+/// Таблица IJG для качества `q` (как `jpeg_set_quality`).
+fn ijg_table(base: &[u16; 64], q: u8, cap: u32) -> [u16; 64] {
+    let q = q.clamp(1, 100) as u32;
+    let scale = if q < 50 { 5000 / q } else { 200 - 2 * q };
+    base.map(|b| ((b as u32 * scale + 50) / 100).clamp(1, cap) as u16)
+}
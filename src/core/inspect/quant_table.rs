@@ -0,0 +1,76 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Порядок зигзага: `ZIGZAG[k]` — индекс в естественном порядке (строка × 8 + столбец) k-го
+/// коэффициента DQT.
+pub(crate) const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, //
+    12, 19, 26, 33, 40, 48, 41, 34, 27, 20, 13, 6, 7, 14, 21, 28, //
+    35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, //
+    58, 59, 52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+/// Таблица квантования из сегмента DQT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantTable {
+    /// `Tq`: 0 — обычно яркость, 1 — цветность.
+    pub id: u8,
+    /// `Pq = 1`: 16-битные значения.
+    pub wide: bool,
+    /// Значения в естественном порядке (строка × 8 + столбец).
+    pub values: [u16; 64],
+}
+
+impl QuantTable {
+    /// Все DQT от SOI до SOS; повторное определение того же `Tq` заменяет прежнее, как у декодера.
+    /// Поток может обрываться после SOS — дальше не читается.
+    pub fn parse_all(jpeg: &[u8]) -> Result<Vec<QuantTable>, BlpError> {
+        let err = |key: &'static str| BlpError::new(ErrorKind::InvalidJpeg, key);
+        if jpeg.get(..2) != Some(&[0xFF, 0xD8][..]) {
+            return Err(err("error-jpeg-bad-soi"));
+        }
+        let mut tables: Vec<QuantTable> = Vec::new();
+        let mut pos = 2;
+        loop {
+            while jpeg.get(pos) == Some(&0xFF) && jpeg.get(pos + 1) == Some(&0xFF) {
+                pos += 1;
+            }
+            let marker = match jpeg.get(pos..pos + 2) {
+                Some(&[0xFF, m]) => m,
+                Some(_) => return Err(err("error-jpeg-marker-expected")),
+                None => return Ok(tables),
+            };
+            pos += 2;
+            match marker {
+                0xD8 | 0xD0..=0xD7 | 0x01 => continue,
+                0xD9 | 0xDA => return Ok(tables),
+                _ => {}
+            }
+            let len = jpeg
+                .get(pos..pos + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+                .ok_or_else(|| err("error-jpeg-truncated"))?;
+            let seg = jpeg
+                .get(pos + 2..pos + len.max(2))
+                .ok_or_else(|| err("error-jpeg-truncated"))?;
+            if marker == 0xDB {
+                let mut p = 0;
+                while p < seg.len() {
+                    let (wide, id) = (seg[p] >> 4 != 0, seg[p] & 0x0F);
+                    let size = if wide { 128 } else { 64 };
+                    let raw = seg
+                        .get(p + 1..p + 1 + size)
+                        .ok_or_else(|| err("error-jpeg-segment-len"))?;
+                    let mut values = [0u16; 64];
+                    for (k, &n) in ZIGZAG.iter().enumerate() {
+                        values[n] = if wide { u16::from_be_bytes([raw[2 * k], raw[2 * k + 1]]) } else { raw[k] as u16 };
+                    }
+                    tables.retain(|t| t.id != id);
+                    tables.push(QuantTable { id, wide, values });
+                    p += 1 + size;
+                }
+            }
+            pos += len;
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Чанк метаданных, который кодировщик пишет сразу за общим JPEG-заголовком (игра эти байты не читает).
+///
+/// Раскладка, все числа little-endian:
+///
+/// ```text
+/// "RAFT"      магия (раньше кодировщик писал только её)
+/// version: u8 = 1
+/// length:  u32 — длина записей ниже
+/// записи:  { key_len: u8, key: UTF-8, value_len: u16, value: UTF-8 } × N
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlpMetadata {
+    /// Пары ключ–значение в порядке записи; ключи уникальны.
+    pub entries: Vec<(String, String)>,
+}
+
+impl BlpMetadata {
+    pub const MAGIC: &'static [u8; 4] = b"RAFT";
+    pub const VERSION: u8 = 1;
+    /// Больше не читаем: чанк лежит в дыре перед данными мипов, а не вместо них.
+    pub const MAX_LEN: usize = 64 * 1024;
+
+    /// Кодировщик и его версия, например `blp 0.1.0`.
+    pub const ENCODER: &'static str = "encoder";
+    /// SHA-1 (hex) исходного файла.
+    pub const SOURCE_SHA1: &'static str = "source_sha1";
+    /// Имя исходного файла.
+    pub const SOURCE: &'static str = "source";
+    pub const QUALITY: &'static str = "quality";
+    pub const BACKEND: &'static str = "backend";
+    /// Маска мипов: `1` — уровень закодирован, `0` — пропущен.
+    pub const MIPS: &'static str = "mips";
+    pub const AUTHOR: &'static str = "author";
+    /// RFC 3339, UTC.
+    pub const TIMESTAMP: &'static str = "timestamp";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Записать значение; существующий ключ перезаписывается на месте.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        let (key, value) = (key.into(), value.into());
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(e) => e.1 = value,
+            None => self.entries.push((key, value)),
+        }
+        self
+    }
+
+    /// Чанк целиком, с магией и длиной.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BlpError> {
+        let mut body = Vec::new();
+        for (k, v) in &self.entries {
+            let too_long = || BlpError::new(ErrorKind::InvalidArgument, "error-metadata-too-long").with_arg("key", k.clone());
+            body.push(u8::try_from(k.len()).map_err(|_| too_long())?);
+            body.extend_from_slice(k.as_bytes());
+            body.extend_from_slice(&u16::try_from(v.len()).map_err(|_| too_long())?.to_le_bytes());
+            body.extend_from_slice(v.as_bytes());
+        }
+        if body.len() > Self::MAX_LEN {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-metadata-too-long").with_arg("key", "*"));
+        }
+        let mut out = Vec::with_capacity(9 + body.len());
+        out.extend_from_slice(Self::MAGIC);
+        out.push(Self::VERSION);
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(&body);
+        Ok(out)
+    }
+
+    /// Разобрать чанк с начала `buf`. Возвращает метаданные и полную длину чанка.
+    pub fn parse(buf: &[u8]) -> Result<(Self, usize), BlpError> {
+        let truncated = || BlpError::new(ErrorKind::Truncated, "error-metadata-truncated");
+        if buf.get(..4) != Some(&Self::MAGIC[..]) {
+            return Err(BlpError::new(ErrorKind::InvalidHeader, "error-metadata-magic"));
+        }
+        let version = *buf.get(4).ok_or_else(truncated)?;
+        if version != Self::VERSION {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-metadata-version").with_arg("version", version as u32));
+        }
+        let len = buf
+            .get(5..9)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(truncated)?;
+        if len > Self::MAX_LEN {
+            return Err(BlpError::new(ErrorKind::LimitExceeded, "error-metadata-too-long").with_arg("key", "*"));
+        }
+        let body = buf.get(9..9 + len).ok_or_else(truncated)?;
+
+        fn take<'a>(body: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8], BlpError> {
+            let s = body
+                .get(*pos..*pos + n)
+                .ok_or_else(|| BlpError::new(ErrorKind::Truncated, "error-metadata-truncated"))?;
+            *pos += n;
+            Ok(s)
+        }
+        let utf8 = |b: &[u8]| String::from_utf8(b.to_vec()).map_err(|e| BlpError::new(ErrorKind::InvalidHeader, "error-metadata-utf8").push_std(e));
+
+        let mut entries = Vec::new();
+        let mut pos = 0;
+        while pos < body.len() {
+            let klen = take(body, &mut pos, 1)?[0] as usize;
+            let key = utf8(take(body, &mut pos, klen)?)?;
+            let vlen = take(body, &mut pos, 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)?;
+            let value = utf8(take(body, &mut pos, vlen)?)?;
+            entries.push((key, value));
+        }
+        Ok((Self { entries }, 9 + len))
+    }
+}
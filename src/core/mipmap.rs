@@ -1,11 +1,33 @@
 use image::RgbaImage;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 #[derive(Debug, Default)]
 pub struct Mipmap {
     pub width: u32,
     pub height: u32,
     pub image: Option<RgbaImage>,
+    /// Полный JPEG-поток мипа из исходного BLP (SOF — по размеру мипа) и отпечаток `image` на момент
+    /// декодирования. Заполняется только с `DecodeOptions::keep_jpeg`; кодировщик копирует поток,
+    /// пока пиксели не менялись (см. [`Mipmap::source_jpeg`]).
+    pub(crate) jpeg: Option<(Vec<u8>, u64)>,
     //
     pub offset: usize,
     pub length: usize,
 }
+
+impl Mipmap {
+    /// Исходный поток, если `image` с декодирования не трогали.
+    pub(crate) fn source_jpeg(&self) -> Option<&[u8]> {
+        let (jpeg, print) = self.jpeg.as_ref()?;
+        let image = self.image.as_ref()?;
+        (pixels_print(image) == *print).then_some(jpeg.as_slice())
+    }
+}
+
+/// Отпечаток пикселей и размеров (SipHash с фиксированными ключами — одинаков в пределах сборки).
+pub(crate) fn pixels_print(image: &RgbaImage) -> u64 {
+    let mut h = DefaultHasher::new();
+    image.dimensions().hash(&mut h);
+    image.as_raw().hash(&mut h);
+    h.finish()
+}
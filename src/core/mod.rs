@@ -1,7 +1,16 @@
+pub mod hole;
 pub mod image;
+pub mod metadata;
+pub mod inspect;
+pub mod codec;
 pub mod mipmap;
 pub mod types;
 mod from;
-mod decode;
-mod encode;
+pub mod decode;
+pub mod encode;
 pub(crate) mod export;
+mod par_map;
+pub mod probe;
+pub mod repair;
+pub mod scan;
+pub mod transform;
@@ -0,0 +1,59 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Сколько потоков реально запускать: `0` → по числу ядер, не больше числа задач.
+pub(crate) fn resolve_threads(threads: usize, jobs: usize) -> usize {
+    let n = if threads == 0 {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        threads
+    };
+    n.min(jobs).max(1)
+}
+
+/// `f(&mut state, i)` для `i in 0..jobs` на scoped-потоках; результат — в порядке индексов.
+///
+/// Задачи раздаются по возрастанию индекса (у мипов это от большего к меньшему),
+/// `init` вызывается один раз на поток — так переиспользуются тяжёлые хендлы.
+/// При `threads == 1` всё выполняется в текущем потоке.
+pub(crate) fn par_map<S, R, I, F>(jobs: usize, threads: usize, init: I, f: F) -> Vec<R>
+where
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, usize) -> R + Sync,
+{
+    let threads = resolve_threads(threads, jobs);
+    if threads <= 1 {
+        let mut state = init();
+        return (0..jobs)
+            .map(|i| f(&mut state, i))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new((0..jobs).map(|_| None).collect());
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                let mut state = init();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= jobs {
+                        break;
+                    }
+                    let r = f(&mut state, i);
+                    slots.lock().unwrap()[i] = Some(r);
+                }
+            });
+        }
+    });
+    slots
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every job produces a result"))
+        .collect()
+}
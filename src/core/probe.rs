@@ -0,0 +1,112 @@
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::ImageBlp;
+use crate::core::metadata::BlpMetadata;
+use crate::core::types::{TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+
+/// Формат файла по сигнатуре.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeFormat {
+    Blp(Version),
+    /// Любой формат, который понимает `image` (PNG, JPEG, TGA, …).
+    Image(image::ImageFormat),
+}
+
+/// Результат [`ImageBlp::probe`]: только то, что есть в заголовке.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeInfo {
+    pub format: ProbeFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Только для BLP.
+    pub texture_type: Option<TextureType>,
+    /// Мипы с данными: для BLP — ненулевые записи в таблице длин (у BLP0 таблиц нет — `0`),
+    /// для обычных картинок — `1`.
+    pub mip_count: usize,
+}
+
+impl ImageBlp {
+    /// Формат, размеры, тип текстуры и число мипов — без декодирования пикселей и без `DecodeLimits`
+    /// (ничего не выделяется). Для обхода каталогов.
+    pub fn probe(buf: &[u8]) -> Result<ProbeInfo, BlpError> {
+        Self::probe_reader(Cursor::new(buf))
+    }
+
+    /// Как [`ImageBlp::probe`], но читает из потока только нужные байты:
+    /// для BLP — фиксированный заголовок (≤ 156 байт), для остального — заголовок формата через `image`.
+    /// `Seek` нужен, чтобы вернуться к началу после сигнатуры.
+    pub fn probe_reader<R: Read + Seek>(mut r: R) -> Result<ProbeInfo, BlpError> {
+        let start = r.stream_position()?;
+        let mut magic = [0u8; 3];
+        r.read_exact(&mut magic)?;
+        r.seek(SeekFrom::Start(start))?;
+
+        if &magic == b"BLP" {
+            let h = BlpHeader::read(&mut r)?;
+            return Ok(ProbeInfo {
+                format: ProbeFormat::Blp(h.version),
+                width: h.width,
+                height: h.height,
+                texture_type: Some(h.texture_type),
+                mip_count: h
+                    .lengths
+                    .iter()
+                    .filter(|&&len| len > 0)
+                    .count(),
+            });
+        }
+
+        let reader = image::ImageReader::new(BufReader::new(r)).with_guessed_format()?;
+        let format = reader
+            .format()
+            .ok_or_else(|| BlpError::new(ErrorKind::InvalidImage, "error-image-load"))?;
+        let (width, height) = reader.into_dimensions()?;
+        Ok(ProbeInfo { format: ProbeFormat::Image(format), width, height, texture_type: None, mip_count: 1 })
+    }
+
+    /// Чанк метаданных BLP (см. [`BlpMetadata`]) — читается заголовок, палитра или размер JPEG-заголовка
+    /// и сам чанк, пиксели не трогаются. `None` — не BLP, BLP0 или чанка нет.
+    pub fn read_metadata<R: Read + Seek>(mut r: R) -> Result<Option<BlpMetadata>, BlpError> {
+        let start = r.stream_position()?;
+        let mut magic = [0u8; 3];
+        r.read_exact(&mut magic)?;
+        r.seek(SeekFrom::Start(start))?;
+        if &magic != b"BLP" {
+            return Ok(None);
+        }
+        let h = BlpHeader::read(&mut r)?;
+        if h.version < Version::BLP1 {
+            return Ok(None);
+        }
+
+        // чанк — сразу за палитрой или общим JPEG-заголовком, до первого мипа
+        let header_size = h.version.header_size();
+        let pos = match h.texture_type {
+            TextureType::JPEG => {
+                r.seek(SeekFrom::Start(start + header_size))?;
+                let mut len = [0u8; 4];
+                r.read_exact(&mut len)?;
+                header_size + 4 + u32::from_le_bytes(len) as u64
+            }
+            TextureType::DIRECT => header_size + 256 * 4,
+        };
+        let limit = h
+            .offsets
+            .iter()
+            .zip(&h.lengths)
+            .filter(|&(&off, &len)| len > 0 && off as u64 >= pos)
+            .map(|(&off, _)| off as u64)
+            .min()
+            .unwrap_or(u64::MAX)
+            .min(pos + 9 + BlpMetadata::MAX_LEN as u64);
+
+        r.seek(SeekFrom::Start(start + pos))?;
+        let mut chunk = Vec::new();
+        r.take(limit - pos).read_to_end(&mut chunk)?;
+        Ok(BlpMetadata::parse(&chunk)
+            .ok()
+            .map(|(m, _)| m))
+    }
+}
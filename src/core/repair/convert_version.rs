@@ -0,0 +1,77 @@
+use crate::core::decode::direct::check_alpha_bits;
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::ImageBlp;
+use crate::core::repair::write_container::write_container;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+impl ImageBlp {
+    /// Тот же BLP в контейнере `target` (BLP1 ⇄ BLP2) без перекодирования: палитра или общий
+    /// JPEG-заголовок и данные мипов копируются побайтно, меняется только заголовок.
+    ///
+    /// BLP1 → BLP2: `extra` теряется (в BLP2 его нет), `compression` — 0 для JPEG и 1 (палитра)
+    /// для DIRECT. BLP2 → BLP1: `extra` = 0, как у нашего кодировщика. Дыры не переносятся,
+    /// кроме чанка метаданных. DXT и ARGB из BLP2 в BLP1 не укладываются.
+    pub fn convert_version(&self, buf: &[u8], target: Version) -> Result<Vec<u8>, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        if target < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-convert-target").with_arg("version", format!("{target:?}")));
+        }
+        // у BLP2 тип 1 — это ещё и DXT (2) и ARGB (3); мы держим только палитру
+        if self.version >= Version::BLP2 && self.texture_type == TextureType::DIRECT && self.compression != 1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedCompression, "error-convert-compression").with_arg("compression", self.compression as u32));
+        }
+        check_alpha_bits(self.alpha_bits)?;
+
+        let mipmapped = self.has_mipmaps != 0;
+        let mut header = BlpHeader::of(self);
+        header.version = target;
+        if target >= Version::BLP2 {
+            header.compression = match self.texture_type {
+                TextureType::JPEG => 0,
+                TextureType::DIRECT => 1,
+            };
+            if self.version < Version::BLP2 {
+                header.alpha_type = if self.texture_type == TextureType::DIRECT && self.alpha_bits == 8 { 8 } else { 0 };
+            }
+            header.has_mips = mipmapped as u8;
+            header.extra = 0;
+        } else {
+            if self.version >= Version::BLP2 {
+                header.extra = 0;
+            }
+            header.has_mipmaps = mipmapped as u32;
+            (header.compression, header.alpha_type, header.has_mips) = (0, 0, 0);
+        }
+
+        // JPEG: [размер][общий заголовок], DIRECT: палитра; затем чанк метаданных, если был
+        let mut prefix = buf
+            .get(self.version.header_size() as usize..self.header_offset + self.header_length)
+            .ok_or_else(|| match self.texture_type {
+                TextureType::JPEG => BlpError::new(ErrorKind::Truncated, "error-jpeg-header-oob"),
+                TextureType::DIRECT => BlpError::new(ErrorKind::Truncated, "error-direct-palette-oob"),
+            })?
+            .to_vec();
+        if let Some(m) = &self.metadata {
+            prefix.extend_from_slice(&m.to_bytes()?);
+        }
+
+        let payloads: Vec<Option<&[u8]>> = self
+            .mipmaps
+            .iter()
+            .map(|m| {
+                m.offset
+                    .checked_add(m.length)
+                    .filter(|_| m.length > 0)
+                    .and_then(|end| buf.get(m.offset..end))
+            })
+            .collect();
+        write_container(header, &prefix, &payloads)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::error::error::BlpError;
+
+/// Одна эвристика, применённая [`ImageBlp::salvage`](crate::core::image::ImageBlp::salvage).
+/// `mip` — индекс в исходной таблице.
+#[derive(Debug, Clone)]
+pub enum Fixup {
+    /// Запись целиком за концом файла или сверх цепочки уровней — выброшена.
+    EntryDropped { mip: usize },
+    /// Диапазон выходит за конец файла — обрезан по концу файла.
+    RangeClamped { mip: usize, length: usize, clamped: usize },
+    /// Диапазон пересекается с диапазоном другого мипа; данные копируются каждому отдельно.
+    Overlap { mip: usize, other: usize },
+    /// Хвост после конца данных мипа (после EOI или сверх пикселей DIRECT) отрезан.
+    JunkTrimmed { mip: usize, bytes: usize },
+    /// Обрезанный мип дополнен: EOI для JPEG, нули для DIRECT.
+    TruncatedPadded { mip: usize, bytes: usize },
+    /// Размеры в SOF переписаны по размеру мипа.
+    SofDims { mip: usize },
+    /// Мип не декодируется даже после правок — выброшен.
+    Undecodable { mip: usize, error: BlpError },
+    /// Первый пригодный мип стал мипом 0, размеры в заголовке — по нему.
+    Promoted { from: usize },
+    /// Флаг мипмапов не соответствовал числу уцелевших уровней.
+    MipmapFlag { was: u32, now: u32 },
+    /// Байты между мипами и после них (`ImageBlp::holes`) не перенесены.
+    HolesDropped { bytes: usize },
+}
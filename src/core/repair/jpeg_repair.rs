@@ -0,0 +1,99 @@
+use crate::core::encode::utils::header_prefix::header_prefix;
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::repair::write_container::write_container;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Результат [`ImageBlp::repair_jpeg`].
+#[derive(Debug, Clone)]
+pub struct JpegRepair {
+    /// Нормализованный BLP.
+    pub bytes: Vec<u8>,
+    /// Мипы, у которых размеры SOF переписаны по таблице BLP.
+    pub sof_fixed: Vec<usize>,
+    /// Мипы с данными, которые выброшены: диапазон за пределами файла или уровень сверх цепочки мипов.
+    pub dropped: Vec<usize>,
+    /// Длина общего JPEG-заголовка до и после.
+    pub shared_header_len: (usize, usize),
+}
+
+impl ImageBlp {
+    /// Переложить JPEG-BLP в стандартный вид: SOF каждого мипа — с размерами мипа, общий заголовок —
+    /// общий префикс заголовков мипов (как у кодировщика), мипы подряд без дыр и без хвостов после таблиц.
+    /// Энтропийные данные не перекодируются; поля заголовка BLP сохраняются.
+    pub fn repair_jpeg(&self, buf: &[u8]) -> Result<JpegRepair, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.texture_type != TextureType::JPEG {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-jpeg"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        let shared = self.jpeg_header(buf)?;
+
+        // 1) полный поток каждого мипа с исправленным SOF
+        let mut streams: Vec<Option<(Vec<u8>, usize)>> = vec![None; MAX_MIPS.min(self.mipmaps.len())];
+        let (mut sof_fixed, mut dropped) = (Vec::new(), Vec::new());
+        for (i, m) in self.mipmaps.iter().enumerate().take(streams.len()) {
+            if m.length == 0 {
+                continue;
+            }
+            let in_file = m
+                .offset
+                .checked_add(m.length)
+                .is_some_and(|end| end <= buf.len());
+            if !in_file || m.width == 0 || m.height == 0 {
+                dropped.push(i);
+                continue;
+            }
+            let mut full = [shared, &buf[m.offset..m.offset + m.length]].concat();
+            let info = JpegHeaderInfo::parse(&full).map_err(|e| e.ctx_with("error-repair-mip", |e| e.with_arg("mip", i as u32)))?;
+            let sof = info
+                .sof
+                .ok_or_else(|| BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-sof-missing").with_arg("mip", i as u32))?;
+            let dims = (u16::try_from(m.width), u16::try_from(m.height));
+            if let (Ok(w), Ok(h)) = dims
+                && (sof.width, sof.height) != (w, h)
+            {
+                sof.write_dims(&mut full, w, h);
+                sof_fixed.push(i);
+            }
+            streams[i] = Some((full, info.header_end));
+        }
+
+        // 2) общий заголовок — общий префикс заголовков, мипы подряд
+        let (bytes, common_len) = jpeg_container(BlpHeader::of(self), &streams, &[])?;
+
+        Ok(JpegRepair { bytes, sof_fixed, dropped, shared_header_len: (shared.len(), common_len) })
+    }
+}
+
+/// JPEG-BLP из полных потоков мипов `(поток, конец заголовка)`: общий заголовок — общий префикс
+/// заголовков (как у кодировщика), за ним `trailer` — чанк метаданных или пусто.
+/// Возвращает файл и длину общего заголовка.
+pub(crate) fn jpeg_container(header: BlpHeader, streams: &[Option<(Vec<u8>, usize)>], trailer: &[u8]) -> Result<(Vec<u8>, usize), BlpError> {
+    let heads: Vec<&[u8]> = streams
+        .iter()
+        .flatten()
+        .map(|(full, end)| &full[..*end])
+        .collect();
+    if heads.is_empty() {
+        return Err(BlpError::new(ErrorKind::MipMissing, "error-repair-no-mips"));
+    }
+    // все потоки начинаются с SOI — префикс не пуст
+    let common = header_prefix(&heads);
+
+    let mut prefix = (common.len() as u32).to_le_bytes().to_vec();
+    prefix.extend_from_slice(&common);
+    prefix.extend_from_slice(trailer);
+    let payloads: Vec<Option<&[u8]>> = streams
+        .iter()
+        .map(|s| s.as_ref().map(|(full, _)| &full[common.len()..]))
+        .collect();
+    Ok((write_container(header, &prefix, &payloads)?, common.len()))
+}
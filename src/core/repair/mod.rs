@@ -0,0 +1,6 @@
+pub mod convert_version;
+pub mod fixup;
+pub mod jpeg_repair;
+pub mod salvage;
+pub mod strip_holes;
+pub(crate) mod write_container;
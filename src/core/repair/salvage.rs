@@ -0,0 +1,207 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::decode::direct::check_alpha_bits;
+use crate::core::decode::jpeg::decode_jpeg_raw;
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::repair::fixup::Fixup;
+use crate::core::repair::jpeg_repair::jpeg_container;
+use crate::core::repair::write_container::write_container;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Результат [`ImageBlp::salvage`].
+#[derive(Debug, Clone)]
+pub struct Salvage {
+    /// Чистый BLP.
+    pub bytes: Vec<u8>,
+    /// Применённые эвристики в порядке применения.
+    pub fixups: Vec<Fixup>,
+    /// Исходные индексы уцелевших мипов; в новом файле уровни сдвинуты, если был `Fixup::Promoted`.
+    pub recovered: Vec<usize>,
+}
+
+impl ImageBlp {
+    /// Спасти «защищённый» или битый BLP: вытащить все уровни, которые удаётся декодировать,
+    /// и собрать из них чистый файл. Что именно поправлено — в [`Salvage::fixups`].
+    pub fn salvage(&self, buf: &[u8]) -> Result<Salvage, BlpError> {
+        self.salvage_with(buf, &DecodeOptions::with_fallback())
+    }
+
+    /// Same as [`ImageBlp::salvage`], with explicit decoders and limits for the per-mip check.
+    pub fn salvage_with(&self, buf: &[u8], opts: &DecodeOptions) -> Result<Salvage, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        let mut fixups = Vec::new();
+
+        // 1) диапазоны: за файлом — выбросить или обрезать, сверх цепочки уровней — выбросить
+        let n = MAX_MIPS.min(self.mipmaps.len());
+        let mut ranges: Vec<Option<(usize, usize)>> = vec![None; n];
+        for (i, m) in self.mipmaps.iter().enumerate().take(n) {
+            if m.length == 0 {
+                continue;
+            }
+            if m.width == 0 || m.height == 0 || m.offset >= buf.len() {
+                fixups.push(Fixup::EntryDropped { mip: i });
+                continue;
+            }
+            let mut len = m.length;
+            if m.offset.saturating_add(len) > buf.len() {
+                len = buf.len() - m.offset;
+                fixups.push(Fixup::RangeClamped { mip: i, length: m.length, clamped: len });
+            }
+            ranges[i] = Some((m.offset, len));
+        }
+        for i in 0..n {
+            let other = (0..i).find(|&j| match (ranges[i], ranges[j]) {
+                (Some((a, al)), Some((b, bl))) => a < b + bl && b < a + al,
+                _ => false,
+            });
+            if let Some(other) = other {
+                fixups.push(Fixup::Overlap { mip: i, other });
+            }
+        }
+
+        // 2) данные каждого мипа: подрезать, дополнить, проверить
+        let mut streams: Vec<Option<(Vec<u8>, usize)>> = vec![None; n];
+        let palette = match self.texture_type {
+            TextureType::JPEG => {
+                let shared = self.jpeg_header(buf)?;
+                for (i, r) in ranges.iter().enumerate() {
+                    let Some((off, len)) = *r else {
+                        continue;
+                    };
+                    let m = &self.mipmaps[i];
+                    let mut full = [shared, &buf[off..off + len]].concat();
+                    match salvage_jpeg_mip(&mut full, i, (m.width, m.height), &mut fixups, opts) {
+                        Ok(header_end) => streams[i] = Some((full, header_end)),
+                        Err(error) => fixups.push(Fixup::Undecodable { mip: i, error }),
+                    }
+                }
+                None
+            }
+            TextureType::DIRECT => {
+                check_alpha_bits(self.alpha_bits)?;
+                self.read_palette(buf)?;
+                for (i, r) in ranges.iter().enumerate() {
+                    let Some((off, len)) = *r else {
+                        continue;
+                    };
+                    let m = &self.mipmaps[i];
+                    let pixels = m.width as usize * m.height as usize;
+                    let need = pixels
+                        + match self.alpha_bits {
+                            0 => 0,
+                            1 => pixels.div_ceil(8),
+                            4 => pixels.div_ceil(2),
+                            _ => pixels,
+                        };
+                    let mut data = buf[off..off + len].to_vec();
+                    if len > need {
+                        fixups.push(Fixup::JunkTrimmed { mip: i, bytes: len - need });
+                    } else if len < need {
+                        fixups.push(Fixup::TruncatedPadded { mip: i, bytes: need - len });
+                    }
+                    data.resize(need, 0);
+                    streams[i] = Some((data, 0));
+                }
+                Some(&buf[self.header_offset..self.header_offset + self.header_length])
+            }
+        };
+
+        // 3) уровни: первый уцелевший становится мипом 0
+        let first = streams
+            .iter()
+            .position(Option::is_some)
+            .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-repair-no-mips"))?;
+        let mut header = BlpHeader::of(self);
+        if first > 0 {
+            fixups.push(Fixup::Promoted { from: first });
+            streams.drain(..first);
+            (header.width, header.height) = (self.mipmaps[first].width, self.mipmaps[first].height);
+        }
+        let recovered: Vec<usize> = streams
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_some())
+            .map(|(i, _)| i + first)
+            .collect();
+
+        // 4) флаг мипмапов — по факту
+        let now = (recovered.len() > 1) as u32;
+        let was = if self.version >= Version::BLP2 { self.has_mips as u32 } else { self.has_mipmaps };
+        if was != now {
+            fixups.push(Fixup::MipmapFlag { was, now });
+            header.has_mipmaps = now;
+            header.has_mips = now as u8;
+        }
+        if self.holes > 0 {
+            fixups.push(Fixup::HolesDropped { bytes: self.holes });
+        }
+
+        // 5) чистый контейнер
+        let bytes = match palette {
+            None => jpeg_container(header, &streams, &[])?.0,
+            Some(palette) => {
+                let payloads: Vec<Option<&[u8]>> = streams
+                    .iter()
+                    .map(|s| s.as_ref().map(|(d, _)| d.as_slice()))
+                    .collect();
+                write_container(header, palette, &payloads)?
+            }
+        };
+        Ok(Salvage { bytes, fixups, recovered })
+    }
+}
+
+/// Полный JPEG мипа: SOF по размеру мипа, ровно до EOI (дописать, если обрезан), затем пробное
+/// декодирование. Возвращает конец заголовка.
+fn salvage_jpeg_mip(full: &mut Vec<u8>, mip: usize, (w, h): (u32, u32), fixups: &mut Vec<Fixup>, opts: &DecodeOptions) -> Result<usize, BlpError> {
+    let header_end = JpegHeaderInfo::parse(full)?.header_end;
+    if JpegHeaderInfo::fix_sof_dims(full, w, h)? {
+        fixups.push(Fixup::SofDims { mip });
+    }
+    match find_eoi(full, header_end) {
+        Some(eoi) if eoi + 2 < full.len() => {
+            fixups.push(Fixup::JunkTrimmed { mip, bytes: full.len() - eoi - 2 });
+            full.truncate(eoi + 2);
+        }
+        Some(_) => {}
+        None => {
+            full.extend_from_slice(&[0xFF, 0xD9]);
+            fixups.push(Fixup::TruncatedPadded { mip, bytes: 2 });
+        }
+    }
+
+    let jpeg = decode_jpeg_raw(full, &[], mip, (w, h), opts)?;
+    if (jpeg.width, jpeg.height) != (w, h) {
+        return Err(BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-size-mismatch").with_arg("mip", mip as u32));
+    }
+    Ok(header_end)
+}
+
+/// EOI после данных скана; сегменты между сканами (прогрессивный JPEG) перешагиваются.
+fn find_eoi(buf: &[u8], from: usize) -> Option<usize> {
+    let mut i = from;
+    while i + 1 < buf.len() {
+        if buf[i] != 0xFF {
+            i += 1;
+            continue;
+        }
+        match buf[i + 1] {
+            0xD9 => return Some(i),
+            0xFF => i += 1,
+            0x00 | 0xD0..=0xD7 => i += 2,
+            _ => {
+                let len = buf.get(i + 2..i + 4)?;
+                i += 2 + u16::from_be_bytes([len[0], len[1]]) as usize;
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,37 @@
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::ImageBlp;
+use crate::core::repair::write_container::write_container;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+impl ImageBlp {
+    /// Тот же BLP без дыр (`hole_ranges`): заголовок, палитра или общий JPEG-заголовок и данные мипов
+    /// копируются побайтно, мипы ложатся подряд. Записи мипов за пределами файла обнуляются.
+    pub fn strip_holes(&self, buf: &[u8]) -> Result<Vec<u8>, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        // JPEG: [размер][общий заголовок], DIRECT: палитра
+        let prefix = buf
+            .get(self.version.header_size() as usize..self.header_offset + self.header_length)
+            .ok_or_else(|| match self.texture_type {
+                TextureType::JPEG => BlpError::new(ErrorKind::Truncated, "error-jpeg-header-oob"),
+                TextureType::DIRECT => BlpError::new(ErrorKind::Truncated, "error-direct-palette-oob"),
+            })?;
+        let payloads: Vec<Option<&[u8]>> = self
+            .mipmaps
+            .iter()
+            .map(|m| {
+                m.offset
+                    .checked_add(m.length)
+                    .filter(|_| m.length > 0)
+                    .and_then(|end| buf.get(m.offset..end))
+            })
+            .collect();
+        write_container(BlpHeader::of(self), prefix, &payloads)
+    }
+}
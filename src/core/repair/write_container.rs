@@ -0,0 +1,34 @@
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::MAX_MIPS;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Собрать BLP: `header` (таблицы мипов заполняются здесь), за ним `prefix` (JPEG: размер и общий
+/// заголовок, DIRECT: палитра), затем мипы подряд без промежутков.
+pub(crate) fn write_container(mut header: BlpHeader, prefix: &[u8], payloads: &[Option<&[u8]>]) -> Result<Vec<u8>, BlpError> {
+    let header_size = header.version.header_size() as usize;
+    let mut pos = header_size + prefix.len();
+    for (i, p) in payloads
+        .iter()
+        .enumerate()
+        .take(MAX_MIPS)
+    {
+        let (off, len) = match p {
+            Some(p) => (pos, p.len()),
+            None => (0, 0),
+        };
+        header.offsets[i] = u32::try_from(off).map_err(|_| BlpError::new(ErrorKind::Encode, "error-encode-offset-too-large"))?;
+        header.lengths[i] = u32::try_from(len).map_err(|_| BlpError::new(ErrorKind::Encode, "error-encode-payload-too-large"))?;
+        pos += len;
+    }
+
+    let mut bytes = Vec::with_capacity(pos);
+    header.write(&mut bytes);
+    // BLP1 пишет ровно HEADER_SIZE, BLP2 — HEADER_SIZE_BLP2; resize только страхует
+    bytes.resize(header_size, 0);
+    bytes.extend_from_slice(prefix);
+    for p in payloads.iter().take(MAX_MIPS).flatten() {
+        bytes.extend_from_slice(p);
+    }
+    Ok(bytes)
+}
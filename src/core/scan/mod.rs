@@ -0,0 +1,4 @@
+pub mod passport;
+pub mod passport_scan;
+pub mod scan_dir;
+pub mod scan_report;
@@ -0,0 +1,44 @@
+use crate::core::image::ImageBlp;
+use crate::core::types::{TextureType, Version};
+
+/// «Паспорт» BLP — поля заголовка, по которым файлы группируются при обходе каталога.
+/// Файлы с одинаковым паспортом устроены одинаково; редкий паспорт — повод посмотреть на файл.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Passport {
+    pub version: Version,
+    pub texture_type: TextureType,
+    pub alpha_bits: u32,
+    pub extra: u32,
+    pub has_mipmaps: u32,
+    /// Мипы с данными (ненулевая длина в таблице).
+    pub mip_count: usize,
+    /// Длина общего JPEG-заголовка; `None` у DIRECT.
+    pub jpeg_header_len: Option<usize>,
+}
+
+impl Passport {
+    pub fn of(img: &ImageBlp) -> Self {
+        Self {
+            version: img.version,
+            texture_type: img.texture_type,
+            alpha_bits: img.alpha_bits,
+            extra: img.extra,
+            has_mipmaps: img.has_mipmaps,
+            mip_count: img
+                .mipmaps
+                .iter()
+                .filter(|m| m.length > 0)
+                .count(),
+            jpeg_header_len: (img.texture_type == TextureType::JPEG).then_some(img.header_length),
+        }
+    }
+
+    /// Короткий ключ, годный для имени папки: `BLP1_JPEG_a8_e5_m1_n9_h624`.
+    pub fn key(&self) -> String {
+        let mut key = format!("{:?}_{:?}_a{}_e{}_m{}_n{}", self.version, self.texture_type, self.alpha_bits, self.extra, self.has_mipmaps, self.mip_count);
+        if let Some(h) = self.jpeg_header_len {
+            key.push_str(&format!("_h{h}"));
+        }
+        key
+    }
+}
@@ -0,0 +1,57 @@
+use crate::core::image::ImageBlp;
+use crate::core::scan::passport::Passport;
+use crate::core::scan::scan_report::{PassportGroup, ScanFailure, ScanReport};
+use crate::error::error::BlpError;
+use std::collections::BTreeMap;
+
+/// Накопитель для обхода: файлы подаются по одному ([`PassportScan::add`]), итог — [`PassportScan::finish`].
+/// Источник файлов любой: каталог ([`scan_dir`](crate::core::scan::scan_dir::scan_dir)), MPQ-архив, свой список.
+#[derive(Debug, Clone)]
+pub struct PassportScan {
+    max_examples: usize,
+    groups: BTreeMap<Passport, PassportGroup>,
+    files: usize,
+    failed: Vec<ScanFailure>,
+}
+
+impl PassportScan {
+    /// `max_examples` — сколько имён файлов запоминать на паспорт.
+    pub fn new(max_examples: usize) -> Self {
+        Self { max_examples, groups: BTreeMap::new(), files: 0, failed: Vec::new() }
+    }
+
+    /// Учесть файл: разбирается только заголовок, пиксели не декодируются.
+    /// Возвращает паспорт, если он встретился впервые (например, чтобы скопировать образец).
+    pub fn add(&mut self, name: &str, buf: &[u8]) -> Option<Passport> {
+        let img = match ImageBlp::from_buf(buf) {
+            Ok(img) => img,
+            Err(error) => {
+                self.add_failure(name, error);
+                return None;
+            }
+        };
+        let passport = Passport::of(&img);
+        self.files += 1;
+        let is_new = !self.groups.contains_key(&passport);
+        let group = self
+            .groups
+            .entry(passport)
+            .or_insert_with(|| PassportGroup { passport, count: 0, examples: Vec::new() });
+        group.count += 1;
+        if group.examples.len() < self.max_examples {
+            group.examples.push(name.to_string());
+        }
+        is_new.then_some(passport)
+    }
+
+    /// Учесть файл, который не удалось прочитать.
+    pub fn add_failure(&mut self, name: &str, error: BlpError) {
+        self.files += 1;
+        self.failed.push(ScanFailure { name: name.to_string(), error });
+    }
+
+    /// Группы по возрастанию паспорта.
+    pub fn finish(self) -> ScanReport {
+        ScanReport { files: self.files, groups: self.groups.into_values().collect(), failed: self.failed }
+    }
+}
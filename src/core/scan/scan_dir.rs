@@ -0,0 +1,99 @@
+use crate::core::scan::passport::Passport;
+use crate::core::scan::passport_scan::PassportScan;
+use crate::core::scan::scan_report::ScanReport;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Параметры обхода.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Сколько примеров запоминать на паспорт.
+    pub max_examples: usize,
+    /// Куда копировать по одному образцу на паспорт: `<dir>/<key>/<file>` и `origin.txt` с исходным путём.
+    pub samples_dir: Option<PathBuf>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self { max_examples: 3, samples_dir: None }
+    }
+}
+
+/// Обойти каталог рекурсивно (файлы `*.blp`, порядок — по именам) и сгруппировать по паспорту.
+/// Нечитаемые и битые файлы попадают в `failed`, обход не прерывают.
+pub fn scan_dir(dir: &Path, opts: &ScanOptions) -> Result<ScanReport, BlpError> {
+    if !dir.is_dir() {
+        return Err(BlpError::new(ErrorKind::Io, "error-scan-not-dir").with_arg("path", dir.to_string_lossy().into_owned()));
+    }
+    let mut scan = PassportScan::new(opts.max_examples);
+    for entry in WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        let path = entry.path();
+        if !entry.file_type().is_file() || !is_blp_name(&path.to_string_lossy()) {
+            continue;
+        }
+        let name = path.display().to_string();
+        let buf = match fs::read(path) {
+            Ok(buf) => buf,
+            Err(e) => {
+                scan.add_failure(&name, e.into());
+                continue;
+            }
+        };
+        if let Some(p) = scan.add(&name, &buf) {
+            copy_sample(opts, &p, &name, &buf)?;
+        }
+    }
+    Ok(scan.finish())
+}
+
+/// То же для MPQ-архива: BLP из его `(listfile)`. Примеры — `archive:inner`.
+#[cfg(feature = "mpq")]
+pub fn scan_archive(path: &Path, opts: &ScanOptions) -> Result<ScanReport, BlpError> {
+    use crate::mpq::archive::MpqArchive;
+
+    let mut ar = MpqArchive::open(path)?;
+    let mut scan = PassportScan::new(opts.max_examples);
+    for inner in ar.file_names()? {
+        if !is_blp_name(&inner) {
+            continue;
+        }
+        let name = format!("{}:{inner}", path.display());
+        let buf = match ar.read_file(&inner) {
+            Ok(buf) => buf,
+            Err(e) => {
+                scan.add_failure(&name, e);
+                continue;
+            }
+        };
+        if let Some(p) = scan.add(&name, &buf) {
+            copy_sample(opts, &p, &name, &buf)?;
+        }
+    }
+    Ok(scan.finish())
+}
+
+fn is_blp_name(name: &str) -> bool {
+    name.len() > 4 && name[name.len() - 4..].eq_ignore_ascii_case(".blp")
+}
+
+fn copy_sample(opts: &ScanOptions, p: &Passport, name: &str, buf: &[u8]) -> Result<(), BlpError> {
+    let Some(root) = &opts.samples_dir else {
+        return Ok(());
+    };
+    let dir = root.join(p.key());
+    fs::create_dir_all(&dir)?;
+    let file_name = name
+        .rsplit(['/', '\\', ':'])
+        .next()
+        .unwrap_or(name);
+    fs::write(dir.join(file_name), buf)?;
+    fs::write(dir.join("origin.txt"), name)?;
+    Ok(())
+}
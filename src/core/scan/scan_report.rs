@@ -0,0 +1,105 @@
+use crate::core::scan::passport::Passport;
+use crate::error::error::BlpError;
+use crate::ext::json_str::write_json_str;
+use std::fmt::Write;
+
+/// Итог обхода: группы по паспорту и файлы, которые не разобрались.
+#[derive(Debug, Clone)]
+pub struct ScanReport {
+    /// Все просмотренные BLP, включая неразобранные.
+    pub files: usize,
+    pub groups: Vec<PassportGroup>,
+    pub failed: Vec<ScanFailure>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PassportGroup {
+    pub passport: Passport,
+    pub count: usize,
+    /// Первые файлы с этим паспортом (не больше `max_examples`).
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanFailure {
+    pub name: String,
+    pub error: BlpError,
+}
+
+impl ScanReport {
+    /// CSV: строка на паспорт; примеры через `;`. Пустой `jpeg_header_len` — DIRECT.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("key,version,texture_type,alpha_bits,extra,has_mipmaps,mip_count,jpeg_header_len,count,examples\n");
+        for g in &self.groups {
+            let p = &g.passport;
+            let _ = writeln!(
+                out,
+                "{},{:?},{:?},{},{},{},{},{},{},{}",
+                p.key(),
+                p.version,
+                p.texture_type,
+                p.alpha_bits,
+                p.extra,
+                p.has_mipmaps,
+                p.mip_count,
+                p.jpeg_header_len
+                    .map(|h| h.to_string())
+                    .unwrap_or_default(),
+                g.count,
+                csv_field(&g.examples.join(";"))
+            );
+        }
+        out
+    }
+
+    /// JSON: `{"files", "groups": [{key, version, …, count, examples}], "failed": [{name, kind, key}]}`.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(out, "{{\"files\":{},\"groups\":[", self.files);
+        for (i, g) in self.groups.iter().enumerate() {
+            let p = &g.passport;
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"key\":");
+            write_json_str(&p.key(), &mut out);
+            let _ = write!(
+                out,
+                ",\"version\":\"{:?}\",\"texture_type\":\"{:?}\",\"alpha_bits\":{},\"extra\":{},\"has_mipmaps\":{},\"mip_count\":{},\"jpeg_header_len\":{},\"count\":{},\"examples\":[",
+                p.version,
+                p.texture_type,
+                p.alpha_bits,
+                p.extra,
+                p.has_mipmaps,
+                p.mip_count,
+                p.jpeg_header_len
+                    .map(|h| h.to_string())
+                    .unwrap_or_else(|| "null".into()),
+                g.count
+            );
+            for (j, e) in g.examples.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                write_json_str(e, &mut out);
+            }
+            out.push_str("]}");
+        }
+        out.push_str("],\"failed\":[");
+        for (i, f) in self.failed.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            write_json_str(&f.name, &mut out);
+            let _ = write!(out, ",\"kind\":\"{}\",\"key\":\"{}\"}}", f.error.kind.as_str(), f.error.key);
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Поле CSV: в кавычках, если есть запятая, кавычка или перевод строки.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
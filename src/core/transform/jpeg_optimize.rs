@@ -0,0 +1,102 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::repair::jpeg_repair::jpeg_container;
+use crate::core::transform::jpeg_transform::minimal_stream;
+use crate::core::transform::lossless::Lossless;
+use crate::core::transform::orientation::Orientation;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Результат [`ImageBlp::optimize_jpeg`].
+#[derive(Debug, Clone)]
+pub struct JpegOptimize {
+    /// Оптимизированный BLP.
+    pub bytes: Vec<u8>,
+    /// Мипы с пересчитанными таблицами Хаффмана.
+    pub optimized: Vec<usize>,
+    /// Мипы, оставленные как были: с оптимальными таблицами поток не короче (так бывает у мелких
+    /// мипов — свой DHT длиннее выигрыша).
+    pub kept: Vec<usize>,
+    /// Мипы с данными за пределами файла — выброшены.
+    pub dropped: Vec<usize>,
+    /// Размер перенесённого чанка метаданных, байт (0 — чанка не было).
+    pub metadata: usize,
+}
+
+impl ImageBlp {
+    /// Пережать энтропийные данные JPEG-BLP с оптимальными таблицами Хаффмана (`jpegtran -optimize`):
+    /// DCT-коэффициенты не меняются, общий заголовок пересчитывается, дыры не переносятся,
+    /// чанк метаданных переносится.
+    /// Пиксели до и после сверяются декодированием; расхождение — ошибка. Нужен TurboJPEG.
+    pub fn optimize_jpeg(&self, buf: &[u8]) -> Result<JpegOptimize, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.texture_type != TextureType::JPEG {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-optimize-not-jpeg"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        if !cfg!(feature = "turbojpeg") {
+            return Err(BlpError::new(ErrorKind::Encode, "error-optimize-unavailable"));
+        }
+        let shared = self.jpeg_header(buf)?;
+
+        let mut tj = Lossless::new()?;
+        let mut streams: Vec<Option<(Vec<u8>, usize)>> = vec![None; MAX_MIPS.min(self.mipmaps.len())];
+        let (mut optimized, mut kept, mut dropped) = (Vec::new(), Vec::new(), Vec::new());
+        for (i, m) in self.mipmaps.iter().enumerate().take(streams.len()) {
+            if m.length == 0 {
+                continue;
+            }
+            let in_file = m
+                .offset
+                .checked_add(m.length)
+                .is_some_and(|end| end <= buf.len());
+            if !in_file || m.width == 0 || m.height == 0 {
+                dropped.push(i);
+                continue;
+            }
+            let mut full = [shared, &buf[m.offset..m.offset + m.length]].concat();
+            JpegHeaderInfo::fix_sof_dims(&mut full, m.width, m.height).map_err(|e| e.ctx_with("error-repair-mip", |e| e.with_arg("mip", i as u32)))?;
+            let better = tj
+                .apply(&full, Orientation::default(), true)
+                .map(|out| minimal_stream(&out))
+                .transpose()?
+                .filter(|(out, _)| out.len() < full.len());
+            match better {
+                Some(s) => {
+                    streams[i] = Some(s);
+                    optimized.push(i);
+                }
+                None => {
+                    let header_end = JpegHeaderInfo::parse(&full)?.header_end;
+                    streams[i] = Some((full, header_end));
+                    kept.push(i);
+                }
+            }
+        }
+        let trailer = self.metadata_chunk()?;
+        let (bytes, _) = jpeg_container(BlpHeader::of(self), &streams, &trailer)?;
+
+        // побайтная сверка пикселей всех уцелевших мипов
+        let decode = |b: &[u8]| -> Result<ImageBlp, BlpError> {
+            let mut img = ImageBlp::from_buf(b)?;
+            img.decode_with(b, &[true; MAX_MIPS], &DecodeOptions { fix_sof_dims: true, ..DecodeOptions::with_fallback() })?;
+            Ok(img)
+        };
+        let (before, after) = (decode(buf)?, decode(&bytes)?);
+        for &i in optimized.iter().chain(&kept) {
+            let (a, b) = (&before.mipmaps[i].image, &after.mipmaps[i].image);
+            if a.as_ref().map(|a| a.as_raw()) != b.as_ref().map(|b| b.as_raw()) {
+                return Err(BlpError::new(ErrorKind::Internal, "error-optimize-mismatch").with_arg("mip", i as u32));
+            }
+        }
+
+        Ok(JpegOptimize { bytes, optimized, kept, dropped, metadata: trailer.len() })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::core::decode::decode_options::DecodeOptions;
+use crate::core::encode::blp::EncodeOptions;
+use crate::core::encode::utils::rebuild_minimal_jpeg_header::rebuild_minimal_jpeg_header;
+use crate::core::from::blp_header::BlpHeader;
+use crate::core::image::{ImageBlp, MAX_MIPS};
+use crate::core::inspect::jpeg_header_info::JpegHeaderInfo;
+use crate::core::repair::jpeg_repair::jpeg_container;
+use crate::core::transform::lossless::Lossless;
+use crate::core::transform::orientation::Orientation;
+use crate::core::types::{SourceKind, TextureType, Version};
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+
+/// Результат [`ImageBlp::transform_jpeg`].
+#[derive(Debug, Clone)]
+pub struct JpegTransform {
+    /// Преобразованный BLP.
+    pub bytes: Vec<u8>,
+    /// Мипы, преобразованные над DCT-коэффициентами, без потерь.
+    pub lossless: Vec<usize>,
+    /// Мипы с неполными MCU на краю, который уходит влево или вверх: декодированы, повёрнуты и
+    /// закодированы заново с `EncodeOptions::quality`.
+    pub reencoded: Vec<usize>,
+    /// Мипы с данными за пределами файла — выброшены.
+    pub dropped: Vec<usize>,
+}
+
+impl ImageBlp {
+    /// Повернуть и/или отразить JPEG-BLP. Каждый мип (с SOF, исправленным по таблице BLP) проходит
+    /// через `tj3Transform` в режиме «perfect»; если TurboJPEG не собран или размеры мипа не кратны
+    /// MCU, мип перекодируется через `opts.backend`. Общий заголовок пересчитывается, как у кодировщика,
+    /// чанк метаданных переносится как есть.
+    pub fn transform_jpeg(&self, buf: &[u8], orientation: Orientation, opts: &EncodeOptions) -> Result<JpegTransform, BlpError> {
+        if self.source != SourceKind::Blp {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-repair-not-blp"));
+        }
+        if self.texture_type != TextureType::JPEG {
+            return Err(BlpError::new(ErrorKind::InvalidArgument, "error-transform-not-jpeg"));
+        }
+        if self.version < Version::BLP1 {
+            return Err(BlpError::new(ErrorKind::UnsupportedVersion, "error-repair-blp0"));
+        }
+        let shared = self.jpeg_header(buf)?;
+
+        // 1) без потерь — что получится
+        let mut tj = Lossless::new()?;
+        let mut streams: Vec<Option<(Vec<u8>, usize)>> = vec![None; MAX_MIPS.min(self.mipmaps.len())];
+        let (mut lossless, mut reencoded, mut dropped) = (Vec::new(), Vec::new(), Vec::new());
+        for (i, m) in self.mipmaps.iter().enumerate().take(streams.len()) {
+            if m.length == 0 {
+                continue;
+            }
+            let in_file = m
+                .offset
+                .checked_add(m.length)
+                .is_some_and(|end| end <= buf.len());
+            if !in_file || m.width == 0 || m.height == 0 {
+                dropped.push(i);
+                continue;
+            }
+            let mut full = [shared, &buf[m.offset..m.offset + m.length]].concat();
+            JpegHeaderInfo::fix_sof_dims(&mut full, m.width, m.height).map_err(|e| e.ctx_with("error-repair-mip", |e| e.with_arg("mip", i as u32)))?;
+            if let Some(out) = tj.apply(&full, orientation, false) {
+                streams[i] = Some(minimal_stream(&out)?);
+                lossless.push(i);
+                continue;
+            }
+            reencoded.push(i);
+        }
+
+        // 2) остальное — через пиксели
+        if !reencoded.is_empty() {
+            let mut img = ImageBlp::from_buf(buf)?;
+            let visible: Vec<bool> = (0..streams.len())
+                .map(|i| reencoded.contains(&i))
+                .collect();
+            img.decode_with(buf, &visible, &DecodeOptions { fix_sof_dims: true, ..DecodeOptions::with_fallback() })?;
+            // та же раскладка, что у исходника: CMYK с альфой в K либо RGB
+            let has_alpha = self.alpha_bits > 0;
+            let mut comp = opts.backend.compressor();
+            for &i in &reencoded {
+                let rgba = img.mipmaps[i]
+                    .image
+                    .as_ref()
+                    .ok_or_else(|| BlpError::new(ErrorKind::MipMissing, "error-transform-mip").with_arg("mip", i as u32))?;
+                let out = orientation.apply(rgba);
+                let jpeg = comp.compress(out.as_raw(), out.width() as usize, out.height() as usize, opts.quality, None, has_alpha)?;
+                streams[i] = Some(minimal_stream(&jpeg)?);
+            }
+        }
+
+        let mut header = BlpHeader::of(self);
+        if orientation.swaps_dims() {
+            (header.width, header.height) = (header.height, header.width);
+        }
+        let (bytes, _) = jpeg_container(header, &streams, &self.metadata_chunk()?)?;
+        Ok(JpegTransform { bytes, lossless, reencoded, dropped })
+    }
+
+    /// Чанк метаданных для записи после общего заголовка; пусто, если его не было.
+    pub(crate) fn metadata_chunk(&self) -> Result<Vec<u8>, BlpError> {
+        Ok(match &self.metadata {
+            Some(m) => m.to_bytes()?,
+            None => Vec::new(),
+        })
+    }
+}
+
+/// Поток без APPn/COM (TurboJPEG добавляет JFIF/Adobe) и конец его заголовка — как в кодировщике.
+pub(crate) fn minimal_stream(jpeg: &[u8]) -> Result<(Vec<u8>, usize), BlpError> {
+    let info = JpegHeaderInfo::parse(jpeg)?;
+    let mut out = rebuild_minimal_jpeg_header(&jpeg[..info.header_end])?;
+    let header_end = out.len();
+    out.extend_from_slice(&jpeg[info.header_end..]);
+    Ok((out, header_end))
+}
@@ -0,0 +1,37 @@
+use crate::core::transform::orientation::Orientation;
+use crate::error::error::BlpError;
+#[cfg(feature = "turbojpeg")]
+use turbojpeg::Transformer;
+
+/// `tj3Transform` в режиме «perfect» над DCT-коэффициентами; `optimize` — заново посчитать таблицы
+/// Хаффмана. `None` — без потерь не выходит. Без TurboJPEG — всегда `None`.
+#[cfg(feature = "turbojpeg")]
+pub(crate) struct Lossless(Transformer);
+
+#[cfg(feature = "turbojpeg")]
+impl Lossless {
+    pub fn new() -> Result<Self, BlpError> {
+        Ok(Self(Transformer::new()?))
+    }
+
+    pub fn apply(&mut self, jpeg: &[u8], orientation: Orientation, optimize: bool) -> Option<Vec<u8>> {
+        let mut t = turbojpeg::Transform::op(orientation.tj_op());
+        t.perfect = true;
+        t.optimize = optimize;
+        self.0.transform_to_vec(&t, jpeg).ok()
+    }
+}
+
+#[cfg(not(feature = "turbojpeg"))]
+pub(crate) struct Lossless;
+
+#[cfg(not(feature = "turbojpeg"))]
+impl Lossless {
+    pub fn new() -> Result<Self, BlpError> {
+        Ok(Self)
+    }
+
+    pub fn apply(&mut self, _jpeg: &[u8], _orientation: Orientation, _optimize: bool) -> Option<Vec<u8>> {
+        None
+    }
+}
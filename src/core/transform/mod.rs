@@ -0,0 +1,4 @@
+pub mod jpeg_optimize;
+pub mod jpeg_transform;
+pub(crate) mod lossless;
+pub mod orientation;
@@ -0,0 +1,62 @@
+use image::{RgbaImage, imageops};
+
+/// Поворот по часовой стрелке.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    Rot90,
+    Rot180,
+    Rot270,
+}
+
+/// Отражение: `Horizontal` — слева направо, `Vertical` — сверху вниз.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flip {
+    Horizontal,
+    Vertical,
+}
+
+/// Поворот, затем отражение. Любая пара сводится к одной из восьми операций TurboJPEG.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub rotate: Option<Rotation>,
+    pub flip: Option<Flip>,
+}
+
+impl Orientation {
+    /// Ширина и высота меняются местами (поворот на 90° или 270°).
+    pub fn swaps_dims(self) -> bool {
+        matches!(self.rotate, Some(Rotation::Rot90 | Rotation::Rot270))
+    }
+
+    /// Та же операция над пикселями — для мипов, которые нельзя преобразовать без потерь.
+    pub fn apply(self, img: &RgbaImage) -> RgbaImage {
+        let mut out = match self.rotate {
+            None => img.clone(),
+            Some(Rotation::Rot90) => imageops::rotate90(img),
+            Some(Rotation::Rot180) => imageops::rotate180(img),
+            Some(Rotation::Rot270) => imageops::rotate270(img),
+        };
+        match self.flip {
+            None => {}
+            Some(Flip::Horizontal) => imageops::flip_horizontal_in_place(&mut out),
+            Some(Flip::Vertical) => imageops::flip_vertical_in_place(&mut out),
+        }
+        out
+    }
+
+    /// Одна операция TurboJPEG: например, 90° + отражение по горизонтали — это транспонирование.
+    #[cfg(feature = "turbojpeg")]
+    pub(crate) fn tj_op(self) -> turbojpeg::TransformOp {
+        use turbojpeg::TransformOp as Op;
+        match (self.rotate, self.flip) {
+            (None, None) => Op::None,
+            (None, Some(Flip::Horizontal)) | (Some(Rotation::Rot180), Some(Flip::Vertical)) => Op::Hflip,
+            (None, Some(Flip::Vertical)) | (Some(Rotation::Rot180), Some(Flip::Horizontal)) => Op::Vflip,
+            (Some(Rotation::Rot90), None) => Op::Rot90,
+            (Some(Rotation::Rot180), None) => Op::Rot180,
+            (Some(Rotation::Rot270), None) => Op::Rot270,
+            (Some(Rotation::Rot90), Some(Flip::Horizontal)) | (Some(Rotation::Rot270), Some(Flip::Vertical)) => Op::Transpose,
+            (Some(Rotation::Rot90), Some(Flip::Vertical)) | (Some(Rotation::Rot270), Some(Flip::Horizontal)) => Op::Transverse,
+        }
+    }
+}
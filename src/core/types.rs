@@ -1,6 +1,7 @@
+use crate::core::image::{HEADER_SIZE, HEADER_SIZE_BLP2};
 use num_enum::TryFromPrimitive;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Eq, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash, TryFromPrimitive)]
 #[repr(u32)]
 pub enum Version {
     BLP0 = 0x424C5030, // "BLP0"
@@ -9,7 +10,17 @@ pub enum Version {
     BLP2 = 0x424C5032, // "BLP2"
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Eq, TryFromPrimitive)]
+impl Version {
+    /// Размер фиксированного заголовка: сразу за ним палитра или размер JPEG-заголовка.
+    pub fn header_size(self) -> u64 {
+        match self {
+            Version::BLP2 => HEADER_SIZE_BLP2,
+            _ => HEADER_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash, TryFromPrimitive)]
 #[repr(u32)]
 pub enum TextureType {
     #[default]
@@ -1,29 +1,32 @@
 use crate::error::args::ArgVal;
 use crate::error::cause::Cause;
+use crate::error::error_kind::ErrorKind;
 use num_enum::TryFromPrimitiveError;
 use std::{collections::BTreeMap, fmt, io, sync::Arc};
 
 #[derive(Debug, Clone)]
 pub struct BlpError {
+    pub kind: ErrorKind,
     pub key: &'static str,
     pub args: BTreeMap<&'static str, ArgVal>,
     pub causes: Vec<Cause>,
 }
 
 impl BlpError {
+    /// Обёртка с новым ключом; вид ошибки — от причины.
     #[inline]
     pub fn ctx(self, key: &'static str) -> BlpError {
-        BlpError::new(key).push_blp(self)
+        BlpError::new(self.kind, key).push_blp(self)
     }
 
     #[inline]
     pub fn ctx_with(self, key: &'static str, f: impl FnOnce(BlpError) -> BlpError) -> BlpError {
-        f(BlpError::new(key).push_blp(self))
+        f(BlpError::new(self.kind, key).push_blp(self))
     }
 
     #[inline]
-    pub fn new(key: &'static str) -> Self {
-        Self { key, args: BTreeMap::new(), causes: Vec::new() }
+    pub fn new(kind: ErrorKind, key: &'static str) -> Self {
+        Self { kind, key, args: BTreeMap::new(), causes: Vec::new() }
     }
 
     #[inline]
@@ -82,31 +85,61 @@ impl std::error::Error for BlpError {
 
 impl From<io::Error> for BlpError {
     fn from(e: io::Error) -> Self {
-        BlpError::new("io-error").push_std(e)
+        // `read_exact` за концом буфера — обрезанные данные, а не сбой ввода-вывода
+        let (kind, key) = match e.kind() {
+            io::ErrorKind::UnexpectedEof => (ErrorKind::Truncated, "error-truncated"),
+            _ => (ErrorKind::Io, "error-io"),
+        };
+        BlpError::new(kind, key)
+            .with_arg("msg", e.to_string())
+            .push_std(e)
     }
 }
 
 impl From<image::ImageError> for BlpError {
     fn from(e: image::ImageError) -> Self {
-        BlpError::new("image-error").push_std(e)
+        let kind = match &e {
+            image::ImageError::Limits(_) => ErrorKind::LimitExceeded,
+            image::ImageError::IoError(_) => ErrorKind::Io,
+            _ => ErrorKind::InvalidImage,
+        };
+        BlpError::new(kind, "error-image")
+            .with_arg("msg", e.to_string())
+            .push_std(e)
     }
 }
 
 impl From<image::error::DecodingError> for BlpError {
     fn from(e: image::error::DecodingError) -> Self {
-        BlpError::new("png-error").push_std(e)
+        BlpError::new(ErrorKind::InvalidImage, "error-image")
+            .with_arg("msg", e.to_string())
+            .push_std(e)
     }
 }
 
 impl From<jpeg_decoder::Error> for BlpError {
     fn from(e: jpeg_decoder::Error) -> Self {
-        BlpError::new("jpeg-error").push_std(e)
+        BlpError::new(ErrorKind::InvalidJpeg, "error-jpeg-decoder")
+            .with_arg("msg", e.to_string())
+            .push_std(e)
     }
 }
 
+#[cfg(feature = "turbojpeg")]
 impl From<turbojpeg::Error> for BlpError {
     fn from(e: turbojpeg::Error) -> Self {
-        BlpError::new("turbojpeg-error").push_std(e)
+        BlpError::new(ErrorKind::Encode, "error-turbojpeg")
+            .with_arg("msg", e.to_string())
+            .push_std(e)
+    }
+}
+
+#[cfg(feature = "pure-jpeg")]
+impl From<jpeg_encoder::EncodingError> for BlpError {
+    fn from(e: jpeg_encoder::EncodingError) -> Self {
+        BlpError::new(ErrorKind::Encode, "error-jpeg-encoder")
+            .with_arg("msg", e.to_string())
+            .push_std(e)
     }
 }
 
@@ -115,7 +148,21 @@ where
     T: num_enum::TryFromPrimitive + 'static,
     T::Primitive: Copy + Into<u64>,
 {
-    fn from(_err: TryFromPrimitiveError<T>) -> Self {
-        BlpError::new("num-error").with_arg("name", core::any::type_name::<T>())
+    fn from(err: TryFromPrimitiveError<T>) -> Self {
+        // Из заголовка читаются только `Version` и `TextureType`
+        let name = core::any::type_name::<T>()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default();
+        let kind = if name == "Version" {
+            ErrorKind::UnsupportedVersion
+        } else if name == "TextureType" {
+            ErrorKind::UnsupportedCompression
+        } else {
+            ErrorKind::InvalidHeader
+        };
+        BlpError::new(kind, "error-enum-value")
+            .with_arg("name", name)
+            .with_arg("value", err.number.into())
     }
 }
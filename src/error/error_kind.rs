@@ -0,0 +1,58 @@
+/// Стабильная категория ошибки — для `match` в коде библиотеки-пользователя.
+///
+/// Ключ (`BlpError::key`) и аргументы остаются для локализации и могут уточняться;
+/// вид ошибки — нет. Обёртки `ctx` / `ctx_with` наследуют вид причины.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Файловая система и потоки: нет файла, нет прав, ошибка чтения/записи.
+    Io,
+    /// Данные закончились раньше, чем требует заголовок (обрезанный файл, смещения за концом).
+    Truncated,
+    /// Неизвестная магия / версия контейнера.
+    UnsupportedVersion,
+    /// Неизвестный тип сжатия текстуры.
+    UnsupportedCompression,
+    /// Заголовок BLP прочитан, но значения в нём недопустимы (например, `alpha_bits`).
+    InvalidHeader,
+    /// Поток JPEG внутри BLP повреждён или не читается декодером.
+    InvalidJpeg,
+    /// Внешняя картинка (PNG и т. п.) не читается или пуста.
+    InvalidImage,
+    /// MPQ-архив повреждён: таблицы, секторы или сжатые данные.
+    InvalidArchive,
+    /// Запрошенного мип-уровня нет.
+    MipMissing,
+    /// Превышен `DecodeLimits` или лимит формата.
+    LimitExceeded,
+    /// Некорректные параметры вызова (маска мипов, размер буфера, нет картинки).
+    InvalidArgument,
+    /// Сбой JPEG-кодировщика или сборки BLP.
+    Encode,
+    /// Окружение: буфер обмена, окно UI.
+    Platform,
+    /// Внутренняя ошибка (не должна возникать).
+    Internal,
+}
+
+impl ErrorKind {
+    /// Имя варианта — стабильная строка для JSON и логов.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Io => "Io",
+            ErrorKind::Truncated => "Truncated",
+            ErrorKind::UnsupportedVersion => "UnsupportedVersion",
+            ErrorKind::UnsupportedCompression => "UnsupportedCompression",
+            ErrorKind::InvalidHeader => "InvalidHeader",
+            ErrorKind::InvalidJpeg => "InvalidJpeg",
+            ErrorKind::InvalidImage => "InvalidImage",
+            ErrorKind::InvalidArchive => "InvalidArchive",
+            ErrorKind::MipMissing => "MipMissing",
+            ErrorKind::LimitExceeded => "LimitExceeded",
+            ErrorKind::InvalidArgument => "InvalidArgument",
+            ErrorKind::Encode => "Encode",
+            ErrorKind::Platform => "Platform",
+            ErrorKind::Internal => "Internal",
+        }
+    }
+}
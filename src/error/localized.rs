@@ -0,0 +1,63 @@
+use crate::error::args::ArgVal;
+use crate::error::cause::Cause;
+use crate::error::error::BlpError;
+use crate::i18n::locales::LOCALES;
+use fluent_templates::fluent_bundle::FluentValue;
+use fluent_templates::{LanguageIdentifier, Loader};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+impl BlpError {
+    /// Локализованный текст ошибки с деревом причин: строка на уровень, отступ — два пробела.
+    ///
+    /// Нет перевода — берётся `en`; нет ключа и там — сырой `key(args)` из `Display`.
+    pub fn localized(&self, lang: &LanguageIdentifier) -> String {
+        let mut out = String::new();
+        fmt_err_loc(self, lang, 0, &mut out);
+        out
+    }
+
+    /// Только заголовок этого узла, без причин.
+    pub(crate) fn localized_line(&self, lang: &LanguageIdentifier) -> String {
+        // Аргументы ошибки → аргументы Fluent
+        let args: HashMap<Cow<'static, str>, FluentValue> = self
+            .args
+            .iter()
+            .map(|(k, v)| (Cow::Borrowed(*k), v.to_fluent_owned()))
+            .collect();
+        LOCALES
+            .try_lookup_with_args(lang, self.key, &args)
+            .unwrap_or_else(|| self.to_string())
+    }
+}
+
+fn fmt_err_loc(err: &BlpError, lang: &LanguageIdentifier, indent: usize, out: &mut String) {
+    // Первая строка — локализованный заголовок
+    for _ in 0..indent {
+        let _ = write!(out, "  ");
+    }
+    let _ = writeln!(out, "{}", err.localized_line(lang));
+
+    // Дочерние причины
+    let msg = match err.args.get("msg") {
+        Some(ArgVal::Str(s)) => Some(s.as_ref()),
+        _ => None,
+    };
+    for cause in &err.causes {
+        match cause {
+            Cause::Blp(a) => fmt_err_loc(a, lang, indent + 1, out),
+            Cause::Std(e) => {
+                // текст std-ошибки уже подставлен в заголовок через $msg — не дублируем
+                let text = e.to_string();
+                if msg == Some(text.as_str()) {
+                    continue;
+                }
+                for _ in 0..(indent + 1) {
+                    let _ = write!(out, "  ");
+                }
+                let _ = writeln!(out, "{text}");
+            }
+        }
+    }
+}
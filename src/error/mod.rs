@@ -1,6 +1,7 @@
-#[cfg(feature = "ui")]
-mod app;
 pub mod args;
 mod cause;
 pub mod error;
+pub mod error_kind;
+mod localized;
 mod macros;
+mod to_json;
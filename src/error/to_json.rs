@@ -0,0 +1,65 @@
+use crate::error::args::ArgVal;
+use crate::error::cause::Cause;
+use crate::error::error::BlpError;
+use crate::ext::json_str::write_json_str;
+use fluent_templates::LanguageIdentifier;
+use std::fmt::Write;
+
+impl BlpError {
+    /// Дерево ошибки одной строкой JSON:
+    /// `{"kind", "key", "message", "args": {..}, "causes": [..]}`; std-причины — `{"message"}`.
+    /// `message` — локализованный заголовок узла.
+    pub fn to_json(&self, lang: &LanguageIdentifier) -> String {
+        let mut out = String::new();
+        write_err(self, lang, &mut out);
+        out
+    }
+}
+
+fn write_err(err: &BlpError, lang: &LanguageIdentifier, out: &mut String) {
+    out.push_str("{\"kind\":");
+    write_json_str(err.kind.as_str(), out);
+    out.push_str(",\"key\":");
+    write_json_str(err.key, out);
+    out.push_str(",\"message\":");
+    write_json_str(&err.localized_line(lang), out);
+
+    out.push_str(",\"args\":{");
+    for (i, (k, v)) in err.args.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_json_str(k, out);
+        out.push(':');
+        match v {
+            ArgVal::Str(s) => write_json_str(s, out),
+            ArgVal::Int(n) => {
+                let _ = write!(out, "{n}");
+            }
+            ArgVal::F64(x) if x.is_finite() => {
+                let _ = write!(out, "{x}");
+            }
+            ArgVal::F64(_) => out.push_str("null"),
+            ArgVal::Bool(b) => {
+                let _ = write!(out, "{b}");
+            }
+            ArgVal::Display(d) => write_json_str(&d.to_string(), out),
+        }
+    }
+
+    out.push_str("},\"causes\":[");
+    for (i, cause) in err.causes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        match cause {
+            Cause::Blp(e) => write_err(e, lang, out),
+            Cause::Std(e) => {
+                out.push_str("{\"message\":");
+                write_json_str(&e.to_string(), out);
+                out.push('}');
+            }
+        }
+    }
+    out.push_str("]}");
+}
@@ -1 +1,2 @@
+pub mod json_str;
 pub mod path;
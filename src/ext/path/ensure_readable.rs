@@ -1,7 +1,10 @@
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::mpq::archive_path::ArchivePath;
 use std::{fs, io, io::Read, path::Path};
 
 /// Расширение для [`Path`] с проверкой и чтением файла.
+/// Путь вида `archive.mpq:inner\file.blp` ([`ArchivePath`]) читает файл из MPQ-архива.
 pub trait EnsureReadable {
     /// Проверить, что путь указывает на **читаемый обычный файл**.
     /// - Ошибки точные: файл не найден / нет прав / это каталог / не обычный файл / битый путь / ошибка метаданных.
@@ -14,14 +17,17 @@ pub trait EnsureReadable {
 
 impl EnsureReadable for Path {
     fn ensure_readable(&self) -> Result<(), BlpError> {
+        if let Some(ap) = ArchivePath::parse(self) {
+            return ap.archive.as_path().ensure_readable();
+        }
         // Проверяем метаданные (без открытия) — различаем типы объекта и базовые причины.
         match fs::symlink_metadata(self) {
             Ok(meta) => {
                 if meta.is_dir() {
-                    return Err(BlpError::new("error-path-is-directory").with_arg("path", self.to_string_lossy().into_owned()));
+                    return Err(BlpError::new(ErrorKind::Io, "error-path-is-directory").with_arg("path", self.to_string_lossy().into_owned()));
                 }
                 if !meta.is_file() {
-                    return Err(BlpError::new("error-path-not-regular-file").with_arg("path", self.to_string_lossy().into_owned()));
+                    return Err(BlpError::new(ErrorKind::Io, "error-path-not-regular-file").with_arg("path", self.to_string_lossy().into_owned()));
                 }
                 Ok(())
             }
@@ -33,7 +39,7 @@ impl EnsureReadable for Path {
                     InvalidInput => "error-invalid-path",
                     _ => "error-io-metadata",
                 };
-                Err(BlpError::new(key)
+                Err(BlpError::new(ErrorKind::Io, key)
                     .with_arg("path", self.to_string_lossy().into_owned())
                     .with_arg("msg", e.to_string())
                     .push_std(e))
@@ -42,6 +48,11 @@ impl EnsureReadable for Path {
     }
 
     fn read_all(&self) -> Result<Vec<u8>, BlpError> {
+        if let Some(ap) = ArchivePath::parse(self) {
+            ap.archive.as_path().ensure_readable()?;
+            return ap.read();
+        }
+
         // Открываем (это и проверка прав/существования, и одно открытие).
         let mut file = fs::File::open(self).map_err(|e| {
             use io::ErrorKind::*;
@@ -51,7 +62,7 @@ impl EnsureReadable for Path {
                 InvalidInput => "error-invalid-path",
                 _ => "error-io-open-file",
             };
-            BlpError::new(key)
+            BlpError::new(ErrorKind::Io, key)
                 .with_arg("path", self.to_string_lossy().into_owned())
                 .with_arg("msg", e.to_string())
                 .push_std(e)
@@ -61,7 +72,7 @@ impl EnsureReadable for Path {
         let mut buf = Vec::new();
         file.read_to_end(&mut buf)
             .map_err(|e| {
-                BlpError::new("error-io-read-file")
+                BlpError::new(ErrorKind::Io, "error-io-read-file")
                     .with_arg("path", self.to_string_lossy().into_owned())
                     .with_arg("msg", e.to_string())
                     .push_std(e)
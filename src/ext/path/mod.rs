@@ -1,4 +1,4 @@
-#[cfg(feature = "ui")]
+#[cfg(feature = "cli")]
 pub mod ensure_readable;
 #[cfg(feature = "ui")]
 pub mod to_abs_string_with_macros;
@@ -0,0 +1,6 @@
+use crate::i18n::lng_list::LngList;
+
+/// Язык интерфейса по локали ОС (`sys-locale`).
+pub fn detect_os_lang() -> LngList {
+    LngList::from_locale(&sys_locale::get_locale().unwrap_or_default())
+}
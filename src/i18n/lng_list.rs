@@ -0,0 +1,97 @@
+use fluent_templates::{LanguageIdentifier, langid};
+#[cfg(feature = "ui")]
+use serde::{Deserialize, Serialize};
+
+// Удобный enum для UI и CLI (`--lang`)
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "ui", derive(Serialize, Deserialize))]
+pub enum LngList {
+    #[default]
+    En,
+    Ru,
+    Uk,
+    Zh,
+    Tc,
+}
+
+impl LngList {
+    pub fn id(self) -> LanguageIdentifier {
+        match self {
+            LngList::En => langid!("en"),
+            LngList::Ru => langid!("ru"),
+            LngList::Uk => langid!("uk"),
+            LngList::Zh => langid!("zh"),
+            LngList::Tc => langid!("tc"),
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            LngList::En => "English",
+            LngList::Ru => "Русский",
+            LngList::Uk => "Українська",
+            LngList::Zh => "简体中文",
+            LngList::Tc => "繁體中文",
+        }
+    }
+
+    /// Язык из BCP 47 / POSIX-тега (`ru-RU`, `zh_Hant_TW`, `uk`); неизвестное → English.
+    pub fn from_locale(raw: &str) -> LngList {
+        let lc = raw.to_lowercase();
+        let mut it = lc.split(['-', '_']);
+
+        let primary = it.next().unwrap_or("");
+
+        // Быстрые пути для не-китайских
+        match primary {
+            "uk" => return LngList::Uk,
+            "ru" => return LngList::Ru,
+            // Иногда ОС/проги возвращают "sc"/"tc"
+            "sc" => return LngList::Zh, // Simplified
+            "tc" => return LngList::Tc, // Traditional
+            // Кантонизский иногда как отдельный первичный язык
+            "yue" => return LngList::Tc, // считаем как Traditional
+            _ => {}
+        }
+
+        // Китайские варианты и их подметки
+        if primary == "zh" {
+            let subtags: Vec<&str> = it.collect();
+
+            // Признаки традиционного китайского:
+            // - script: Hant
+            // - region: TW, HK, MO
+            // - legacy: CHT
+            // - диалект: yue (к примеру "zh-yue")
+            let is_traditional = lc.contains("hant")
+                || subtags
+                    .iter()
+                    .any(|s| matches!(*s, "tw" | "hk" | "mo"))
+                || lc.contains("cht")
+                || lc.contains("yue");
+
+            if is_traditional {
+                return LngList::Tc;
+            }
+
+            // Признаки упрощённого:
+            // - script: Hans
+            // - region: CN, SG
+            // - legacy: CHS
+            let is_simplified = lc.contains("hans")
+                || subtags
+                    .iter()
+                    .any(|s| matches!(*s, "cn" | "sg"))
+                || lc.contains("chs");
+
+            if is_simplified {
+                return LngList::Zh;
+            }
+
+            // Если просто "zh" или непонятные подметки — считаем упрощённым по умолчанию
+            return LngList::Zh;
+        }
+
+        // Дефолт — английский
+        LngList::En
+    }
+}
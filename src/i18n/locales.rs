@@ -0,0 +1,13 @@
+use fluent_templates::static_loader;
+
+// Сгенерированный при компиляции лоадер.
+// Он сам найдёт assets/locales/{lang}/*.ftl, вшьёт и даст API lookup/lookup_with_args.
+// Общий для UI и CLI: строки интерфейса и тексты ошибок лежат в одних бандлах.
+static_loader! {
+    pub static LOCALES = {
+        locales: "./assets/locales",
+        fallback_language: "en",
+        // можно тонко настроить бандл:
+        customise: |bundle| bundle.set_use_isolating(false),
+    };
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "cli")]
+pub mod detect_os_lang;
+pub mod lng_list;
+pub mod locales;
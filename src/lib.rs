@@ -3,6 +3,9 @@ mod cli;
 pub mod core;
 pub mod error;
 mod ext;
+pub mod i18n;
+#[cfg(feature = "mpq")]
+pub mod mpq;
 pub mod run;
 #[cfg(feature = "ui")]
 pub mod ui;
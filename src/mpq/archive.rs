@@ -0,0 +1,183 @@
+use crate::core::decode::decode_limits::DecodeLimits;
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::mpq::block_entry::BlockEntry;
+use crate::mpq::compression::decompress_sector;
+use crate::mpq::crypt::{HASH_FILE_KEY, decrypt, file_key, hash_string};
+use crate::mpq::hash_entry::{HashEntry, find_entry};
+use crate::mpq::header::MpqHeader;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// MPQ-архив (War3.mpq, War3x.mpq, карты `.w3x`/`.w3m`): поиск и чтение файлов по имени.
+///
+/// Поддерживаются шифрованные файлы и таблицы, сжатие zlib, bzip2 и PKWARE (implode).
+/// Размер распакованного файла ограничен `DecodeLimits::default().max_alloc`.
+pub struct MpqArchive<R> {
+    reader: R,
+    len: u64,
+    pub(crate) header: MpqHeader,
+    pub(crate) hashes: Vec<HashEntry>,
+    pub(crate) blocks: Vec<BlockEntry>,
+}
+
+impl MpqArchive<BufReader<File>> {
+    pub fn open(path: &Path) -> Result<Self, BlpError> {
+        let file = File::open(path).map_err(|e| BlpError::from(e).ctx_with("error-mpq-open", |e| e.with_arg("path", path.to_string_lossy().into_owned())))?;
+        Self::new(BufReader::new(file)).map_err(|e| e.ctx_with("error-mpq-open", |e| e.with_arg("path", path.to_string_lossy().into_owned())))
+    }
+}
+
+impl<R: Read + Seek> MpqArchive<R> {
+    /// Прочитать заголовок и обе таблицы. Таблицы, обрезанные концом файла
+    /// (так делают «защищённые» карты), читаются до конца файла.
+    pub fn new(mut reader: R) -> Result<Self, BlpError> {
+        let header = MpqHeader::find(&mut reader)?;
+        let len = reader.seek(SeekFrom::End(0))?;
+
+        let raw = read_table(&mut reader, len, header.archive_offset + header.hash_table_pos as u64, header.hash_table_size, hash_string("(hash table)", HASH_FILE_KEY))?;
+        let hashes = raw
+            .chunks_exact(HashEntry::SIZE)
+            .map(HashEntry::from_bytes)
+            .collect();
+        let raw = read_table(&mut reader, len, header.archive_offset + header.block_table_pos as u64, header.block_table_size, hash_string("(block table)", HASH_FILE_KEY))?;
+        let blocks = raw
+            .chunks_exact(BlockEntry::SIZE)
+            .map(BlockEntry::from_bytes)
+            .collect();
+
+        Ok(Self { reader, len, header, hashes, blocks })
+    }
+
+    /// Есть ли файл в архиве (имя без учёта регистра, `/` == `\`).
+    pub fn contains(&self, name: &str) -> bool {
+        self.block_of(name).is_some()
+    }
+
+    /// Прочитать и распаковать файл.
+    pub fn read_file(&mut self, name: &str) -> Result<Vec<u8>, BlpError> {
+        let block = self
+            .block_of(name)
+            .ok_or_else(|| BlpError::new(ErrorKind::Io, "error-mpq-file-not-found").with_arg("name", name))?;
+        self.read_block(name, block)
+            .map_err(|e| e.ctx_with("error-mpq-read-file", |e| e.with_arg("name", name)))
+    }
+
+    /// Имена из `(listfile)`, которые действительно есть в архиве. Без `(listfile)` — пусто.
+    pub fn file_names(&mut self) -> Result<Vec<String>, BlpError> {
+        if !self.contains("(listfile)") {
+            return Ok(Vec::new());
+        }
+        let list = self.read_file("(listfile)")?;
+        Ok(parse_listfile(&list)
+            .filter(|n| self.contains(n))
+            .collect())
+    }
+
+    pub(crate) fn block_of(&self, name: &str) -> Option<BlockEntry> {
+        let i = find_entry(&self.hashes, name)?;
+        self.blocks
+            .get(self.hashes[i].block_index as usize)
+            .copied()
+            .filter(|b| b.has(BlockEntry::EXISTS))
+    }
+
+    fn read_block(&mut self, name: &str, b: BlockEntry) -> Result<Vec<u8>, BlpError> {
+        let file_size = b.file_size as usize;
+        DecodeLimits::default().check_alloc(file_size as u64)?;
+        let key = b
+            .has(BlockEntry::ENCRYPTED)
+            .then(|| file_key(name, b.offset, b.file_size, b.has(BlockEntry::FIX_KEY)));
+        let compressed = b.has(BlockEntry::COMPRESS) || b.has(BlockEntry::IMPLODE);
+        let pos = self.header.archive_offset + b.offset as u64;
+
+        // Одним куском: файл целиком — один «сектор»
+        if b.has(BlockEntry::SINGLE_UNIT) || file_size == 0 {
+            let size = if compressed { b.compressed_size as usize } else { file_size };
+            let mut data = self.read_at(pos, size)?;
+            if let Some(key) = key {
+                decrypt(&mut data, key);
+            }
+            return match compressed && data.len() < file_size {
+                true => decompress_sector(&data, file_size, b.has(BlockEntry::IMPLODE)),
+                false => Ok(data),
+            };
+        }
+
+        let sector_size = self.header.sector_size();
+        let sectors = file_size.div_ceil(sector_size);
+
+        // Несжатый файл: сектора подряд, шифруются по отдельности
+        if !compressed {
+            let mut data = self.read_at(pos, file_size)?;
+            if let Some(key) = key {
+                for (i, sector) in data.chunks_mut(sector_size).enumerate() {
+                    decrypt(sector, key.wrapping_add(i as u32));
+                }
+            }
+            return Ok(data);
+        }
+
+        // Таблица смещений секторов (+1 запись на конец; CRC-запись не нужна)
+        let mut table = self.read_at(pos, (sectors + 1) * 4)?;
+        if let Some(key) = key {
+            decrypt(&mut table, key.wrapping_sub(1));
+        }
+        let offsets: Vec<usize> = table
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()) as usize)
+            .collect();
+        let (first, last) = (offsets[0], offsets[sectors]);
+        if offsets.windows(2).any(|w| w[0] > w[1]) || first < table.len() {
+            return Err(BlpError::new(ErrorKind::InvalidArchive, "error-mpq-sector-table"));
+        }
+        let packed = self.read_at(pos + first as u64, last - first)?;
+
+        let mut out = Vec::with_capacity(file_size);
+        for i in 0..sectors {
+            let mut sector = packed[offsets[i] - first..offsets[i + 1] - first].to_vec();
+            if let Some(key) = key {
+                decrypt(&mut sector, key.wrapping_add(i as u32));
+            }
+            let expected = sector_size.min(file_size - i * sector_size);
+            if sector.len() == expected {
+                out.extend_from_slice(&sector);
+            } else {
+                out.extend_from_slice(&decompress_sector(&sector, expected, b.has(BlockEntry::IMPLODE))?);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Прочитать `size` байт с абсолютной позиции; выход за конец файла — `Truncated` без выделения.
+    fn read_at(&mut self, pos: u64, size: usize) -> Result<Vec<u8>, BlpError> {
+        if pos.saturating_add(size as u64) > self.len {
+            return Err(BlpError::new(ErrorKind::Truncated, "error-mpq-out-of-bounds")
+                .with_arg("offset", pos)
+                .with_arg("size", size as u64));
+        }
+        self.reader.seek(SeekFrom::Start(pos))?;
+        let mut buf = vec![0u8; size];
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// Прочитать и расшифровать таблицу из `count` записей по 16 байт.
+fn read_table<R: Read + Seek>(r: &mut R, len: u64, pos: u64, count: u32, key: u32) -> Result<Vec<u8>, BlpError> {
+    let available = len.saturating_sub(pos) / 16;
+    let count = (count as u64).min(available) as usize;
+    r.seek(SeekFrom::Start(pos.min(len)))?;
+    let mut buf = vec![0u8; count * 16];
+    r.read_exact(&mut buf)?;
+    decrypt(&mut buf, key);
+    Ok(buf)
+}
+
+/// Строки `(listfile)`: разделители — переводы строк и `;`.
+pub(crate) fn parse_listfile(data: &[u8]) -> impl Iterator<Item = String> + '_ {
+    data.split(|&b| matches!(b, b'\r' | b'\n' | b';'))
+        .map(|s| String::from_utf8_lossy(s).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
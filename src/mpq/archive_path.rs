@@ -0,0 +1,51 @@
+use crate::error::error::BlpError;
+use crate::mpq::archive::MpqArchive;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Расширения, после которых `:` отделяет путь внутри архива.
+const ARCHIVE_EXTS: [&str; 4] = [".mpq", ".w3x", ".w3m", ".w3n"];
+
+/// Файл внутри MPQ: `War3.mpq:Textures\Foo.blp`, `map.w3x:war3mapImported/icon.blp`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivePath {
+    pub archive: PathBuf,
+    /// Имя внутри архива, через `\`.
+    pub inner: String,
+}
+
+impl ArchivePath {
+    /// Разобрать путь; `None` — обычный файл. Двоеточие диска Windows (`C:\…`) не мешает:
+    /// ищется первое `:` сразу после расширения архива.
+    pub fn parse(path: &Path) -> Option<Self> {
+        let s = path.to_str()?;
+        s.match_indices(':').find_map(|(i, _)| {
+            let (archive, inner) = (&s[..i], &s[i + 1..]);
+            let lower = archive.to_ascii_lowercase();
+            (!inner.is_empty() && ARCHIVE_EXTS.iter().any(|ext| lower.ends_with(ext))).then(|| Self { archive: PathBuf::from(archive), inner: inner.replace('/', "\\") })
+        })
+    }
+
+    /// Имя файла без каталогов внутри архива.
+    pub fn file_name(&self) -> &str {
+        self.inner
+            .rsplit('\\')
+            .next()
+            .unwrap_or(&self.inner)
+    }
+
+    pub fn read(&self) -> Result<Vec<u8>, BlpError> {
+        MpqArchive::open(&self.archive)?.read_file(&self.inner)
+    }
+
+    /// Записать (добавить или заменить) файл в архив на диске.
+    pub fn write(&self, data: &[u8]) -> Result<(), BlpError> {
+        MpqArchive::import(&self.archive, &[(&self.inner, data)])
+    }
+}
+
+impl fmt::Display for ArchivePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.archive.display(), self.inner)
+    }
+}
@@ -0,0 +1,36 @@
+/// Запись таблицы блоков MPQ (16 байт).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockEntry {
+    /// Смещение данных от начала архива.
+    pub offset: u32,
+    pub compressed_size: u32,
+    pub file_size: u32,
+    pub flags: u32,
+}
+
+impl BlockEntry {
+    pub const SIZE: usize = 16;
+
+    pub const IMPLODE: u32 = 0x0000_0100;
+    pub const COMPRESS: u32 = 0x0000_0200;
+    pub const ENCRYPTED: u32 = 0x0001_0000;
+    pub const FIX_KEY: u32 = 0x0002_0000;
+    pub const SINGLE_UNIT: u32 = 0x0100_0000;
+    pub const EXISTS: u32 = 0x8000_0000;
+
+    pub fn from_bytes(b: &[u8]) -> Self {
+        let u32_at = |i: usize| u32::from_le_bytes(b[i..i + 4].try_into().unwrap());
+        Self { offset: u32_at(0), compressed_size: u32_at(4), file_size: u32_at(8), flags: u32_at(12) }
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.offset.to_le_bytes());
+        out.extend_from_slice(&self.compressed_size.to_le_bytes());
+        out.extend_from_slice(&self.file_size.to_le_bytes());
+        out.extend_from_slice(&self.flags.to_le_bytes());
+    }
+
+    pub fn has(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+}
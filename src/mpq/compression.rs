@@ -0,0 +1,80 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::mpq::block_entry::BlockEntry;
+use crate::mpq::explode::explode;
+use std::io::{Read, Write};
+
+/// Биты маски сжатия — первый байт сжатого сектора.
+const ZLIB: u8 = 0x02;
+const PKWARE: u8 = 0x08;
+const BZIP2: u8 = 0x10;
+
+/// Распаковать сектор ровно в `expected` байт.
+/// `imploded` — файл с флагом `IMPLODE`: поток PKWARE без байта маски.
+pub(crate) fn decompress_sector(data: &[u8], expected: usize, imploded: bool) -> Result<Vec<u8>, BlpError> {
+    let out = if imploded {
+        explode(data, expected)?
+    } else {
+        let (&mask, body) = data
+            .split_first()
+            .ok_or_else(|| BlpError::new(ErrorKind::InvalidArchive, "error-mpq-decompress").with_arg("method", "none"))?;
+        match mask {
+            ZLIB => read_limited(flate2::read::ZlibDecoder::new(body), expected, "zlib")?,
+            PKWARE => explode(body, expected)?,
+            BZIP2 => read_limited(bzip2::read::BzDecoder::new(body), expected, "bzip2")?,
+            // Huffman/ADPCM — только у звуков, LZMA/sparse — у архивов StarCraft II
+            _ => return Err(BlpError::new(ErrorKind::UnsupportedCompression, "error-mpq-compression").with_arg("mask", mask as u32)),
+        }
+    };
+    if out.len() != expected {
+        return Err(BlpError::new(ErrorKind::InvalidArchive, "error-mpq-sector-size-mismatch")
+            .with_arg("expected", expected as u64)
+            .with_arg("actual", out.len() as u64));
+    }
+    Ok(out)
+}
+
+fn read_limited(r: impl Read, expected: usize, method: &'static str) -> Result<Vec<u8>, BlpError> {
+    let mut out = Vec::with_capacity(expected);
+    r.take(expected as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| {
+            BlpError::new(ErrorKind::InvalidArchive, "error-mpq-decompress")
+                .with_arg("method", method)
+                .push_std(e)
+        })?;
+    Ok(out)
+}
+
+/// Упаковать файл для записи в архив: посекторно zlib, как World Editor.
+/// Сектор, который не сжимается, хранится как есть. Возвращает данные блока и флаги.
+pub(crate) fn pack_file(data: &[u8], sector_size: usize) -> Result<(Vec<u8>, u32), BlpError> {
+    if data.is_empty() {
+        return Ok((Vec::new(), BlockEntry::EXISTS));
+    }
+    let sectors = data.len().div_ceil(sector_size);
+    let table_len = (sectors + 1) * 4;
+
+    let mut offsets = Vec::with_capacity(sectors + 1);
+    let mut body = Vec::new();
+    for sector in data.chunks(sector_size) {
+        offsets.push((table_len + body.len()) as u32);
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        enc.write_all(sector)?;
+        let packed = enc.finish()?;
+        if packed.len() + 1 < sector.len() {
+            body.push(ZLIB);
+            body.extend_from_slice(&packed);
+        } else {
+            body.extend_from_slice(sector);
+        }
+    }
+    offsets.push((table_len + body.len()) as u32);
+
+    let mut out = Vec::with_capacity(table_len + body.len());
+    for o in offsets {
+        out.extend_from_slice(&o.to_le_bytes());
+    }
+    out.extend_from_slice(&body);
+    Ok((out, BlockEntry::EXISTS | BlockEntry::COMPRESS))
+}
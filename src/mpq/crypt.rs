@@ -0,0 +1,86 @@
+use std::sync::OnceLock;
+
+/// Типы хэша имени (`hash_string`).
+pub(crate) const HASH_OFFSET: u32 = 0;
+pub(crate) const HASH_NAME_A: u32 = 1;
+pub(crate) const HASH_NAME_B: u32 = 2;
+pub(crate) const HASH_FILE_KEY: u32 = 3;
+
+static CRYPT_TABLE: OnceLock<[u32; 0x500]> = OnceLock::new();
+
+fn crypt_table() -> &'static [u32; 0x500] {
+    CRYPT_TABLE.get_or_init(|| {
+        let mut table = [0u32; 0x500];
+        let mut seed: u32 = 0x0010_0001;
+        for i in 0..0x100 {
+            for j in 0..5 {
+                seed = (seed * 125 + 3) % 0x2A_AAAB;
+                let hi = (seed & 0xFFFF) << 16;
+                seed = (seed * 125 + 3) % 0x2A_AAAB;
+                let lo = seed & 0xFFFF;
+                table[i + j * 0x100] = hi | lo;
+            }
+        }
+        table
+    })
+}
+
+/// Хэш имени файла в архиве: регистр не важен, `/` == `\`.
+pub(crate) fn hash_string(name: &str, hash_type: u32) -> u32 {
+    let table = crypt_table();
+    let mut seed1: u32 = 0x7FED_7FED;
+    let mut seed2: u32 = 0xEEEE_EEEE;
+    for &b in name.as_bytes() {
+        let ch = match b {
+            b'/' => b'\\',
+            _ => b.to_ascii_uppercase(),
+        } as u32;
+        seed1 = table[((hash_type << 8) + ch) as usize] ^ seed1.wrapping_add(seed2);
+        seed2 = ch
+            .wrapping_add(seed1)
+            .wrapping_add(seed2)
+            .wrapping_add(seed2 << 5)
+            .wrapping_add(3);
+    }
+    seed1
+}
+
+/// Ключ шифрования файла: хэш имени без каталога; с `FIX_KEY` — ещё смещение и размер.
+pub(crate) fn file_key(name: &str, offset: u32, file_size: u32, fix_key: bool) -> u32 {
+    let base = name.rsplit(['\\', '/']).next().unwrap_or(name);
+    let key = hash_string(base, HASH_FILE_KEY);
+    if fix_key { key.wrapping_add(offset) ^ file_size } else { key }
+}
+
+/// Расшифровать блок на месте. Хвост короче 4 байт не шифруется.
+pub(crate) fn decrypt(data: &mut [u8], mut key: u32) {
+    let table = crypt_table();
+    let mut seed: u32 = 0xEEEE_EEEE;
+    for chunk in data.chunks_exact_mut(4) {
+        seed = seed.wrapping_add(table[0x400 + (key & 0xFF) as usize]);
+        let plain = u32::from_le_bytes(chunk.try_into().unwrap()) ^ key.wrapping_add(seed);
+        key = ((!key << 21).wrapping_add(0x1111_1111)) | (key >> 11);
+        seed = plain
+            .wrapping_add(seed)
+            .wrapping_add(seed << 5)
+            .wrapping_add(3);
+        chunk.copy_from_slice(&plain.to_le_bytes());
+    }
+}
+
+/// Зашифровать блок на месте (обратное к [`decrypt`]).
+pub(crate) fn encrypt(data: &mut [u8], mut key: u32) {
+    let table = crypt_table();
+    let mut seed: u32 = 0xEEEE_EEEE;
+    for chunk in data.chunks_exact_mut(4) {
+        seed = seed.wrapping_add(table[0x400 + (key & 0xFF) as usize]);
+        let plain = u32::from_le_bytes(chunk.try_into().unwrap());
+        let enc = plain ^ key.wrapping_add(seed);
+        key = ((!key << 21).wrapping_add(0x1111_1111)) | (key >> 11);
+        seed = plain
+            .wrapping_add(seed)
+            .wrapping_add(seed << 5)
+            .wrapping_add(3);
+        chunk.copy_from_slice(&enc.to_le_bytes());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use std::sync::OnceLock;
+
+// PKWARE Data Compression Library («implode»), которым сжаты старые файлы War3.mpq.
+// Формат и таблицы — по blast.c Марка Адлера.
+
+const MAX_BITS: usize = 13;
+
+/// Длины кодов в сжатом виде: байт = (повторы − 1) << 4 | длина.
+const LIT_LEN: [u8; 98] = [
+    11, 124, 8, 7, 28, 7, 188, 13, 76, 4, 10, 8, 12, 10, 12, 10, 8, 23, 8, 9, 7, 6, 7, 8, 7, 6, 55, 8, 23, 24, 12, 11, 7, 9, 11, 12, 6, 7, 22, 5, 7, 24, 6, 11, 9, 6, 7, 22, 7, 11, 38, 7, 9, 8, 25, 11, 8, 11, 9, 12, 8, 12, 5, 38, 5, 38, 5, 11, 7, 5, 6, 21, 6, 10, 53, 8, 7, 24, 10, 27, 44, 253, 253, 253, 252, 252, 252, 13, 12, 45, 12, 45, 12, 61, 12, 45, 44, 173,
+];
+const LEN_LEN: [u8; 6] = [2, 35, 36, 53, 38, 23];
+const DIST_LEN: [u8; 7] = [2, 20, 53, 230, 247, 151, 248];
+const LEN_BASE: [u16; 16] = [3, 2, 4, 5, 6, 7, 8, 9, 10, 12, 16, 24, 40, 72, 136, 264];
+const LEN_EXTRA: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+/// Длина-маркер конца потока.
+const END: u16 = 519;
+
+/// Канонический код Хаффмана: число кодов каждой длины и символы по возрастанию кода.
+struct Huffman {
+    count: [u16; MAX_BITS + 1],
+    symbol: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(rep: &[u8]) -> Self {
+        let lengths: Vec<usize> = rep
+            .iter()
+            .flat_map(|&r| std::iter::repeat_n((r & 15) as usize, (r >> 4) as usize + 1))
+            .collect();
+
+        let mut count = [0u16; MAX_BITS + 1];
+        for &len in &lengths {
+            count[len] += 1;
+        }
+        let mut offs = [0usize; MAX_BITS + 1];
+        for len in 1..MAX_BITS {
+            offs[len + 1] = offs[len] + count[len] as usize;
+        }
+        let mut symbol = vec![0u16; lengths.len()];
+        for (sym, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbol[offs[len]] = sym as u16;
+                offs[len] += 1;
+            }
+        }
+        Self { count, symbol }
+    }
+}
+
+struct Tables {
+    lit: Huffman,
+    len: Huffman,
+    dist: Huffman,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+/// Поток бит, младший бит байта — первый.
+struct Bits<'a> {
+    input: &'a [u8],
+    pos: usize,
+    buf: u32,
+    cnt: u32,
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, need: u32) -> Option<u32> {
+        while self.cnt < need {
+            let b = *self.input.get(self.pos)?;
+            self.pos += 1;
+            self.buf |= (b as u32) << self.cnt;
+            self.cnt += 8;
+        }
+        let v = self.buf & ((1 << need) - 1);
+        self.buf >>= need;
+        self.cnt -= need;
+        Some(v)
+    }
+
+    /// Символ по коду Хаффмана; биты кода в потоке инвертированы.
+    fn decode(&mut self, h: &Huffman) -> Option<u16> {
+        let (mut code, mut first, mut index) = (0usize, 0usize, 0usize);
+        for len in 1..=MAX_BITS {
+            code |= (self.bits(1)? ^ 1) as usize;
+            let count = h.count[len] as usize;
+            if code < first + count {
+                return Some(h.symbol[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+}
+
+/// Распаковать поток PKWARE DCL; выход — не длиннее `max_out`.
+pub(crate) fn explode(input: &[u8], max_out: usize) -> Result<Vec<u8>, BlpError> {
+    let t = TABLES.get_or_init(|| Tables { lit: Huffman::new(&LIT_LEN), len: Huffman::new(&LEN_LEN), dist: Huffman::new(&DIST_LEN) });
+    let bad = || BlpError::new(ErrorKind::InvalidArchive, "error-mpq-decompress").with_arg("method", "pkware");
+
+    let mut s = Bits { input, pos: 0, buf: 0, cnt: 0 };
+    let coded_literals = match s.bits(8).ok_or_else(bad)? {
+        0 => false,
+        1 => true,
+        _ => return Err(bad()),
+    };
+    let dict = s.bits(8).ok_or_else(bad)?;
+    if !(4..=6).contains(&dict) {
+        return Err(bad());
+    }
+
+    let mut out = Vec::with_capacity(max_out);
+    loop {
+        if s.bits(1).ok_or_else(bad)? == 1 {
+            let sym = s.decode(&t.len).ok_or_else(bad)? as usize;
+            let len = LEN_BASE[sym] + s.bits(LEN_EXTRA[sym] as u32).ok_or_else(bad)? as u16;
+            if len == END {
+                break;
+            }
+            let shift = if len == 2 { 2 } else { dict };
+            let dist = ((s.decode(&t.dist).ok_or_else(bad)? as usize) << shift) + s.bits(shift).ok_or_else(bad)? as usize + 1;
+            if dist > out.len() || out.len() + len as usize > max_out {
+                return Err(bad());
+            }
+            for _ in 0..len {
+                out.push(out[out.len() - dist]);
+            }
+        } else {
+            let lit = match coded_literals {
+                true => s.decode(&t.lit).ok_or_else(bad)? as u8,
+                false => s.bits(8).ok_or_else(bad)? as u8,
+            };
+            if out.len() == max_out {
+                return Err(bad());
+            }
+            out.push(lit);
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,89 @@
+use crate::mpq::crypt::{HASH_NAME_A, HASH_NAME_B, HASH_OFFSET, hash_string};
+
+/// Запись хэш-таблицы MPQ (16 байт).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HashEntry {
+    pub name_a: u32,
+    pub name_b: u32,
+    pub locale: u16,
+    pub platform: u16,
+    pub block_index: u32,
+}
+
+impl HashEntry {
+    pub const SIZE: usize = 16;
+    /// Слот ни разу не занимался — поиск на нём останавливается.
+    pub const EMPTY: u32 = 0xFFFF_FFFF;
+    /// Файл удалён — поиск идёт дальше.
+    pub const DELETED: u32 = 0xFFFF_FFFE;
+
+    pub fn empty() -> Self {
+        Self { name_a: 0xFFFF_FFFF, name_b: 0xFFFF_FFFF, locale: 0xFFFF, platform: 0xFFFF, block_index: Self::EMPTY }
+    }
+
+    pub fn deleted() -> Self {
+        Self { block_index: Self::DELETED, ..Self::empty() }
+    }
+
+    pub fn new(name: &str, block_index: u32) -> Self {
+        Self {
+            name_a: hash_string(name, HASH_NAME_A),
+            name_b: hash_string(name, HASH_NAME_B),
+            locale: 0,
+            platform: 0,
+            block_index,
+        }
+    }
+
+    pub fn from_bytes(b: &[u8]) -> Self {
+        let u32_at = |i: usize| u32::from_le_bytes(b[i..i + 4].try_into().unwrap());
+        Self {
+            name_a: u32_at(0),
+            name_b: u32_at(4),
+            locale: u16::from_le_bytes([b[8], b[9]]),
+            platform: u16::from_le_bytes([b[10], b[11]]),
+            block_index: u32_at(12),
+        }
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.name_a.to_le_bytes());
+        out.extend_from_slice(&self.name_b.to_le_bytes());
+        out.extend_from_slice(&self.locale.to_le_bytes());
+        out.extend_from_slice(&self.platform.to_le_bytes());
+        out.extend_from_slice(&self.block_index.to_le_bytes());
+    }
+}
+
+/// Слоты таблицы в порядке линейного пробирования для имени — с первого, как в игре.
+fn probe(table_len: usize, name: &str) -> impl Iterator<Item = usize> {
+    let start = match table_len {
+        0 => 0,
+        n => (hash_string(name, HASH_OFFSET) as usize & (n.next_power_of_two() - 1)) % n,
+    };
+    (0..table_len).map(move |i| (start + i) % table_len)
+}
+
+/// Индекс записи файла: сначала нейтральная локаль, иначе первая найденная.
+pub(crate) fn find_entry(table: &[HashEntry], name: &str) -> Option<usize> {
+    let (a, b) = (hash_string(name, HASH_NAME_A), hash_string(name, HASH_NAME_B));
+    let mut found = None;
+    for i in probe(table.len(), name) {
+        let e = &table[i];
+        if e.block_index == HashEntry::EMPTY {
+            break;
+        }
+        if e.block_index != HashEntry::DELETED && e.name_a == a && e.name_b == b {
+            if e.locale == 0 {
+                return Some(i);
+            }
+            found.get_or_insert(i);
+        }
+    }
+    found
+}
+
+/// Первый свободный (пустой или удалённый) слот для нового имени.
+pub(crate) fn free_slot(table: &[HashEntry], name: &str) -> Option<usize> {
+    probe(table.len(), name).find(|&i| matches!(table[i].block_index, HashEntry::EMPTY | HashEntry::DELETED))
+}
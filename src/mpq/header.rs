@@ -0,0 +1,95 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Read, Seek, SeekFrom};
+
+/// Заголовок MPQ v1 (32 байта). Поля расширенных версий не читаются — как и в самом Warcraft III,
+/// который всегда работает с архивом как с v1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MpqHeader {
+    /// Позиция заголовка в файле (у карт `.w3x` — 512); смещения таблиц и блоков — от неё.
+    pub archive_offset: u64,
+    pub archive_size: u32,
+    pub format_version: u16,
+    pub sector_size_shift: u16,
+    pub hash_table_pos: u32,
+    pub block_table_pos: u32,
+    pub hash_table_size: u32,
+    pub block_table_size: u32,
+}
+
+impl MpqHeader {
+    pub const SIZE: usize = 32;
+    const MAGIC: &'static [u8; 4] = b"MPQ\x1A";
+    const USER_DATA_MAGIC: &'static [u8; 4] = b"MPQ\x1B";
+
+    /// Найти заголовок: он лежит на границе 512 байт (впереди может быть заголовок карты или `MPQ\x1B`).
+    pub fn find<R: Read + Seek>(r: &mut R) -> Result<Self, BlpError> {
+        let len = r.seek(SeekFrom::End(0))?;
+        let mut pos = 0u64;
+        while pos + Self::SIZE as u64 <= len {
+            r.seek(SeekFrom::Start(pos))?;
+            let mut magic = [0u8; 4];
+            r.read_exact(&mut magic)?;
+            if &magic == Self::MAGIC {
+                return Self::read_at(r, pos);
+            }
+            if &magic == Self::USER_DATA_MAGIC {
+                let _user_data_size = r.read_u32::<LittleEndian>()?;
+                let header_offset = r.read_u32::<LittleEndian>()? as u64;
+                let at = pos + header_offset;
+                if header_offset > 0 && at + Self::SIZE as u64 <= len {
+                    r.seek(SeekFrom::Start(at))?;
+                    r.read_exact(&mut magic)?;
+                    if &magic == Self::MAGIC {
+                        return Self::read_at(r, at);
+                    }
+                }
+            }
+            pos += 512;
+        }
+        Err(BlpError::new(ErrorKind::InvalidArchive, "error-mpq-no-header"))
+    }
+
+    /// Поля после сигнатуры; поток стоит на `pos + 4`.
+    fn read_at<R: Read>(r: &mut R, pos: u64) -> Result<Self, BlpError> {
+        let _header_size = r.read_u32::<LittleEndian>()?;
+        let archive_size = r.read_u32::<LittleEndian>()?;
+        let format_version = r.read_u16::<LittleEndian>()?;
+        let sector_size_shift = r.read_u16::<LittleEndian>()?;
+        let h = Self {
+            archive_offset: pos,
+            archive_size,
+            format_version,
+            sector_size_shift,
+            hash_table_pos: r.read_u32::<LittleEndian>()?,
+            block_table_pos: r.read_u32::<LittleEndian>()?,
+            hash_table_size: r.read_u32::<LittleEndian>()?,
+            block_table_size: r.read_u32::<LittleEndian>()?,
+        };
+        if h.sector_size_shift > 16 {
+            return Err(BlpError::new(ErrorKind::InvalidArchive, "error-mpq-sector-size").with_arg("shift", h.sector_size_shift as u32));
+        }
+        Ok(h)
+    }
+
+    /// Размер сектора: `512 << shift` (у Warcraft III — 4096).
+    pub fn sector_size(&self) -> usize {
+        512 << self.sector_size_shift
+    }
+
+    /// Заголовок v1 в байтах.
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..4].copy_from_slice(Self::MAGIC);
+        out[4..8].copy_from_slice(&(Self::SIZE as u32).to_le_bytes());
+        out[8..12].copy_from_slice(&self.archive_size.to_le_bytes());
+        out[12..14].copy_from_slice(&0u16.to_le_bytes());
+        out[14..16].copy_from_slice(&self.sector_size_shift.to_le_bytes());
+        out[16..20].copy_from_slice(&self.hash_table_pos.to_le_bytes());
+        out[20..24].copy_from_slice(&self.block_table_pos.to_le_bytes());
+        out[24..28].copy_from_slice(&self.hash_table_size.to_le_bytes());
+        out[28..32].copy_from_slice(&self.block_table_size.to_le_bytes());
+        out
+    }
+}
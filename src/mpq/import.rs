@@ -0,0 +1,88 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::mpq::archive::{MpqArchive, parse_listfile};
+use crate::mpq::block_entry::BlockEntry;
+use crate::mpq::hash_entry::{HashEntry, find_entry};
+use crate::mpq::writer::{append_file, finish_archive, normalize_name, set_entry};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+impl MpqArchive<Cursor<&[u8]>> {
+    /// Больше записей в хэш-таблице не бывает у настоящих карт; это ещё и защита от выделения гигабайт.
+    const MAX_HASH_TABLE_SIZE: usize = 1 << 20;
+
+    /// Добавить или заменить файлы в архиве `buf`; возвращает новый архив.
+    ///
+    /// Старые данные остаются на своих местах (ключи шифрованных файлов зависят от смещения),
+    /// новые дописываются после них, таблицы пишутся заново в конец. `(listfile)` дополняется
+    /// новыми именами, устаревший `(attributes)` удаляется. Если в хэш-таблице нет места — ошибка.
+    /// Хэш-таблица, обрезанная концом файла (защищённые карты), дополняется пустыми записями
+    /// до размера из заголовка: игра ищет по `hash & (size - 1)`.
+    pub fn import_into(buf: &[u8], files: &[(&str, &[u8])]) -> Result<Vec<u8>, BlpError> {
+        let mut ar = MpqArchive::new(Cursor::new(buf))?;
+        let archive_offset = ar.header.archive_offset as usize;
+
+        let mut names: Vec<String> = match ar.contains("(listfile)") {
+            true => parse_listfile(&ar.read_file("(listfile)")?).collect(),
+            false => Vec::new(),
+        };
+        for (name, _) in files {
+            let name = normalize_name(name);
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                names.push(name);
+            }
+        }
+        let listfile = names.join("\r\n").into_bytes();
+
+        // Конец данных существующих файлов; всё дальше — старые таблицы
+        let data_end = ar
+            .blocks
+            .iter()
+            .filter(|b| b.has(BlockEntry::EXISTS))
+            .map(|b| archive_offset + b.offset as usize + b.compressed_size as usize)
+            .fold(archive_offset + 32, usize::max)
+            .min(buf.len());
+
+        let mut out = buf[..data_end].to_vec();
+        let size = ar.header.hash_table_size as usize;
+        if !size.is_power_of_two() || size > Self::MAX_HASH_TABLE_SIZE {
+            return Err(BlpError::new(ErrorKind::InvalidArchive, "error-mpq-hash-table-size").with_arg("size", size as u64));
+        }
+        let mut hashes = ar.hashes.clone();
+        hashes.resize(size, HashEntry::empty());
+        let mut blocks = ar.blocks.clone();
+        let sector_size = ar.header.sector_size();
+
+        let files = files
+            .iter()
+            .map(|&(n, d)| (n, d))
+            .chain([("(listfile)", listfile.as_slice())]);
+        for (name, data) in files {
+            blocks.push(append_file(&mut out, archive_offset, data, sector_size)?);
+            set_entry(&mut hashes, &normalize_name(name), blocks.len() as u32 - 1)?;
+        }
+        if let Some(i) = find_entry(&hashes, "(attributes)") {
+            hashes[i] = HashEntry::deleted();
+        }
+
+        finish_archive(&mut out, archive_offset, ar.header.sector_size_shift, &hashes, &blocks)?;
+        Ok(out)
+    }
+
+    /// [`MpqArchive::import_into`] для архива на диске: запись во временный файл рядом и переименование.
+    pub fn import(path: &Path, files: &[(&str, &[u8])]) -> Result<(), BlpError> {
+        let with_path = |e: BlpError| e.ctx_with("error-mpq-import", |e| e.with_arg("path", path.to_string_lossy().into_owned()));
+        let buf = fs::read(path).map_err(|e| with_path(e.into()))?;
+        let out = Self::import_into(&buf, files).map_err(with_path)?;
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, &out)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| {
+                let _ = fs::remove_file(&tmp);
+                with_path(e.into())
+            })
+    }
+}
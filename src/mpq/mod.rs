@@ -0,0 +1,10 @@
+pub mod archive;
+pub mod archive_path;
+mod block_entry;
+mod compression;
+mod crypt;
+mod explode;
+mod hash_entry;
+mod header;
+mod import;
+pub mod writer;
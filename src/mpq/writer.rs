@@ -0,0 +1,128 @@
+use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
+use crate::mpq::block_entry::BlockEntry;
+use crate::mpq::compression::pack_file;
+use crate::mpq::crypt::{HASH_FILE_KEY, encrypt, hash_string};
+use crate::mpq::hash_entry::{HashEntry, find_entry, free_slot};
+use crate::mpq::header::MpqHeader;
+
+/// Сборка нового MPQ-архива в памяти.
+///
+/// Файлы сжимаются посекторно zlib и не шифруются; `(listfile)` добавляется автоматически.
+/// Для замены файлов в существующем архиве — [`MpqArchive::import`](crate::mpq::archive::MpqArchive::import).
+#[derive(Debug, Clone, Default)]
+pub struct MpqWriter {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl MpqWriter {
+    /// Сектор 4096 байт — как у архивов Warcraft III.
+    const SECTOR_SIZE_SHIFT: u16 = 3;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавить файл; файл с тем же именем (без учёта регистра и вида слэша) заменяется.
+    pub fn add_file(&mut self, name: &str, data: Vec<u8>) -> &mut Self {
+        let name = normalize_name(name);
+        self.files
+            .retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        self.files.push((name, data));
+        self
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BlpError> {
+        let mut listfile = String::new();
+        for (name, _) in &self.files {
+            listfile.push_str(name);
+            listfile.push_str("\r\n");
+        }
+
+        let count = self.files.len() + 1;
+        let mut hashes = vec![HashEntry::empty(); (count * 2).next_power_of_two().max(16)];
+        let mut blocks = Vec::with_capacity(count);
+        let mut out = vec![0u8; MpqHeader::SIZE];
+        let sector_size = 512 << Self::SECTOR_SIZE_SHIFT;
+
+        let files = self
+            .files
+            .iter()
+            .map(|(n, d)| (n.as_str(), d.as_slice()))
+            .chain([("(listfile)", listfile.as_bytes())]);
+        for (name, data) in files {
+            blocks.push(append_file(&mut out, 0, data, sector_size)?);
+            set_entry(&mut hashes, name, blocks.len() as u32 - 1)?;
+        }
+
+        finish_archive(&mut out, 0, Self::SECTOR_SIZE_SHIFT, &hashes, &blocks)?;
+        Ok(out)
+    }
+}
+
+/// Имена внутри MPQ пишутся через `\`.
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.replace('/', "\\")
+}
+
+/// Дописать упакованный файл в конец `out`; смещение в записи — от `archive_offset`.
+pub(crate) fn append_file(out: &mut Vec<u8>, archive_offset: usize, data: &[u8], sector_size: usize) -> Result<BlockEntry, BlpError> {
+    let file_size = to_u32(data.len())?;
+    let (packed, flags) = pack_file(data, sector_size)?;
+    let entry = BlockEntry {
+        offset: to_u32(out.len() - archive_offset)?,
+        compressed_size: to_u32(packed.len())?,
+        file_size,
+        flags,
+    };
+    out.extend_from_slice(&packed);
+    Ok(entry)
+}
+
+/// Направить имя на блок: существующая запись переиспользуется, иначе занимается свободный слот.
+pub(crate) fn set_entry(hashes: &mut [HashEntry], name: &str, block_index: u32) -> Result<(), BlpError> {
+    if let Some(slot) = find_entry(hashes, name) {
+        hashes[slot].block_index = block_index;
+        return Ok(());
+    }
+    let slot = free_slot(hashes, name).ok_or_else(|| BlpError::new(ErrorKind::LimitExceeded, "error-mpq-hash-table-full").with_arg("size", hashes.len() as u64))?;
+    hashes[slot] = HashEntry::new(name, block_index);
+    Ok(())
+}
+
+/// Дописать зашифрованные таблицы и записать заголовок v1 по `archive_offset`.
+pub(crate) fn finish_archive(out: &mut Vec<u8>, archive_offset: usize, sector_size_shift: u16, hashes: &[HashEntry], blocks: &[BlockEntry]) -> Result<(), BlpError> {
+    let hash_table_pos = to_u32(out.len() - archive_offset)?;
+    let mut table = Vec::with_capacity(hashes.len() * HashEntry::SIZE);
+    for e in hashes {
+        e.write(&mut table);
+    }
+    encrypt(&mut table, hash_string("(hash table)", HASH_FILE_KEY));
+    out.extend_from_slice(&table);
+
+    let block_table_pos = to_u32(out.len() - archive_offset)?;
+    let mut table = Vec::with_capacity(blocks.len() * BlockEntry::SIZE);
+    for e in blocks {
+        e.write(&mut table);
+    }
+    encrypt(&mut table, hash_string("(block table)", HASH_FILE_KEY));
+    out.extend_from_slice(&table);
+
+    let header = MpqHeader {
+        archive_offset: archive_offset as u64,
+        archive_size: to_u32(out.len() - archive_offset)?,
+        format_version: 0,
+        sector_size_shift,
+        hash_table_pos,
+        block_table_pos,
+        hash_table_size: hashes.len() as u32,
+        block_table_size: blocks.len() as u32,
+    };
+    out[archive_offset..archive_offset + MpqHeader::SIZE].copy_from_slice(&header.to_bytes());
+    Ok(())
+}
+
+/// MPQ v1 адресует 32 битами.
+fn to_u32(n: usize) -> Result<u32, BlpError> {
+    u32::try_from(n).map_err(|_| BlpError::new(ErrorKind::LimitExceeded, "error-mpq-too-large").with_arg("size", n as u64))
+}
@@ -7,11 +7,27 @@ use std::path::PathBuf;
 use crate::ui::viewer::run_native::run_native;
 #[cfg(feature = "cli")]
 use {
+    crate::cli::command::convert_version::convert_version,
+    crate::cli::command::holes::holes,
+    crate::cli::command::jpeg_inspect::jpeg_inspect,
+    crate::cli::command::optimize::optimize,
+    crate::cli::command::orientation_arg::{FlipArg, RotateArg},
+    crate::cli::command::quality::quality,
+    crate::cli::command::repair::repair,
+    crate::cli::command::report_format::ReportFormat,
+    crate::cli::command::scan::scan,
+    crate::cli::command::target_version::TargetVersion,
     crate::cli::command::to_blp::to_blp,
     crate::cli::command::to_png::to_png,
+    crate::cli::command::transform::transform,
+    crate::cli::exit_code::ExitCode,
     crate::core::image::MAX_MIPS,
     crate::error::error::BlpError,
+    crate::error::error_kind::ErrorKind as BlpErrorKind,
+    crate::i18n::detect_os_lang::detect_os_lang,
+    crate::i18n::lng_list::LngList,
     clap::{Parser, Subcommand, error::ErrorKind},
+    fluent_templates::LanguageIdentifier,
 };
 
 // ===== enforce: 'ui' always together with 'cli' =====
@@ -25,9 +41,10 @@ compile_error!("Feature 'ui' requires 'cli'. Use either `--features \"cli\"` or
 enum Command {
     /// Convert an image into BLP format
     ToBlp {
-        /// Input file (e.g. PNG)
+        /// Input file (e.g. PNG), or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
         input: PathBuf,
-        /// Optional output path. If not specified, the extension will be replaced with .blp
+        /// Optional output path. If not specified, the extension will be replaced with .blp.
+        /// `map.w3x:war3mapImported\foo.blp` imports the result into the archive.
         output: Option<PathBuf>,
 
         /// Explicit mipmap levels (1–16 numbers).
@@ -41,18 +58,141 @@ enum Command {
         /// JPEG quality (1–100).
         #[arg(short = 'q', long = "quality", default_value_t = 100, value_parser = clap::value_parser!(u8).range(1..=100))]
         quality: u8,
+
+        /// When the input is a JPEG BLP, copy its mip payloads as is and re-encode the rest at its estimated quality instead of `--quality`.
+        #[arg(long = "keep-quality")]
+        keep_quality: bool,
+
+        /// Rebuild missing and disabled mips from the level above, so the chain always goes down to 1×1.
+        #[arg(long = "fill-mips")]
+        fill_mips: bool,
+
+        /// Author recorded in the BLP metadata chunk.
+        #[arg(long = "author", value_name = "NAME")]
+        author: Option<String>,
     },
     /// Convert a BLP texture into PNG format
     ToPng {
-        /// Input file (e.g. BLP)
+        /// Input file (e.g. BLP), or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
         input: PathBuf,
         /// Optional output path. If not specified, the extension will be replaced with .png
         output: Option<PathBuf>,
     },
+    /// Rewrite a JPEG BLP into the standard layout: per-mip SOF sizes, common shared header, no gaps
+    Repair {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+        /// Optional output path (also `map.w3x:path`). Defaults to `<input>.repaired.blp`.
+        output: Option<PathBuf>,
+
+        /// Recover damaged or "protected" files: clamp or drop bad mip entries, trim junk, pad truncated mips,
+        /// drop undecodable levels and fix the mipmap flag. Works for JPEG and paletted textures.
+        #[arg(long = "salvage")]
+        salvage: bool,
+    },
+    /// Rewrite a BLP1 container as BLP2 or back; palette, JPEG header and mip data are copied as is
+    ConvertVersion {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+        /// Optional output path (also `map.w3x:path`). Defaults to `<input>.blp1.blp` / `<input>.blp2.blp`.
+        output: Option<PathBuf>,
+
+        /// Target container version.
+        #[arg(long = "to", value_enum)]
+        to: TargetVersion,
+    },
+    /// Estimate the JPEG quality (IJG scale) a BLP was saved with, from its quantization tables
+    Quality {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+
+        /// Also print the quantization tables (natural order, 8×8).
+        #[arg(long = "tables")]
+        tables: bool,
+    },
+    /// Re-entropy-code a JPEG BLP with optimized Huffman tables and drop holes; pixels stay bit-exact
+    Optimize {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+        /// Optional output path (also `map.w3x:path`). Defaults to `<input>.optimized.blp`.
+        output: Option<PathBuf>,
+    },
+    /// Rotate or flip a JPEG BLP losslessly (DCT coefficients); mips with partial MCUs are re-encoded
+    #[command(group(clap::ArgGroup::new("op").required(true).multiple(true).args(["rotate", "flip"])))]
+    Transform {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+        /// Optional output path (also `map.w3x:path`). Defaults to `<input>.transformed.blp`.
+        output: Option<PathBuf>,
+
+        /// Rotate clockwise by 90, 180 or 270 degrees.
+        #[arg(long = "rotate", value_enum)]
+        rotate: Option<RotateArg>,
+
+        /// Flip after rotating: `h` (left–right) or `v` (top–bottom).
+        #[arg(long = "flip", value_enum)]
+        flip: Option<FlipArg>,
+
+        /// JPEG quality (1–100) for mips that have to be re-encoded.
+        #[arg(short = 'q', long = "quality", default_value_t = 100, value_parser = clap::value_parser!(u8).range(1..=100))]
+        quality: u8,
+    },
+    /// Group BLP files by header "passport" (version, type, alpha bits, extra, mips, JPEG header length)
+    Scan {
+        /// Directory to scan recursively, or an MPQ archive (files from its (listfile))
+        input: PathBuf,
+
+        /// Report format.
+        #[arg(long = "format", value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Write the report to this file instead of stdout.
+        #[arg(short = 'o', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+
+        /// Copy one sample per passport into DIR/<passport>/ (with origin.txt).
+        #[arg(long = "samples", value_name = "DIR")]
+        samples: Option<PathBuf>,
+
+        /// Example files listed per passport.
+        #[arg(long = "examples", default_value_t = 3)]
+        examples: usize,
+    },
+    /// List bytes not covered by the header or any mip ("holes"), dump them or save a copy without them
+    Holes {
+        /// Input BLP, or a file inside an MPQ archive: `War3.mpq:path\in\archive.blp`
+        input: PathBuf,
+
+        /// Report format.
+        #[arg(long = "format", value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Write each hole into DIR/<name>.hole-<offset>.bin.
+        #[arg(long = "extract", value_name = "DIR")]
+        extract: Option<PathBuf>,
+
+        /// Save a copy without holes (mip data is copied byte for byte).
+        #[arg(long = "strip", value_name = "FILE")]
+        strip: Option<PathBuf>,
+    },
+    /// Analyze JPEG headers of BLP mips: shared header, per-mip SOF dimensions, deviations from mip 0
+    JpegInspect {
+        /// BLP files (also `archive.mpq:path`) or directories scanned recursively
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Report format.
+        #[arg(long = "format", value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Write the report to this file instead of stdout.
+        #[arg(short = 'o', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
 }
 
 /// One unified CLI struct for both builds.
-/// - In CLI-only builds, [PATH] triggers a sanity decode (exit 0/3); or use a subcommand.
+/// - In CLI-only builds, [PATH] triggers a header probe (exit 0, or an error exit code); or use a subcommand.
 /// - In UI+CLI builds, [PATH] launches native GUI; or use a subcommand.
 /// Command metadata (about/long_about/usage) is specialized with cfg_attr.
 #[cfg(feature = "cli")]
@@ -61,11 +201,20 @@ enum Command {
 #[cfg_attr(all(feature = "cli", feature = "ui"), command(name = "blp", version, about = "BLP ↔ PNG converter and simple viewer for Warcraft III textures", long_about = "blp is a command-line utility for converting Warcraft III textures between BLP and PNG formats. It can also launch a native GUI viewer.", override_usage = "blp [PATH]\nblp <COMMAND>"))]
 struct Cli {
     /// PATH behavior:
-    /// - CLI-only: try to decode this file; success = exit 0, failure = exit 3
+    /// - CLI-only: probe this file's header; success = exit 0, failure = exit code by error kind
     /// - UI+CLI: open native GUI with this file (used by “Open With…”)
     #[arg(value_name = "PATH")]
     path: Option<PathBuf>,
 
+    /// Language for error messages (en, ru, uk, zh, tc; also locale tags like ru_RU.UTF-8).
+    /// Default: detected from the OS locale.
+    #[arg(long = "lang", value_name = "LANG", global = true)]
+    lang: Option<String>,
+
+    /// Print errors to stderr as one JSON object (kind, key, message, args, causes) instead of text.
+    #[arg(long = "json", global = true)]
+    json: bool,
+
     /// Optional subcommand. When present it takes precedence over PATH.
     #[command(subcommand)]
     command: Option<Command>,
@@ -73,10 +222,42 @@ struct Cli {
 
 // ======================= Helpers =======================
 
+/// `--lang`, иначе язык ОС.
+#[cfg(feature = "cli")]
+fn cli_lang(lang: Option<&str>) -> LanguageIdentifier {
+    lang.map(LngList::from_locale)
+        .unwrap_or_else(detect_os_lang)
+        .id()
+}
+
+/// Как печатать ошибки: язык и `--json`.
+#[cfg(feature = "cli")]
+struct ErrorReport {
+    lang: LanguageIdentifier,
+    json: bool,
+}
+
+#[cfg(feature = "cli")]
+impl ErrorReport {
+    /// Print the error tree to stderr (localized text or JSON) and exit with the code for its kind.
+    fn exit_with(&self, e: &BlpError, code: ExitCode) -> ! {
+        if self.json {
+            eprintln!("{}", e.to_json(&self.lang));
+        } else {
+            eprint!("{}", e.localized(&self.lang));
+        }
+        code.exit()
+    }
+
+    fn exit(&self, e: &BlpError) -> ! {
+        self.exit_with(e, ExitCode::from_kind(e.kind))
+    }
+}
+
 #[cfg(feature = "cli")]
 fn run_cli_command(cmd: Command) -> Result<(), BlpError> {
     match cmd {
-        Command::ToBlp { input, output, mips, mips_limit, quality } => {
+        Command::ToBlp { input, output, mips, mips_limit, quality, keep_quality, fill_mips, author } => {
             let mut mip_visible = vec![true; MAX_MIPS];
 
             if !mips.is_empty() {
@@ -93,37 +274,71 @@ fn run_cli_command(cmd: Command) -> Result<(), BlpError> {
                     mip_visible[i] = false;
                 }
             }
-            to_blp(&input, output.as_ref(), quality, &mip_visible)
+            to_blp(&input, output.as_ref(), quality, keep_quality, fill_mips, &mip_visible, author.as_deref())
         }
         Command::ToPng { input, output } => to_png(&input, output.as_ref()),
+        Command::Scan { input, format, out, samples, examples } => scan(&input, format, out.as_ref(), samples.as_ref(), examples),
+        Command::Repair { input, output, salvage } => repair(&input, output.as_ref(), salvage),
+        Command::ConvertVersion { input, output, to } => convert_version(&input, output.as_ref(), to),
+        Command::Quality { input, tables } => quality(&input, tables),
+        Command::Optimize { input, output } => optimize(&input, output.as_ref()),
+        Command::Transform { input, output, rotate, flip, quality } => transform(&input, output.as_ref(), rotate, flip, quality),
+        Command::Holes { input, format, extract, strip } => holes(&input, format, extract.as_ref(), strip.as_ref()),
+        Command::JpegInspect { inputs, format, out } => jpeg_inspect(&inputs, format, out.as_ref()),
+    }
+}
+
+/// CLI-only: metadata chunk of a valid file, one `key: value` per line (nothing if there is none).
+#[cfg(all(feature = "cli", not(feature = "ui")))]
+fn print_metadata(meta: Result<Option<crate::core::metadata::BlpMetadata>, BlpError>) {
+    for (k, v) in meta
+        .ok()
+        .flatten()
+        .map(|m| m.entries)
+        .unwrap_or_default()
+    {
+        println!("{k}: {v}");
     }
 }
 
 /// CLI-only: probe if the file is a valid BLP.
-/// Success → exit 0; failure → exit 3.
+/// Success → exit 0; failure → exit code by error kind (see `ExitCode`).
 /// This function always terminates the process.
 #[cfg(all(feature = "cli", not(feature = "ui")))]
-fn sanity_decode_or_exit(path: PathBuf) -> ! {
+fn sanity_decode_or_exit(path: PathBuf, report: &ErrorReport) -> ! {
     use crate::core::image::ImageBlp;
-    use std::fs;
+    use crate::ext::path::ensure_readable::EnsureReadable;
+    use crate::mpq::archive_path::ArchivePath;
+    use std::fs::File;
 
-    // Read file
-    let data = match fs::read(&path) {
-        Ok(b) => b,
-        Err(e) => {
-            eprintln!("read error: {e}");
-            std::process::exit(3);
+    // File inside an MPQ archive: unpack it, then probe the buffer
+    if ArchivePath::parse(&path).is_some() {
+        match path.read_all() {
+            Ok(buf) => match ImageBlp::probe(&buf) {
+                Ok(_) => print_metadata(ImageBlp::read_metadata(std::io::Cursor::new(&buf))),
+                Err(e) => report.exit(&e),
+            },
+            Err(e) => report.exit(&e),
         }
+        ExitCode::Ok.exit()
+    }
+
+    // Open file (only the header is read)
+    let file = match File::open(&path) {
+        Ok(f) => f,
+        Err(e) => report.exit(&BlpError::from(e)),
     };
 
-    // Minimal probe: try to parse the BLP header
-    if let Err(e) = ImageBlp::from_buf(&data) {
-        eprintln!("{e}");
-        std::process::exit(3);
+    // Minimal probe: header only, no pixel decoding
+    let mut file = std::io::BufReader::new(file);
+    if let Err(e) = ImageBlp::probe_reader(&mut file) {
+        report.exit(&e);
     }
+    // Metadata chunk, if any: a few more bytes after the header
+    print_metadata(std::io::Seek::rewind(&mut file).map_err(BlpError::from).and_then(|_| ImageBlp::read_metadata(file)));
 
     // If we reach here → file is parseable
-    std::process::exit(0);
+    ExitCode::Ok.exit()
 }
 
 // ======================= Entry point ========================
@@ -133,7 +348,7 @@ fn sanity_decode_or_exit(path: PathBuf) -> ! {
 pub fn run() -> Result<(), BlpError> {
     // Unified CLI parsing:
     // - Help/Version → print and return Ok(())
-    // - Other errors → print and exit with code 2
+    // - Other errors → print (or JSON with --json) and exit with ExitCode::Usage
     let Some(cli) = (match Cli::try_parse() {
         Ok(cli) => Some(cli),
         Err(e) => {
@@ -143,8 +358,19 @@ pub fn run() -> Result<(), BlpError> {
                     None
                 }
                 _ => {
+                    // Parsing failed, so --json/--lang are looked up in the raw arguments.
+                    let args: Vec<String> = std::env::args().collect();
+                    if args.iter().any(|a| a == "--json") {
+                        let lang = args
+                            .iter()
+                            .position(|a| a == "--lang")
+                            .and_then(|i| args.get(i + 1));
+                        let report = ErrorReport { lang: cli_lang(lang.map(String::as_str)), json: true };
+                        let err = BlpError::new(BlpErrorKind::InvalidArgument, "error-cli-usage").with_arg("msg", e.to_string().trim_end());
+                        report.exit_with(&err, ExitCode::Usage);
+                    }
                     let _ = e.print();
-                    std::process::exit(e.exit_code()); // usually 2
+                    ExitCode::Usage.exit();
                 }
             }
         }
@@ -152,10 +378,16 @@ pub fn run() -> Result<(), BlpError> {
         return Ok(());
     };
 
+    let report = ErrorReport { lang: cli_lang(cli.lang.as_deref()), json: cli.json };
+
     // ===== UI + CLI build =====
     #[cfg(all(feature = "cli", feature = "ui"))]
     {
-        return if let Some(cmd) = cli.command { run_cli_command(cmd) } else { run_native(cli.path) };
+        return if let Some(cmd) = cli.command {
+            run_cli_command(cmd).or_else(|e| report.exit(&e))
+        } else {
+            run_native(cli.path).or_else(|e| report.exit(&e))
+        };
     }
 
     // ===== CLI-only build =====
@@ -164,17 +396,14 @@ pub fn run() -> Result<(), BlpError> {
         match (cli.path, cli.command) {
             // Single PATH → sanity decode (process exits inside helper)
             (Some(p), None) => {
-                sanity_decode_or_exit(p);
+                sanity_decode_or_exit(p, &report);
             }
             // Subcommand without PATH
-            (None, Some(cmd)) => run_cli_command(cmd),
+            (None, Some(cmd)) => run_cli_command(cmd).or_else(|e| report.exit(&e)),
             // Both PATH and subcommand → prefer subcommand (ignore PATH)
-            (Some(_), Some(cmd)) => run_cli_command(cmd),
-            // Neither PATH nor subcommand → print error and exit with code 2
-            (None, None) => {
-                eprintln!("error: a PATH or a subcommand is required\n\nUse --help for more information.");
-                std::process::exit(2);
-            }
+            (Some(_), Some(cmd)) => run_cli_command(cmd).or_else(|e| report.exit(&e)),
+            // Neither PATH nor subcommand → usage error
+            (None, None) => report.exit(&BlpError::new(BlpErrorKind::InvalidArgument, "error-cli-path-required")),
         }
     }
 }
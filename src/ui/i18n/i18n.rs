@@ -1,20 +1,10 @@
 use crate::ui::viewer::app::App;
 use fluent_templates::fluent_bundle::{FluentArgs, FluentValue};
-use fluent_templates::{Loader, static_loader};
+use crate::i18n::locales::LOCALES;
+use fluent_templates::Loader;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-// Сгенерированный при компиляции лоадер.
-// Он сам найдёт assets/locales/{lang}/*.ftl, вшьёт и даст API lookup/lookup_with_args.
-static_loader! {
-    pub static LOCALES = {
-        locales: "./assets/locales",
-        fallback_language: "en",
-        // можно тонко настроить бандл:
-        customise: |bundle| bundle.set_use_isolating(false),
-    };
-}
-
 // Шорткаты перевода
 impl App {
     #[inline]
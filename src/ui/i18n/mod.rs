@@ -1,5 +1,3 @@
-pub mod detect_os_lang;
 pub mod i18n;
-pub mod lng_list;
 pub mod prefs;
 pub mod shortcut;
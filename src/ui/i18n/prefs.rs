@@ -1,5 +1,5 @@
-use crate::ui::i18n::detect_os_lang::detect_os_lang;
-use crate::ui::i18n::lng_list::LngList;
+use crate::i18n::detect_os_lang::detect_os_lang;
+use crate::i18n::lng_list::LngList;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Serialize, Deserialize)]
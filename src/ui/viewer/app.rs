@@ -1,9 +1,10 @@
 use crate::core::image::{ImageBlp, MAX_MIPS};
 use crate::error::error::BlpError;
 use crate::ui::fonts::install_fonts;
-use crate::ui::i18n::lng_list::LngList;
+use crate::i18n::lng_list::LngList;
 use crate::ui::i18n::prefs::load_prefs;
 use crate::ui::viewer::layout::file_saver::export_quality::export_quality_load;
+use crate::ui::viewer::layout::file_saver::fill_mips::fill_mips_load;
 use crate::ui::viewer::layout::file_saver::save_same_dir::save_same_dir_load;
 use eframe::egui::{Context, RawInput, TextureHandle};
 use std::path::PathBuf;
@@ -23,6 +24,7 @@ pub struct App {
     pub mip_visible: [bool; MAX_MIPS], // init: [true; 16]
     pub save_same_dir: bool,
     pub export_quality: u8,
+    pub fill_mips: bool,
 }
 
 impl App {
@@ -52,6 +54,7 @@ impl App {
             mip_visible: [true; MAX_MIPS],
             save_same_dir: save_same_dir_load(),
             export_quality: export_quality_load(),
+            fill_mips: fill_mips_load(),
         }
     }
 }
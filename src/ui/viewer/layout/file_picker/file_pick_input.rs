@@ -1,25 +1,26 @@
+use crate::core::decode::decode_options::DecodeOptions;
 use crate::core::image::ImageBlp;
 use crate::error::error::BlpError;
 use crate::ext::path::ensure_readable::EnsureReadable;
+use image::RgbaImage;
 use std::path::PathBuf;
 
 pub enum FilePickInput {
     Path(PathBuf),
-    Bytes(Vec<u8>),
+    Rgba(RgbaImage),
 }
 
 impl FilePickInput {
-    fn into_bytes(self) -> Result<Vec<u8>, BlpError> {
+    pub fn decode(self) -> Result<ImageBlp, BlpError> {
         match self {
-            FilePickInput::Path(path) => path.as_path().read_all(),
-            FilePickInput::Bytes(data) => Ok(data),
+            FilePickInput::Path(path) => {
+                let data = path.as_path().read_all()?;
+                let mut img = ImageBlp::from_buf(&data)?;
+                // исходные потоки — чтобы «Сохранить как BLP» не перекодировал нетронутые мипы
+                img.decode_with(&data, &[], &DecodeOptions { keep_jpeg: true, ..DecodeOptions::with_fallback() })?;
+                Ok(img)
+            }
+            FilePickInput::Rgba(img) => ImageBlp::from_rgba(img, &[]),
         }
     }
-
-    pub fn decode(self) -> Result<ImageBlp, BlpError> {
-        let data = self.into_bytes()?;
-        let mut img = ImageBlp::from_buf(&data)?;
-        img.decode(&data, &[])?;
-        Ok(img)
-    }
 }
@@ -1,5 +1,6 @@
 use crate::ui::viewer::layout::file_picker::file_pick_input::FilePickInput;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use crate::ui::viewer::app::App;
 
 impl App {
@@ -17,18 +18,16 @@ impl App {
 
         // ---------- bitmap через arboard ----------
         use arboard::Clipboard;
-        use image::{DynamicImage, ImageFormat};
-        use std::io::Cursor;
         use std::sync::mpsc;
         use std::thread;
 
         // init буфера обмена
-        let mut cb = Clipboard::new().map_err(|e| BlpError::new("error-clipboard-init-failed").push_std(e))?;
+        let mut cb = Clipboard::new().map_err(|e| BlpError::new(ErrorKind::Platform, "error-clipboard-init-failed").push_std(e))?;
 
         // получаем RGBA-данные из буфера
         let img = cb
             .get_image()
-            .map_err(|e| BlpError::new("error-clipboard-no-image").push_std(e))?;
+            .map_err(|e| BlpError::new(ErrorKind::Platform, "error-clipboard-no-image").push_std(e))?;
 
         let w = img.width as u32;
         let h = img.height as u32;
@@ -43,17 +42,10 @@ impl App {
 
         // собираем RgbaImage (проверяем валидность буфера)
         let rgba_img = image::RgbaImage::from_raw(w, h, rgba).ok_or_else(|| {
-            BlpError::new("error-clipboard-invalid-image-buffer")
+            BlpError::new(ErrorKind::InvalidImage, "error-clipboard-invalid-buffer")
                 .with_arg("width", w)
                 .with_arg("height", h)
         })?;
-        let dyn_img = DynamicImage::ImageRgba8(rgba_img);
-
-        // кодируем во временный PNG (твой декодер ест png/jpg/…)
-        let mut buf = Vec::new();
-        dyn_img
-            .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
-            .map_err(|e| BlpError::new("error-clipboard-encode-png-failed").push_std(e))?;
 
         // Сброс состояния + запуск декодера
         self.picked_file = None;
@@ -66,7 +58,7 @@ impl App {
         self.loading = true;
 
         thread::spawn(move || {
-            let res = FilePickInput::Bytes(buf).decode();
+            let res = FilePickInput::Rgba(rgba_img).decode();
             let _ = tx.send(res);
         });
 
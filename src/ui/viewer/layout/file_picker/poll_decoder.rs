@@ -1,4 +1,5 @@
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use crate::core::image::MAX_MIPS;
 use crate::ui::viewer::app::App;
 use eframe::egui::{ColorImage, Context, TextureOptions, vec2};
@@ -50,7 +51,7 @@ impl App {
             // === ошибка из воркера (AppErr) ===
             Ok(Err(err)) => {
                 // Вкладываем как причину в "внешний" ключ, если нужен контекст
-                self.error = Some(BlpError::new("error-poll-decoder").push_blp(err));
+                self.error = Some(err.ctx("error-poll-decoder"));
                 self.blp = None;
                 self.loading = false;
                 // rx дропаем
@@ -64,7 +65,7 @@ impl App {
 
             // === воркер умер — фиксируем явную ошибку ===
             Err(TryRecvError::Disconnected) => {
-                self.error = Some(BlpError::new("blp.decode-thread-disconnected").with_arg("msg", "decoder thread disconnected"));
+                self.error = Some(BlpError::new(ErrorKind::Internal, "error-decode-thread-disconnected").with_arg("msg", "decoder thread disconnected"));
                 self.blp = None;
                 self.loading = false;
                 // rx дропаем
@@ -1,7 +1,8 @@
 use crate::ext::path::to_abs_string_with_macros::PathMacrosExt;
+use crate::mpq::archive_path::ArchivePath;
 use crate::ui::viewer::app::App;
 use crate::ui::viewer::layout::file_saver::last_safe_dir::{last_save_dir_load, last_save_dir_save};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Предпросмотр того, куда именно полетит файл при сохранении.
 pub enum SavePreview {
@@ -31,6 +32,12 @@ fn ensure_ext(name: &str, ext: &str) -> String {
     }
 }
 
+/// Папка исходника; для файла из архива (`War3.mpq:…`) — папка архива.
+fn source_dir(src: &Path) -> Option<PathBuf> {
+    let file = ArchivePath::parse(src).map_or_else(|| src.to_path_buf(), |a| a.archive);
+    file.parent().map(Path::to_path_buf)
+}
+
 /// Если у Path нет нужного расширения — добавляем (только если пусто).
 fn ensure_path_has_ext(mut p: PathBuf, ext: &str) -> PathBuf {
     let ext = trim_dot(ext);
@@ -51,7 +58,7 @@ impl App {
 
         if self.save_same_dir && self.picked_file.is_some() {
             if let Some(src) = self.picked_file.as_ref() {
-                if let Some(parent) = src.parent() {
+                if let Some(parent) = source_dir(src) {
                     return SavePreview::Direct(parent.join(&name));
                 }
             }
@@ -62,7 +69,7 @@ impl App {
         let start_dir = last_save_dir_load().or_else(|| {
             self.picked_file
                 .as_ref()
-                .and_then(|p| p.parent().map(|pp| pp.to_path_buf()))
+                .and_then(|p| source_dir(p))
         });
 
         SavePreview::Dialog { start_dir, name }
@@ -92,7 +99,7 @@ impl App {
         // 1) «рядом», если можем
         if self.save_same_dir && self.picked_file.is_some() {
             if let Some(src) = self.picked_file.as_ref() {
-                if let Some(parent) = src.parent() {
+                if let Some(parent) = source_dir(src) {
                     let file_name = ensure_ext(default_name, ext);
                     return Some(parent.join(file_name));
                 }
@@ -107,7 +114,7 @@ impl App {
         if let Some(dir) = last_save_dir_load().or_else(|| {
             self.picked_file
                 .as_ref()
-                .and_then(|p| p.parent().map(|pp| pp.to_path_buf()))
+                .and_then(|p| source_dir(p))
         }) {
             dlg = dlg.set_directory(dir);
         }
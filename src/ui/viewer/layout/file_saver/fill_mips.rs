@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+const APP: &str = env!("CARGO_PKG_NAME");
+const CFG: Option<&str> = Some(stringify!(FillMips));
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct FillMipsPrefs {
+    pub val: bool,
+}
+
+pub fn fill_mips_load() -> bool {
+    confy::load::<FillMipsPrefs>(APP, CFG)
+        .unwrap_or_default()
+        .val
+}
+
+pub fn fill_mips_save(val: bool) -> Result<(), confy::ConfyError> {
+    confy::store(APP, CFG, FillMipsPrefs { val })
+}
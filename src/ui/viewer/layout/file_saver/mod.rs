@@ -1,4 +1,5 @@
 pub mod export_quality;
 mod file_saver;
+pub mod fill_mips;
 mod last_safe_dir;
 pub mod save_same_dir;
@@ -1,13 +1,20 @@
+use crate::core::encode::blp::EncodeOptions;
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use crate::flargs;
+use crate::mpq::archive_path::ArchivePath;
 use crate::ui::viewer::app::App;
 use crate::ui::viewer::layout::file_saver::export_quality::export_quality_save;
+use crate::ui::viewer::layout::file_saver::fill_mips::fill_mips_save;
 use crate::ui::viewer::layout::file_saver::save_same_dir::save_same_dir_save;
 use eframe::egui::{Button, Context, CursorIcon, Frame, Margin, RichText, ScrollArea, Sense, SidePanel, Slider, vec2};
+use std::path::PathBuf;
 
 impl App {
     fn default_names(&self) -> (String, String) {
         if let Some(p) = self.picked_file.as_ref() {
+            // для `War3.mpq:Textures\Foo.blp` — имя внутри архива
+            let p = ArchivePath::parse(p).map_or_else(|| p.clone(), |a| PathBuf::from(a.file_name()));
             if let Some(stem) = p
                 .file_stem()
                 .map(|s| s.to_string_lossy().into_owned())
@@ -24,7 +31,7 @@ impl App {
     where
         F: FnOnce(&crate::core::image::ImageBlp) -> Result<(), BlpError>,
     {
-        let res = if let Some(img) = self.blp.as_ref() { f(img) } else { Err(BlpError::new("error-save-no-image")) };
+        let res = if let Some(img) = self.blp.as_ref() { f(img) } else { Err(BlpError::new(ErrorKind::InvalidArgument, "error-save-no-image")) };
         self.error = res.err();
     }
 
@@ -86,9 +93,9 @@ impl App {
                                     .clicked()
                                 {
                                     if let Some(path) = self.pick_save_path(&def_blp, "blp", self.tr("blp-texture")) {
-                                        let export_quality = self.export_quality;
+                                        let opts = EncodeOptions { quality: self.export_quality, reuse_source: true, fill_mips: self.fill_mips, ..Default::default() };
                                         let mip_visible = self.mip_visible;
-                                        self.run_export(|img| img.export_blp(&path, export_quality, &mip_visible));
+                                        self.run_export(|img| img.export_blp_with(&path, &opts, &mip_visible));
                                     }
                                 }
 
@@ -131,6 +138,22 @@ impl App {
                             }
                         });
 
+                        // ------- Достроить мипы до 1×1 -------
+                        let (label_key, hint_key) = if self.fill_mips {
+                            ("fill-mips-on", "fill-mips-hint-on")
+                        } else {
+                            ("fill-mips-off", "fill-mips-hint-off")
+                        };
+                        if ui
+                            .add_sized([ui.available_width(), 0.0], Button::new(self.tr(label_key)))
+                            .on_hover_text(self.tr(hint_key))
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            self.fill_mips = !self.fill_mips;
+                            let _ = fill_mips_save(self.fill_mips);
+                        }
+
                         let _ = ui.allocate_exact_size(vec2(ui.available_width(), 0.0), Sense::hover());
                     });
             });
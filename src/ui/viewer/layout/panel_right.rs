@@ -1,4 +1,5 @@
 use crate::core::image::MAX_MIPS;
+use crate::flargs;
 use crate::ui::viewer::app::App;
 use eframe::egui::{Button, Context, CursorIcon, Frame, Margin, Response, RichText, ScrollArea, Sense, SidePanel, TextStyle, Ui, vec2};
 
@@ -60,6 +61,52 @@ impl App {
                                         self.mip_visible.fill(false);
                                     }
                                 });
+
+                                // Дыры: байты вне заголовка и мипов (маркеры, чужие данные)
+                                if let Some(b) = self
+                                    .blp
+                                    .as_ref()
+                                    .filter(|b| b.holes > 0)
+                                {
+                                    let ranges = b
+                                        .hole_ranges
+                                        .iter()
+                                        .map(|h| format!("@{} +{}", h.offset, h.length))
+                                        .collect::<Vec<_>>()
+                                        .join("\n");
+                                    let summary = self.tr_args("holes-summary", &flargs!(count = b.hole_ranges.len(), bytes = b.holes));
+                                    ui.label(RichText::new(summary).text_style(TextStyle::Small))
+                                        .on_hover_text(format!("{}\n\n{ranges}", self.tr("hint-holes")));
+                                }
+
+                                // Качество JPEG по таблицам DQT
+                                if let Some(e) = self
+                                    .blp
+                                    .as_ref()
+                                    .and_then(|b| b.quality_estimate.as_ref())
+                                {
+                                    let q = if e.exact { e.quality.to_string() } else { format!("≈{}", e.quality) };
+                                    let summary = self.tr_args("jpeg-quality", &flargs!(quality = q));
+                                    ui.label(RichText::new(summary).text_style(TextStyle::Small))
+                                        .on_hover_text(self.tr("hint-jpeg-quality"));
+                                }
+
+                                // Метаданные, записанные нашим кодировщиком
+                                if let Some(m) = self
+                                    .blp
+                                    .as_ref()
+                                    .and_then(|b| b.metadata.as_ref())
+                                {
+                                    let lines = m
+                                        .entries
+                                        .iter()
+                                        .map(|(k, v)| format!("{k}: {v}"))
+                                        .collect::<Vec<_>>()
+                                        .join("\n");
+                                    let summary = self.tr_args("metadata-summary", &flargs!(count = m.entries.len()));
+                                    ui.label(RichText::new(summary).text_style(TextStyle::Small))
+                                        .on_hover_text(format!("{}\n\n{lines}", self.tr("hint-metadata")));
+                                }
                             });
                         });
 
@@ -1,4 +1,5 @@
 use crate::error::error::BlpError;
+use crate::error::error_kind::ErrorKind;
 use crate::ui::viewer::app::App;
 use eframe::egui::{IconData, ViewportBuilder, vec2};
 use eframe::{NativeOptions, Renderer};
@@ -53,6 +54,6 @@ pub fn run_native(path: Option<PathBuf>) -> Result<(), BlpError> {
     )
     .map_err(|err| {
         report_error(&format!("Failed to launch UI: {}", err));
-        BlpError::new("ui-run-native").with_arg("msg", err.to_string())
+        BlpError::new(ErrorKind::Platform, "error-ui-run-native").with_arg("msg", err.to_string())
     })
 }
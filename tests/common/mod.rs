@@ -0,0 +1,19 @@
+//! Общие помощники интеграционных тестов; каждый тест подключает их через `mod common;`.
+#![allow(dead_code)]
+
+use blp::core::image::ImageBlp;
+use std::fs;
+use std::path::PathBuf;
+
+/// Файл из `test-data/`.
+pub fn test_data(name: &str) -> Vec<u8> {
+    fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data").join(name)).unwrap()
+}
+
+/// Данные мипов по таблице смещений BLP.
+pub fn mip_bytes<'a>(img: &ImageBlp, buf: &'a [u8]) -> Vec<&'a [u8]> {
+    img.mipmaps
+        .iter()
+        .map(|m| &buf[m.offset..m.offset + m.length])
+        .collect()
+}
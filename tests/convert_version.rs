@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod convert_version {
+    use crate::common::{mip_bytes, test_data};
+    use blp::core::image::ImageBlp;
+    use blp::core::metadata::BlpMetadata;
+    use blp::core::types::{TextureType, Version};
+    use blp::error::error_kind::ErrorKind;
+
+    fn pixels(buf: &[u8]) -> Vec<u8> {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode(buf, &[true]).unwrap();
+        img.mipmaps[0]
+            .image
+            .as_ref()
+            .unwrap()
+            .as_raw()
+            .clone()
+    }
+
+    #[test]
+    fn jpeg_round_trip() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+
+        let blp2 = img.convert_version(&buf, Version::BLP2).unwrap();
+        assert_eq!(&blp2[..4], b"BLP2");
+        let img2 = ImageBlp::from_buf(&blp2).unwrap();
+        assert_eq!((img2.version, img2.texture_type, img2.compression), (Version::BLP2, TextureType::JPEG, 0));
+        assert_eq!((img2.width, img2.height, img2.alpha_bits, img2.has_mips), (img.width, img.height, img.alpha_bits, 1));
+        // общий JPEG-заголовок сразу за 148-байтным заголовком BLP2
+        assert_eq!(img2.header_offset, 152);
+        assert_eq!(mip_bytes(&img2, &blp2), mip_bytes(&img, &buf));
+        assert!(img2.hole_ranges.is_empty());
+        assert_eq!(pixels(&blp2), pixels(&buf));
+
+        let blp1 = img2.convert_version(&blp2, Version::BLP1).unwrap();
+        let img1 = ImageBlp::from_buf(&blp1).unwrap();
+        assert_eq!((img1.version, img1.has_mipmaps, img1.extra), (Version::BLP1, img.has_mipmaps, 0));
+        assert_eq!(mip_bytes(&img1, &blp1), mip_bytes(&img, &buf));
+        assert_eq!(img1.strip_holes(&blp1).unwrap(), blp1);
+    }
+
+    #[test]
+    fn direct_round_trip() {
+        let buf = test_data("to-png/a.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+
+        let blp2 = img.convert_version(&buf, Version::BLP2).unwrap();
+        let img2 = ImageBlp::from_buf(&blp2).unwrap();
+        assert_eq!((img2.texture_type, img2.compression), (TextureType::DIRECT, 1));
+        assert_eq!(img2.header_offset, 148);
+        assert_eq!(blp2[148..148 + 1024], buf[156..156 + 1024]);
+        assert_eq!(pixels(&blp2), pixels(&buf));
+
+        let blp1 = img2.convert_version(&blp2, Version::BLP1).unwrap();
+        assert_eq!(pixels(&blp1), pixels(&buf));
+        assert_eq!(blp1.len(), buf.len());
+    }
+
+    #[test]
+    fn keeps_metadata() {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true]).unwrap();
+        let buf = img.encode_blp(80, &[true]).unwrap().bytes;
+        let img = ImageBlp::from_buf(&buf).unwrap();
+
+        let blp2 = img.convert_version(&buf, Version::BLP2).unwrap();
+        let img2 = ImageBlp::from_buf(&blp2).unwrap();
+        assert_eq!(img2.metadata, img.metadata);
+        assert!(
+            img2.metadata
+                .unwrap()
+                .get(BlpMetadata::ENCODER)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn rejected() {
+        let buf = test_data("to-png/a.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        assert_eq!(img.convert_version(&buf, Version::BLP0).unwrap_err().kind, ErrorKind::InvalidArgument);
+
+        // BLP2 с DXT (compression = 2) в BLP1 не переносится
+        let mut dxt = img.convert_version(&buf, Version::BLP2).unwrap();
+        dxt[8] = 2;
+        let img = ImageBlp::from_buf(&dxt).unwrap();
+        let e = img.convert_version(&dxt, Version::BLP1).unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::UnsupportedCompression, "error-convert-compression"));
+
+        let png = test_data("to-png/a.png");
+        let img = ImageBlp::from_buf(&png).unwrap();
+        assert_eq!(img.convert_version(&png, Version::BLP2).unwrap_err().kind, ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod decode_backends {
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::decode::jpeg_backend::{DecodedJpeg, JpegDecode, JpegDecoderBackend};
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use blp::error::error_kind::ErrorKind;
+    use image::{Rgba, RgbaImage};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use walkdir::WalkDir;
+
+    /// Все `.blp` из test-data плюс свежезакодированные RGB/CMYK.
+    fn corpus() -> Vec<(String, Vec<u8>)> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data");
+        let mut out: Vec<(String, Vec<u8>)> = WalkDir::new(&root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| {
+                e.path()
+                    .extension()
+                    .is_some_and(|x| x.eq_ignore_ascii_case("blp"))
+            })
+            .map(|e| (e.path().display().to_string(), std::fs::read(e.path()).unwrap()))
+            .collect();
+
+        for alpha in [false, true] {
+            let src = RgbaImage::from_fn(64, 64, |x, y| Rgba([(x * 4) as u8, (y * 4) as u8, (x ^ y) as u8, if alpha { (x + y) as u8 } else { 255 }]));
+            let ctx = ImageBlp::from_rgba(src, &[])
+                .unwrap()
+                .encode_blp(90, &[])
+                .unwrap();
+            out.push((format!("generated alpha={alpha}"), ctx.bytes));
+        }
+        out
+    }
+
+    fn decode(buf: &[u8], opts: &DecodeOptions) -> Result<ImageBlp, BlpError> {
+        let mut img = ImageBlp::from_buf(buf)?;
+        img.decode_with(buf, &[], opts)?;
+        Ok(img)
+    }
+
+    /// Пиксели совпадают с точностью до ±1: у `jpeg_decoder` своя (stb) IDCT,
+    /// у libjpeg-turbo — islow (FASTDCT расходится сильнее, FASTUPSAMPLE на 4:4:4 ни на что не влияет).
+    /// Расхождения редкие: на корпусе — около 1% отсчётов, порог — 2%.
+    /// Файлы, которые не читает ни один бэкенд, пропускаются.
+    #[cfg(feature = "turbojpeg")]
+    #[test]
+    fn backends_agree_on_corpus() -> Result<(), BlpError> {
+        use blp::core::decode::turbojpeg_backend::TurboJpegBackend;
+        use blp::core::types::TextureType;
+
+        let turbo = DecodeOptions { jpeg: Arc::new(TurboJpegBackend), jpeg_fallback: None, ..Default::default() };
+        let mut jpeg_seen = 0;
+        let (mut off, mut total) = (0usize, 0usize);
+        for (name, buf) in corpus() {
+            let (a, b) = match (decode(&buf, &DecodeOptions::default()), decode(&buf, &turbo)) {
+                (Ok(a), Ok(b)) => (a, b),
+                (Err(_), Err(_)) => continue,
+                (a, b) => panic!("{name}: jpeg-decoder ok={}, turbojpeg ok={}", a.is_ok(), b.is_ok()),
+            };
+            if a.texture_type == TextureType::JPEG {
+                jpeg_seen += 1;
+            }
+            assert_eq!(a.mipmaps.len(), b.mipmaps.len(), "{name}");
+            for (i, (ma, mb)) in a.mipmaps.iter().zip(&b.mipmaps).enumerate() {
+                match (&ma.image, &mb.image) {
+                    (Some(x), Some(y)) => {
+                        assert_eq!(x.dimensions(), y.dimensions(), "{name} mip {i}");
+                        let max = x
+                            .as_raw()
+                            .iter()
+                            .zip(y.as_raw())
+                            .map(|(&p, &q)| p.abs_diff(q))
+                            .max()
+                            .unwrap_or(0);
+                        assert!(max <= 1, "{name} mip {i}: pixels differ by {max}");
+                        off += x
+                            .as_raw()
+                            .iter()
+                            .zip(y.as_raw())
+                            .filter(|(p, q)| p != q)
+                            .count();
+                        total += x.as_raw().len();
+                    }
+                    (None, None) => {}
+                    _ => panic!("{name} mip {i}: decoded by one backend only"),
+                }
+            }
+        }
+        assert!(jpeg_seen > 0, "corpus has no JPEG BLPs");
+        assert!(off * 50 < total, "{off} of {total} samples differ");
+        Ok(())
+    }
+
+    /// Основной бэкенд всегда падает — мипы должен вытянуть запасной.
+    struct Broken;
+
+    impl JpegDecode for Broken {
+        fn name(&self) -> &'static str {
+            "broken"
+        }
+
+        fn decode(&self, _jpeg: &[u8]) -> Result<DecodedJpeg, BlpError> {
+            Err(BlpError::new(ErrorKind::InvalidJpeg, "broken"))
+        }
+    }
+
+    #[test]
+    fn fallback_on_failure() -> Result<(), BlpError> {
+        let (_, buf) = corpus().pop().unwrap();
+        let reference = decode(&buf, &DecodeOptions::default())?;
+
+        let no_fallback = DecodeOptions { jpeg: Arc::new(Broken), jpeg_fallback: None, ..Default::default() };
+        let err = decode(&buf, &no_fallback).unwrap_err();
+        assert_eq!(err.key, "broken");
+
+        let with_fallback = DecodeOptions { jpeg: Arc::new(Broken), jpeg_fallback: Some(Arc::new(JpegDecoderBackend)), ..Default::default() };
+        let img = decode(&buf, &with_fallback)?;
+        for (a, b) in reference.mipmaps.iter().zip(&img.mipmaps) {
+            assert_eq!(a.image, b.image);
+        }
+
+        let both_broken = DecodeOptions { jpeg: Arc::new(Broken), jpeg_fallback: Some(Arc::new(Broken)), ..Default::default() };
+        let err = decode(&buf, &both_broken).unwrap_err();
+        assert_eq!(err.causes.len(), 1, "fallback error is attached as a cause");
+        Ok(())
+    }
+}
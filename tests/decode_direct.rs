@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod decode_direct {
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use blp::error::error_kind::ErrorKind;
+
+    /// DIRECT BLP1 одним мипом `w×h`; пиксель `p` → индекс `p * 31 % 251`, альфа — байты `p * 7`.
+    fn direct_blp(w: u32, h: u32, alpha_bits: u32) -> (Vec<u8>, Vec<u8>, Vec<[u8; 4]>) {
+        let n = (w * h) as usize;
+        let alpha_len = match alpha_bits {
+            1 => n.div_ceil(8),
+            4 => n.div_ceil(2),
+            8 => n,
+            _ => 0,
+        };
+        let indices: Vec<u8> = (0..n).map(|p| (p * 31 % 251) as u8).collect();
+        let alpha: Vec<u8> = (0..alpha_len).map(|p| (p * 7) as u8).collect();
+        let palette: Vec<[u8; 4]> = (0..256u32)
+            .map(|c| [c as u8, (255 - c) as u8, (c * 3) as u8, 0x5A]) // B, G, R, (игнорируется)
+            .collect();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(b"BLP1");
+        for v in [1, alpha_bits, w, h, 5, 0] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        let mut offsets = [0u32; 16];
+        let mut sizes = [0u32; 16];
+        offsets[0] = 156 + 1024;
+        sizes[0] = (n + alpha_len) as u32;
+        offsets
+            .iter()
+            .chain(&sizes)
+            .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+        palette
+            .iter()
+            .for_each(|c| out.extend_from_slice(c));
+        out.extend_from_slice(&indices);
+        out.extend_from_slice(&alpha);
+        (out, indices, palette)
+    }
+
+    /// Попиксельная эталонная сборка — как было до векторизации.
+    fn reference(indices: &[u8], alpha: &[u8], palette: &[[u8; 4]], alpha_bits: u32) -> Vec<u8> {
+        let mut out = Vec::with_capacity(indices.len() * 4);
+        for (p, &idx) in indices.iter().enumerate() {
+            let [b, g, r, _] = palette[idx as usize];
+            let a = match alpha_bits {
+                1 => {
+                    if (alpha[p / 8] >> (p % 8)) & 1 == 1 { 255 } else { 0 }
+                }
+                4 => {
+                    let byte = alpha[p / 2];
+                    let nibble = if p & 1 == 0 { byte & 0x0F } else { byte >> 4 };
+                    (nibble << 4) | nibble
+                }
+                8 => alpha[p],
+                _ => 255,
+            };
+            out.extend_from_slice(&[r, g, b, a]);
+        }
+        out
+    }
+
+    #[test]
+    fn matches_per_pixel_reference() -> Result<(), BlpError> {
+        // нечётные размеры — хвосты у 1- и 4-битной альфы
+        for (w, h) in [(16, 8), (7, 3), (1, 1)] {
+            for alpha_bits in [0, 1, 4, 8] {
+                let (buf, indices, palette) = direct_blp(w, h, alpha_bits);
+                let mut img = ImageBlp::from_buf(&buf)?;
+                img.decode(&buf, &[true])?;
+                let got = img.mipmaps[0]
+                    .image
+                    .as_ref()
+                    .expect("mip 0 decoded");
+
+                let alpha_start = 156 + 1024 + indices.len();
+                let want = reference(&indices, &buf[alpha_start..], &palette, alpha_bits);
+                assert_eq!(got.dimensions(), (w, h));
+                assert!(got.as_raw() == &want, "{w}x{h} alpha_bits={alpha_bits}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_alpha_is_an_error() {
+        // длина мипа покрывает только индексы, альфы в файле нет вовсе
+        let (mut buf, indices, _) = direct_blp(8, 8, 8);
+        buf.truncate(156 + 1024 + indices.len());
+        buf[92..96].copy_from_slice(&(indices.len() as u32).to_le_bytes());
+
+        let mut img = ImageBlp::from_buf(&buf).unwrap();
+        let err = img.decode(&buf, &[true]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Truncated);
+        assert_eq!(err.key, "error-direct-alpha-truncated");
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod decode_layout {
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::decode::pixel_layout::PixelLayout;
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::{Rgba, RgbaImage};
+    use std::path::PathBuf;
+
+    const LAYOUTS: [PixelLayout; 5] = [
+        PixelLayout::Rgba8,
+        PixelLayout::Bgra8,
+        PixelLayout::Rgba8Premultiplied,
+        PixelLayout::Rgb8,
+        PixelLayout::L8,
+    ];
+
+    /// Эталон: попиксельное преобразование уже декодированного RGBA.
+    fn reference(img: &RgbaImage, layout: PixelLayout) -> Vec<u8> {
+        let pm = |c: u8, a: u8| ((c as f32 * a as f32 / 255.0).round()) as u8;
+        let mut out = Vec::new();
+        for &Rgba([r, g, b, a]) in img.pixels() {
+            match layout {
+                PixelLayout::Rgba8 => out.extend([r, g, b, a]),
+                PixelLayout::Bgra8 => out.extend([b, g, r, a]),
+                PixelLayout::Rgba8Premultiplied => out.extend([pm(r, a), pm(g, a), pm(b, a), a]),
+                PixelLayout::Rgb8 => out.extend([r, g, b]),
+                PixelLayout::L8 => out.push(((77 * r as u32 + 150 * g as u32 + 29 * b as u32 + 128) >> 8) as u8),
+            }
+        }
+        out
+    }
+
+    fn check(buf: &[u8], what: &str) -> Result<(), BlpError> {
+        let mut full = ImageBlp::from_buf(buf)?;
+        full.decode(buf, &[])?;
+        let probe = ImageBlp::from_buf(buf)?;
+
+        for (mip, m) in full.mipmaps.iter().enumerate() {
+            let Some(img) = &m.image else { continue };
+            for layout in LAYOUTS {
+                // Буфер с запасом: хвост не трогается.
+                let need = layout.buffer_len(img.width(), img.height());
+                let mut out = vec![0xAB; need + 7];
+                let dims = probe.decode_mip_into(buf, mip, layout, &mut out, &DecodeOptions::default())?;
+                assert_eq!(dims, img.dimensions(), "{what} mip={mip} {layout:?}");
+                assert!(out[..need] == reference(img, layout), "{what} mip={mip} {layout:?}");
+                assert!(out[need..].iter().all(|&b| b == 0xAB), "{what} mip={mip} {layout:?}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn layouts_match_rgba() -> Result<(), BlpError> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        // JPEG и DIRECT из тестовых данных
+        for rel in ["test-data/to-blp/WarcraftIIIFTLogo.blp", "test-data/to-png/a.blp"] {
+            check(&std::fs::read(root.join(rel)).unwrap(), rel)?;
+        }
+
+        // JPEG с альфой (CMYK)
+        let src = RgbaImage::from_fn(64, 32, |x, y| Rgba([x as u8 * 4, y as u8 * 8, 200, (x * 4 + y) as u8]));
+        let bytes = ImageBlp::from_rgba(src, &[])?
+            .encode_blp(90, &[])?
+            .bytes;
+        check(&bytes, "generated")
+    }
+
+    #[test]
+    fn small_buffer_is_an_error() -> Result<(), BlpError> {
+        let buf = std::fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/to-png/a.blp")).unwrap();
+        let img = ImageBlp::from_buf(&buf)?;
+        let m = &img.mipmaps[0];
+        let mut out = vec![0; PixelLayout::Rgb8.buffer_len(m.width, m.height) - 1];
+        assert!(
+            img.decode_mip_into(&buf, 0, PixelLayout::Rgb8, &mut out, &DecodeOptions::default())
+                .is_err()
+        );
+        assert!(
+            img.decode_mip_into(&buf, 99, PixelLayout::L8, &mut out, &DecodeOptions::default())
+                .is_err()
+        );
+        Ok(())
+    }
+}
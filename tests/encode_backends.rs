@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod encode_backends {
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::encode::jpeg_backend::JpegBackend;
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::{Rgba, RgbaImage};
+
+    fn gradient(w: u32, h: u32, alpha: bool) -> RgbaImage {
+        RgbaImage::from_fn(w, h, |x, y| {
+            let a = if alpha { ((x + y) * 255 / (w + h)) as u8 } else { 255 };
+            Rgba([(x * 255 / w) as u8, (y * 255 / h) as u8, ((x ^ y) & 0xFF) as u8 / 2 + 64, a])
+        })
+    }
+
+    /// Средняя абсолютная ошибка по каналам.
+    fn mean_abs_diff(a: &RgbaImage, b: &RgbaImage) -> f64 {
+        let sum: u64 = a
+            .as_raw()
+            .iter()
+            .zip(b.as_raw())
+            .map(|(&x, &y)| (x as i32 - y as i32).unsigned_abs() as u64)
+            .sum();
+        sum as f64 / a.as_raw().len() as f64
+    }
+
+    /// Компоненты из SOF0 мипа 0: общий JPEG-заголовок BLP1 + payload мипа.
+    fn sof_component_ids(blp: &[u8]) -> Vec<u8> {
+        let u32_at = |p: usize| u32::from_le_bytes(blp[p..p + 4].try_into().unwrap()) as usize;
+        let hdr_len = u32_at(156);
+        let (off, size) = (u32_at(28), u32_at(92));
+        let hdr = [&blp[160..160 + hdr_len], &blp[off..off + size]].concat();
+        let mut i = 2;
+        loop {
+            let m = hdr[i + 1];
+            let len = u16::from_be_bytes([hdr[i + 2], hdr[i + 3]]) as usize;
+            assert!(!(0xE0..=0xEF).contains(&m) && m != 0xFE, "APPn/COM must be stripped");
+            if m == 0xC0 {
+                let nf = hdr[i + 9] as usize;
+                return (0..nf).map(|k| hdr[i + 10 + k * 3]).collect();
+            }
+            i += 2 + len;
+        }
+    }
+
+    fn round_trip(backend: JpegBackend, src: &RgbaImage) -> Result<(Vec<u8>, RgbaImage), BlpError> {
+        let blp = ImageBlp::from_rgba(src.clone(), &[])?;
+        let ctx = blp.encode_blp_with(&EncodeOptions { quality: 100, backend, ..Default::default() }, &[])?;
+
+        let mut back = ImageBlp::from_buf(&ctx.bytes)?;
+        back.decode(&ctx.bytes, &[])?;
+        let img = back.mipmaps[0]
+            .image
+            .take()
+            .expect("mip 0 decoded");
+        Ok((ctx.bytes, img))
+    }
+
+    #[test]
+    fn rgb_and_cmyk_round_trip() -> Result<(), BlpError> {
+        for &backend in JpegBackend::available() {
+            for alpha in [false, true] {
+                let src = gradient(64, 32, alpha);
+                let (bytes, img) = round_trip(backend, &src)?;
+
+                let ids = sof_component_ids(&bytes);
+                assert_eq!(ids, if alpha { b"CMYK".to_vec() } else { b"RGB".to_vec() }, "{backend:?}");
+                // одна таблица Tq = 0 на все компоненты, как у TurboJPEG
+                let e = ImageBlp::from_buf(&bytes)?.quality_estimate.expect("DQT");
+                let tq: Vec<u8> = e.tables.iter().map(|t| t.id).collect();
+                assert_eq!((tq, e.quality, e.exact), (vec![0], 100, true), "{backend:?}");
+                assert_eq!(img.dimensions(), src.dimensions());
+
+                let err = mean_abs_diff(&src, &img);
+                assert!(err < 2.0, "{backend:?} alpha={alpha}: mean abs diff {err}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn backends_agree() -> Result<(), BlpError> {
+        let all = JpegBackend::available();
+        for alpha in [false, true] {
+            let src = gradient(32, 32, alpha);
+            let decoded: Vec<RgbaImage> = all
+                .iter()
+                .map(|&b| round_trip(b, &src).map(|(_, img)| img))
+                .collect::<Result<_, _>>()?;
+            for pair in decoded.windows(2) {
+                let err = mean_abs_diff(&pair[0], &pair[1]);
+                assert!(err < 1.0, "alpha={alpha}: backends differ by {err}");
+            }
+        }
+        Ok(())
+    }
+}
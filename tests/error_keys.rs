@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod error_keys {
+    use blp::core::decode::decode_limits::DecodeLimits;
+    use blp::core::image::ImageBlp;
+    use blp::error::error_kind::ErrorKind;
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    /// Все строковые литералы `"error-…"` в исходниках.
+    fn keys_in_src() -> BTreeSet<String> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut keys = BTreeSet::new();
+        for entry in walkdir::WalkDir::new(root) {
+            let path = entry.unwrap().into_path();
+            if path.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            let src = std::fs::read_to_string(&path).unwrap();
+            for (i, _) in src.match_indices("\"error-") {
+                let key: String = src[i + 1..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                    .collect();
+                keys.insert(key);
+            }
+            // Ключ — всегда литерал `error-…`, чтобы его видел этот тест
+            for (i, _) in src.match_indices("BlpError::new(ErrorKind::") {
+                let rest = &src[i..];
+                let arg = rest[rest.find(',').unwrap() + 1..].trim_start();
+                assert!(arg.starts_with("\"error-") || arg.starts_with("key"), "{path:?}: {}", &rest[..rest.len().min(80)]);
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn every_key_has_en_entry() {
+        let ftl = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/locales/en/core.ftl")).unwrap();
+        let defined: BTreeSet<&str> = ftl
+            .lines()
+            .filter_map(|l| l.split_once(" ="))
+            .map(|(k, _)| k.trim())
+            .collect();
+
+        let keys = keys_in_src();
+        assert!(keys.len() > 40, "{keys:?}");
+        let missing: Vec<_> = keys
+            .iter()
+            .filter(|k| !defined.contains(k.as_str()))
+            .collect();
+        assert!(missing.is_empty(), "missing in en/core.ftl: {missing:?}");
+    }
+
+    #[test]
+    fn kinds() {
+        let kind = |buf: &[u8]| ImageBlp::from_buf(buf).unwrap_err().kind;
+
+        assert_eq!(kind(b"BLP9\0\0\0\0"), ErrorKind::UnsupportedVersion);
+        assert_eq!(kind(b"BLP1\x07\0\0\0"), ErrorKind::UnsupportedCompression);
+        assert_eq!(kind(b"BLP1\x01\0\0\0\x08"), ErrorKind::Truncated);
+
+        let mut header = b"BLP2\x01\0\0\0\x01\x08\0\x01".to_vec();
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        header.extend_from_slice(&1u32.to_le_bytes());
+        header.resize(148, 0);
+        assert_eq!(kind(&header), ErrorKind::LimitExceeded);
+
+        // обёртка сохраняет вид причины
+        let e = ImageBlp::from_buf_with(&header, &DecodeLimits::default())
+            .unwrap_err()
+            .ctx("error-blp-decode-failed");
+        assert_eq!((e.kind, e.key), (ErrorKind::LimitExceeded, "error-blp-decode-failed"));
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+#[cfg(test)]
+mod fill_mips {
+    use crate::common::test_data;
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::image::ImageBlp;
+    use blp::core::metadata::BlpMetadata;
+
+    fn decode(buf: &[u8], mip_visible: &[bool]) -> ImageBlp {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode(buf, mip_visible).unwrap();
+        img
+    }
+
+    fn fill(quality: u8) -> EncodeOptions {
+        EncodeOptions { quality, fill_mips: true, ..Default::default() }
+    }
+
+    #[test]
+    fn same_as_full_chain() {
+        // пересчёт скрытых уровней совпадает с цепочкой decode_image побайтно
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let full = decode(&png, &[true; 16])
+            .encode_blp(90, &[true; 16])
+            .unwrap();
+
+        let mut visible = [true; 16];
+        visible[1] = false;
+        visible[4..].fill(false);
+        let filled = decode(&png, &visible)
+            .encode_blp_with(&fill(90), &visible)
+            .unwrap();
+        assert!(filled.mips[..10].iter().all(|m| m.visible));
+        assert_eq!(full.bytes, filled.bytes);
+    }
+
+    #[test]
+    fn rebuilds_blp_chain() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let mut visible = [true; 16];
+        visible[3..].fill(false);
+        let mut src = ImageBlp::from_buf(&buf).unwrap();
+        src.decode_with(&buf, &visible, &DecodeOptions { keep_jpeg: true, ..Default::default() })
+            .unwrap();
+
+        let ctx = src.encode_blp(90, &visible).unwrap();
+        let out = decode(&ctx.bytes, &[true; 16]);
+        assert_eq!(out.metadata.as_ref().unwrap().get(BlpMetadata::MIPS), Some("1110000000000000"));
+
+        // имеющиеся мипы копируются, недостающие — пересчитываются
+        let ctx = src.encode_blp_with(&EncodeOptions { reuse_source: true, ..fill(90) }, &visible).unwrap();
+        assert!(ctx.mips[..3].iter().all(|m| m.copied));
+        assert!(ctx.mips[3..10].iter().all(|m| m.visible && !m.copied));
+
+        let out = decode(&ctx.bytes, &[true; 16]);
+        assert_eq!(out.metadata.as_ref().unwrap().get(BlpMetadata::MIPS), Some("1111111111000000"));
+        for (i, m) in out.mipmaps[..10].iter().enumerate() {
+            let img = m.image.as_ref().unwrap();
+            assert_eq!(img.dimensions(), ((512 >> i).max(1), (256 >> i).max(1)), "mip {i}");
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod from_rgba {
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::DynamicImage;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn png_bytes() -> Vec<u8> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/to-blp/a.png");
+        fs::read(&path).unwrap_or_else(|e| panic!("read {}: {e}", path.display()))
+    }
+
+    #[test]
+    fn same_as_buf_path() -> Result<(), BlpError> {
+        let data = png_bytes();
+        let mask = [true, false, true];
+
+        let mut via_buf = ImageBlp::from_buf(&data)?;
+        via_buf.decode(&data, &mask)?;
+
+        let rgba = image::load_from_memory(&data)?.into_rgba8();
+        let via_rgba = ImageBlp::from_rgba(rgba, &mask)?;
+
+        assert_eq!((via_buf.width, via_buf.height), (via_rgba.width, via_rgba.height));
+        assert_eq!(via_buf.mipmaps.len(), via_rgba.mipmaps.len());
+        for (i, (a, b)) in via_buf
+            .mipmaps
+            .iter()
+            .zip(&via_rgba.mipmaps)
+            .enumerate()
+        {
+            assert_eq!((a.width, a.height), (b.width, b.height), "mip {i} dims");
+            assert_eq!(a.image, b.image, "mip {i} pixels");
+        }
+        assert!(via_rgba.mipmaps[1].image.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn from_dynamic_image() -> Result<(), BlpError> {
+        let dyn_img = image::load_from_memory(&png_bytes())?;
+        let blp = ImageBlp::from(dyn_img);
+        assert!(blp.width.is_power_of_two() && blp.height.is_power_of_two());
+        assert!(blp.mipmaps[0].image.is_some());
+
+        let empty = ImageBlp::from(DynamicImage::new_rgba8(0, 0));
+        assert!(empty.mipmaps.iter().all(|m| m.image.is_none()));
+        Ok(())
+    }
+}
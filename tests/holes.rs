@@ -0,0 +1,54 @@
+mod common;
+
+#[cfg(test)]
+mod holes {
+    use crate::common::{mip_bytes, test_data};
+    use blp::core::hole::Hole;
+    use blp::core::image::ImageBlp;
+
+    #[test]
+    fn ranges_and_strip() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        assert_eq!(img.hole_ranges, [Hole { offset: 784, length: 396 }]);
+        assert_eq!(img.holes, 396);
+        assert!(!img.hole_ranges[0].is_raft(&buf));
+
+        let stripped = img.strip_holes(&buf).unwrap();
+        assert_eq!(stripped.len(), buf.len() - 396);
+        let again = ImageBlp::from_buf(&stripped).unwrap();
+        assert!(again.hole_ranges.is_empty());
+        assert_eq!(mip_bytes(&again, &stripped), mip_bytes(&img, &buf));
+        let header = |i: &ImageBlp, b: &[u8]| b[i.header_offset..i.header_offset + i.header_length].to_vec();
+        assert_eq!(header(&again, &stripped), header(&img, &buf));
+    }
+
+    #[test]
+    fn raft_marker_of_own_encoder() {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true, true]).unwrap();
+        let blp = img.encode_blp(80, &[true, true]).unwrap().bytes;
+
+        let img = ImageBlp::from_buf(&blp).unwrap();
+        assert_eq!(img.hole_ranges.len(), 1);
+        assert!(img.hole_ranges[0].is_raft(&blp));
+        assert!(img.hole_ranges[0].bytes(&blp).starts_with(Hole::RAFT));
+        assert!(img.metadata.is_some());
+    }
+
+    #[test]
+    fn direct_trailing_junk() {
+        let mut buf = test_data("to-png/a.blp");
+        let len = buf.len();
+        buf.extend(b"signed by someone");
+
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        assert_eq!(img.hole_ranges, [Hole { offset: len, length: 17 }]);
+        assert_eq!(img.hole_ranges[0].bytes(&buf), b"signed by someone");
+
+        let stripped = img.strip_holes(&buf).unwrap();
+        let again = ImageBlp::from_buf(&stripped).unwrap();
+        assert_eq!((again.holes, mip_bytes(&again, &stripped)), (0, mip_bytes(&img, &buf)));
+    }
+}
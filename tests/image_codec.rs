@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod image_codec {
+    use blp::core::codec::blp_decoder::BlpDecoder;
+    use blp::core::codec::blp_encoder::BlpEncoder;
+    use blp::core::codec::register_blp_format::{SaveBlp, register_blp_format};
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::{DynamicImage, ExtendedColorType, ImageDecoder, ImageEncoder};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn data(rel: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(rel)
+    }
+
+    #[test]
+    fn decoder_matches_decode() -> Result<(), BlpError> {
+        let buf = fs::read(data("test-data/to-png/a.blp"))?;
+        let mut blp = ImageBlp::from_buf(&buf)?;
+        blp.decode(&buf, &[])?;
+
+        for level in [0usize, 1] {
+            let dec = BlpDecoder::new(buf.as_slice())?.with_mip(level)?;
+            let expected = blp.mipmaps[level].image.as_ref().expect("mip decoded");
+            assert_eq!(dec.dimensions(), expected.dimensions());
+
+            let img = DynamicImage::from_decoder(dec)?.into_rgba8();
+            assert_eq!(img.as_raw(), expected.as_raw(), "mip {level}");
+        }
+
+        let dec = BlpDecoder::new(buf.as_slice())?;
+        let count = dec.mip_count();
+        assert!(BlpDecoder::new(buf.as_slice())?.with_mip(count).is_err());
+        assert!(BlpDecoder::new(&b"PNG not blp"[..]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn open_and_save_via_hooks() -> Result<(), BlpError> {
+        register_blp_format();
+        assert!(!register_blp_format());
+
+        let img = image::open(data("test-data/to-png/a.blp"))?;
+        let buf = fs::read(data("test-data/to-png/a.blp"))?;
+        let from_mem = image::load_from_memory(&buf)?;
+        assert_eq!(img.to_rgba8().as_raw(), from_mem.to_rgba8().as_raw());
+
+        let out = std::env::temp_dir().join(format!("blp-image-codec-{}.blp", std::process::id()));
+        img.save_blp(&out, 90)?;
+        let back = image::open(&out)?;
+        fs::remove_file(&out).ok();
+        assert_eq!(back.width(), img.width());
+        assert_eq!(back.height(), img.height());
+        Ok(())
+    }
+
+    #[test]
+    fn encoder_round_trip() -> Result<(), BlpError> {
+        let src = image::open(data("test-data/to-blp/a.png"))?.into_rgb8();
+
+        let mut bytes = Vec::new();
+        BlpEncoder::new(&mut bytes)
+            .with_quality(100)
+            .write_image(src.as_raw(), src.width(), src.height(), ExtendedColorType::Rgb8)?;
+        assert_eq!(&bytes[..4], b"BLP1");
+
+        let dec = BlpDecoder::new(bytes.as_slice())?;
+        let (w, h) = (dec.blp().width, dec.blp().height);
+        assert!(w.is_power_of_two() && h.is_power_of_two());
+        let out = DynamicImage::from_decoder(dec)?;
+        assert_eq!((out.width(), out.height()), (w, h));
+
+        let err = BlpEncoder::new(Vec::new()).write_image(&[0u8; 8], 1, 1, ExtendedColorType::Rgba16);
+        assert!(matches!(err, Err(image::ImageError::Unsupported(_))));
+        Ok(())
+    }
+}
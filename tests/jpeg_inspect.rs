@@ -0,0 +1,82 @@
+mod common;
+
+#[cfg(test)]
+mod jpeg_inspect {
+    use crate::common::test_data;
+    use blp::core::image::ImageBlp;
+    use blp::core::inspect::jpeg_header_info::JpegHeaderInfo;
+    use blp::error::error_kind::ErrorKind;
+
+    #[test]
+    fn standard_layout() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let r = ImageBlp::from_buf(&buf).unwrap().jpeg_inspect(&buf).unwrap();
+
+        assert_eq!((r.shared_header_len, r.mips.len()), (624, 10));
+        // SOF лежит в данных мипа: заголовки различаются только размерами
+        assert!(!r.all_equal() && r.all_equal_except_dims());
+        assert_eq!(r.sof_mismatches(), 0);
+        assert_eq!((r.mips[1].sof_dims(), r.mips[1].lcp_to_mip0), (Some((256, 128)), Some(627)));
+
+        let csv = r.to_csv("x.blp");
+        assert_eq!(csv.lines().count(), 10);
+        assert!(csv.starts_with("\"x.blp\",0,512,256,512,256,660,13019bb2"), "{csv}");
+        assert!(r.to_json("x.blp").contains("\"all_equal_except_dims\":true,\"sof_mismatches\":0,"));
+    }
+
+    #[test]
+    fn patched_sof_detected() {
+        let mut buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        let shared = img.jpeg_inspect(&buf).unwrap().shared_header_len;
+
+        // SOF начинается за 2 байта до конца общего заголовка: ширина мипа 3 — в его данных
+        let m = &img.mipmaps[3];
+        let sof = JpegHeaderInfo::parse(&[&buf[160..160 + shared], &buf[m.offset..m.offset + m.length]].concat())
+            .unwrap()
+            .sof
+            .unwrap();
+        buf[m.offset + sof.width_off - shared] = 0x01;
+
+        let r = img.jpeg_inspect(&buf).unwrap();
+        assert_eq!(r.sof_mismatches(), 1);
+        assert!(r.mips[3].sof_mismatch());
+        assert_eq!(r.mips[3].sof_dims(), Some((256 + 64, 32)));
+        assert!(r.all_equal_except_dims());
+    }
+
+    #[test]
+    fn unparsable_base() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let mut img = ImageBlp::from_buf(&buf).unwrap();
+        // единственный мип с данными обрезан посреди SOF
+        img.mipmaps[0].length = 3;
+        for m in &mut img.mipmaps[1..] {
+            m.length = 0;
+        }
+
+        let r = img.jpeg_inspect(&buf).unwrap();
+        assert_eq!(r.mips.len(), 1);
+        assert!(r.mips[0].header.is_err());
+        assert!(!r.mips[0].header_eq_mip0 && !r.mips[0].header_eq_mip0_except_dims);
+        assert!(!r.all_equal() && !r.all_equal_except_dims());
+    }
+
+    #[test]
+    fn errors() {
+        let direct = test_data("to-png/a.blp");
+        let e = ImageBlp::from_buf(&direct).unwrap().jpeg_inspect(&direct).unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidArgument, "error-inspect-not-jpeg"));
+
+        let cases: [(&[u8], &str); 4] = [
+            (b"PNG", "error-jpeg-bad-soi"),
+            (&[0xFF, 0xD8, 0x00], "error-jpeg-marker-expected"),
+            (&[0xFF, 0xD8, 0xFF, 0xD9], "error-jpeg-eoi-before-sos"),
+            (&[0xFF, 0xD8, 0xFF, 0xDB, 0x00, 0x10, 0x00], "error-jpeg-truncated"),
+        ];
+        for (buf, key) in cases {
+            let e = JpegHeaderInfo::parse(buf).unwrap_err();
+            assert_eq!((e.kind, e.key), (ErrorKind::InvalidJpeg, key));
+        }
+    }
+}
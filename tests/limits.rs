@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod limits {
+    use blp::core::decode::decode_limits::DecodeLimits;
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::{Rgba, RgbaImage};
+    use std::path::PathBuf;
+
+    fn data(rel: &str) -> Vec<u8> {
+        std::fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(rel)).unwrap()
+    }
+
+    /// BLP1 DIRECT с произвольными размерами и одним мипом длины `len` сразу после палитры.
+    fn hostile_direct(w: u32, h: u32, len: u32) -> Vec<u8> {
+        let mut b = Vec::new();
+        b.extend_from_slice(b"BLP1");
+        b.extend_from_slice(&1u32.to_le_bytes()); // DIRECT
+        b.extend_from_slice(&8u32.to_le_bytes()); // alpha_bits
+        b.extend_from_slice(&w.to_le_bytes());
+        b.extend_from_slice(&h.to_le_bytes());
+        b.extend_from_slice(&[0; 8]); // extra, has_mipmaps
+        let mut offsets = [0u32; 16];
+        let mut lengths = [0u32; 16];
+        offsets[0] = 156 + 1024;
+        lengths[0] = len;
+        offsets.iter().for_each(|v| b.extend_from_slice(&v.to_le_bytes()));
+        lengths.iter().for_each(|v| b.extend_from_slice(&v.to_le_bytes()));
+        b.resize(156 + 1024 + len as usize, 0);
+        b
+    }
+
+    #[test]
+    fn header_dims_are_limited() {
+        let buf = hostile_direct(65535, 65535, 16);
+        let e = ImageBlp::from_buf(&buf).unwrap_err();
+        assert_eq!(e.key, "error-limits-exceeded");
+
+        // Без лимитов заголовок проходит, но мип проверяется по данным до выделения памяти.
+        let unlimited = DecodeLimits::unlimited();
+        let mut img = ImageBlp::from_buf_with(&buf, &unlimited).unwrap();
+        let opts = DecodeOptions { limits: unlimited, ..Default::default() };
+        assert_eq!(img.decode_with(&buf, &[], &opts).unwrap_err().key, "error-direct-indices-truncated");
+    }
+
+    #[test]
+    fn pixel_and_mip_limits() {
+        let buf = hostile_direct(64, 64, 64 * 64 * 2);
+        assert!(ImageBlp::from_buf(&buf).is_ok());
+        for limits in [
+            DecodeLimits { max_pixels: 64 * 64 - 1, ..Default::default() },
+            DecodeLimits { max_mips: 0, ..Default::default() },
+            DecodeLimits { max_alloc: 64 * 64 * 4 - 1, ..Default::default() },
+            DecodeLimits { max_height: 63, ..Default::default() },
+        ] {
+            let e = ImageBlp::from_buf_with(&buf, &limits).unwrap_err();
+            assert_eq!(e.key, "error-limits-exceeded", "{limits:?}");
+        }
+    }
+
+    #[test]
+    fn jpeg_sof_and_external_images_are_limited() -> Result<(), BlpError> {
+        let src = RgbaImage::from_fn(64, 32, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+
+        // SOF мипа 0 больше лимита: отказ до JPEG-декодера.
+        let bytes = ImageBlp::from_rgba(src.clone(), &[])?
+            .encode_blp(80, &[])?
+            .bytes;
+        let mut img = ImageBlp::from_buf(&bytes)?;
+        img.width = 1; // поля публичные: заголовок «врёт», SOF — нет
+        img.height = 1;
+        let opts = DecodeOptions { limits: DecodeLimits { max_width: 32, ..Default::default() }, ..Default::default() };
+        assert_eq!(img.decode_with(&bytes, &[], &opts).unwrap_err().key, "error-limits-exceeded");
+
+        // Внешняя картинка: лимиты передаются в `image`.
+        let mut png = Vec::new();
+        src.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let small = DecodeLimits { max_width: 16, ..Default::default() };
+        assert!(ImageBlp::from_buf_with(&png, &small).is_err());
+        assert!(ImageBlp::from_buf(&png).is_ok());
+        Ok(())
+    }
+
+    /// Обрезки и битые байты реальных файлов: только ошибки, без паник.
+    #[test]
+    fn mangled_files_do_not_panic() {
+        let opts = DecodeOptions { threads: 1, ..Default::default() };
+        let mut seed = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for rel in ["test-data/to-png/a.blp", "test-data/to-blp/WarcraftIIIFTLogo.blp"] {
+            let orig = data(rel);
+            let run = |buf: &[u8]| {
+                if let Ok(mut img) = ImageBlp::from_buf(buf) {
+                    let _ = img.decode_with(buf, &[], &opts);
+                }
+            };
+            for cut in (0..orig.len()).step_by(orig.len() / 40 + 1) {
+                run(&orig[..cut]);
+            }
+            for _ in 0..40 {
+                let mut buf = orig.clone();
+                for _ in 0..8 {
+                    // в основном заголовок и таблицы мипов
+                    let at = next() as usize % buf.len().min(2048);
+                    buf[at] = next() as u8;
+                }
+                run(&buf);
+            }
+        }
+    }
+}
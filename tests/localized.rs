@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod localized {
+    use blp::error::error::BlpError;
+    use blp::error::error_kind::ErrorKind;
+    use blp::i18n::lng_list::LngList;
+
+    #[test]
+    fn cause_tree() {
+        let io = std::io::Error::other("disk on fire");
+        let e = BlpError::from(io)
+            .ctx("error-blp-decode-failed")
+            .push_blp(BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", 3u32));
+
+        assert_eq!(
+            e.localized(&LngList::En.id()),
+            "Failed to decode BLP.\n  I/O error: disk on fire\n  Mip level 3 is missing.\n"
+        );
+        assert_eq!(
+            e.localized(&LngList::Ru.id()),
+            "Не удалось декодировать BLP.\n  Ошибка ввода-вывода: disk on fire\n  Мип-уровень 3 отсутствует.\n"
+        );
+    }
+
+    #[test]
+    fn fallbacks() {
+        // нет перевода → en
+        let e = BlpError::new(ErrorKind::Encode, "error-encode-no-heads");
+        assert_eq!(e.localized(&LngList::Ru.id()), "Encoder produced no JPEG headers.\n");
+
+        // нет ключа вовсе → сырой Display
+        let e = BlpError::new(ErrorKind::Internal, "no-such-key").with_arg("x", 1u32);
+        assert_eq!(e.localized(&LngList::En.id()), format!("{e}\n"));
+    }
+
+    #[test]
+    fn json() {
+        let e = BlpError::from(std::io::Error::other("say \"hi\"\n"))
+            .ctx("error-blp-decode-failed")
+            .push_blp(BlpError::new(ErrorKind::MipMissing, "error-blp-mip-missing").with_arg("mip", 3u32));
+
+        assert_eq!(
+            e.to_json(&LngList::En.id()),
+            concat!(
+                r#"{"kind":"Io","key":"error-blp-decode-failed","message":"Failed to decode BLP.","args":{},"causes":["#,
+                r#"{"kind":"Io","key":"error-io","message":"I/O error: say \"hi\"\n","args":{"msg":"say \"hi\"\n"},"#,
+                r#""causes":[{"message":"say \"hi\"\n"}]},"#,
+                r#"{"kind":"MipMissing","key":"error-blp-mip-missing","message":"Mip level 3 is missing.","args":{"mip":3},"causes":[]}"#,
+                "]}"
+            )
+        );
+    }
+
+    #[test]
+    fn from_locale() {
+        for (tag, lng) in [
+            ("ru_RU.UTF-8", LngList::Ru),
+            ("uk", LngList::Uk),
+            ("zh-Hant-TW", LngList::Tc),
+            ("zh_CN", LngList::Zh),
+            ("de-DE", LngList::En),
+            ("", LngList::En),
+        ] {
+            assert_eq!(LngList::from_locale(tag), lng, "{tag}");
+        }
+    }
+}
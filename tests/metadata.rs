@@ -0,0 +1,87 @@
+mod common;
+
+#[cfg(test)]
+mod metadata {
+    use crate::common::test_data;
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::image::ImageBlp;
+    use blp::core::metadata::BlpMetadata;
+    use blp::error::error_kind::ErrorKind;
+    use std::io::Cursor;
+
+    fn encode(opts: &EncodeOptions) -> Vec<u8> {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true, true]).unwrap();
+        img.encode_blp_with(opts, &[true, false]).unwrap().bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut m = BlpMetadata::new();
+        m.set("a", "1").set("ключ", "значение").set("a", "2");
+        assert_eq!(m.entries, [("a".to_string(), "2".to_string()), ("ключ".to_string(), "значение".to_string())]);
+
+        let mut bytes = m.to_bytes().unwrap();
+        let len = bytes.len();
+        bytes.extend(b"tail");
+        let (parsed, used) = BlpMetadata::parse(&bytes).unwrap();
+        assert_eq!(parsed, m);
+        assert_eq!(used, len);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut m = BlpMetadata::new();
+        m.set("k", "v");
+        let bytes = m.to_bytes().unwrap();
+
+        let kind = |b: &[u8]| BlpMetadata::parse(b).unwrap_err().kind;
+        assert_eq!(kind(b"RAFT"), ErrorKind::Truncated);
+        assert_eq!(kind(b"JUNK\x01\0\0\0\0"), ErrorKind::InvalidHeader);
+        assert_eq!(kind(&bytes[..bytes.len() - 1]), ErrorKind::Truncated);
+
+        let mut v2 = bytes.clone();
+        v2[4] = 2;
+        assert_eq!(kind(&v2), ErrorKind::UnsupportedVersion);
+
+        let mut bad = bytes.clone();
+        let last = bad.len() - 1;
+        bad[last] = 0xFF;
+        assert_eq!(kind(&bad), ErrorKind::InvalidHeader);
+
+        let mut long = BlpMetadata::new();
+        long.set("k".repeat(300), "v");
+        assert_eq!(long.to_bytes().unwrap_err().kind, ErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn written_by_encoder() {
+        let mut extra = BlpMetadata::new();
+        extra
+            .set(BlpMetadata::AUTHOR, "someone")
+            .set(BlpMetadata::QUALITY, "override");
+        let blp = encode(&EncodeOptions { quality: 70, metadata: extra, ..Default::default() });
+
+        let img = ImageBlp::from_buf(&blp).unwrap();
+        let m = img.metadata.as_ref().unwrap();
+        assert!(m.get(BlpMetadata::ENCODER).unwrap().starts_with("blp "));
+        assert_eq!(m.get(BlpMetadata::AUTHOR), Some("someone"));
+        // записи вызывающего перекрывают записи кодировщика
+        assert_eq!(m.get(BlpMetadata::QUALITY), Some("override"));
+        assert_eq!(m.get(BlpMetadata::MIPS).unwrap(), "1011111111000000");
+
+        let read = ImageBlp::read_metadata(Cursor::new(&blp)).unwrap();
+        assert_eq!(read.as_ref(), Some(m));
+    }
+
+    #[test]
+    fn absent() {
+        let logo = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        assert!(ImageBlp::from_buf(&logo).unwrap().metadata.is_none());
+        assert!(ImageBlp::read_metadata(Cursor::new(&logo)).unwrap().is_none());
+
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        assert!(ImageBlp::read_metadata(Cursor::new(&png)).unwrap().is_none());
+    }
+}
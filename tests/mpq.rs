@@ -0,0 +1,204 @@
+#[cfg(all(test, feature = "mpq"))]
+mod mpq {
+    use blp::error::error_kind::ErrorKind;
+    use blp::mpq::archive::MpqArchive;
+    use blp::mpq::archive_path::ArchivePath;
+    use blp::mpq::writer::MpqWriter;
+    use std::io::Cursor;
+    use std::path::{Path, PathBuf};
+
+    /// Независимая реализация шифрования MPQ — для ручной сборки архива.
+    fn crypt_table() -> Vec<u32> {
+        let mut table = vec![0u32; 0x500];
+        let mut seed: u32 = 0x0010_0001;
+        for i in 0..0x100 {
+            for j in 0..5 {
+                seed = (seed * 125 + 3) % 0x2A_AAAB;
+                let hi = (seed & 0xFFFF) << 16;
+                seed = (seed * 125 + 3) % 0x2A_AAAB;
+                table[i + j * 0x100] = hi | (seed & 0xFFFF);
+            }
+        }
+        table
+    }
+
+    fn hash(name: &str, kind: u32) -> u32 {
+        let t = crypt_table();
+        let (mut s1, mut s2) = (0x7FED_7FEDu32, 0xEEEE_EEEEu32);
+        for c in name.to_ascii_uppercase().bytes() {
+            s1 = t[(kind * 256 + c as u32) as usize] ^ s1.wrapping_add(s2);
+            s2 = (c as u32).wrapping_add(s1).wrapping_add(s2).wrapping_add(s2 << 5).wrapping_add(3);
+        }
+        s1
+    }
+
+    fn encrypt(data: &mut [u8], mut key: u32) {
+        let t = crypt_table();
+        let mut seed = 0xEEEE_EEEEu32;
+        for c in data.chunks_exact_mut(4) {
+            seed = seed.wrapping_add(t[0x400 + (key & 0xFF) as usize]);
+            let v = u32::from_le_bytes(c.try_into().unwrap());
+            c.copy_from_slice(&(v ^ key.wrapping_add(seed)).to_le_bytes());
+            key = ((!key << 21).wrapping_add(0x1111_1111)) | (key >> 11);
+            seed = v.wrapping_add(seed).wrapping_add(seed << 5).wrapping_add(3);
+        }
+    }
+
+    fn u32s(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn table_keys() {
+        assert_eq!(hash("(hash table)", 3), 0xC3AF_3770);
+        assert_eq!(hash("(block table)", 3), 0xEC83_B3A3);
+    }
+
+    /// Архив собран вручную: один файл, сжатый PKWARE (пример из blast.c), зашифрованный с `FIX_KEY`.
+    #[test]
+    fn read_imploded_encrypted() {
+        let name = "Units\\AI.txt";
+        let offset = 32u32;
+        let mut data = vec![0x08, 0x00, 0x04, 0x82, 0x24, 0x25, 0x8f, 0x80, 0x7f];
+        let (csize, fsize) = (data.len() as u32, 13u32);
+        encrypt(&mut data, hash("AI.txt", 3).wrapping_add(offset) ^ fsize);
+
+        let mut hashes = vec![[u32::MAX; 4]; 16];
+        hashes[(hash(name, 0) & 15) as usize] = [hash(name, 1), hash(name, 2), 0, 0];
+        let mut hash_table = u32s(&hashes.concat());
+        encrypt(&mut hash_table, hash("(hash table)", 3));
+        let flags = 0x8000_0000 | 0x0100_0000 | 0x0002_0000 | 0x0001_0000 | 0x200;
+        let mut block_table = u32s(&[offset, csize, fsize, flags]);
+        encrypt(&mut block_table, hash("(block table)", 3));
+
+        let hash_pos = offset + csize;
+        let block_pos = hash_pos + hash_table.len() as u32;
+        let total = block_pos + block_table.len() as u32;
+        let mut buf = b"MPQ\x1A".to_vec();
+        buf.extend(u32s(&[32, total]));
+        buf.extend([0, 0, 3, 0]);
+        buf.extend(u32s(&[hash_pos, block_pos, 16, 1]));
+        buf.extend(data);
+        buf.extend(hash_table);
+        buf.extend(block_table);
+
+        let mut ar = MpqArchive::new(Cursor::new(buf)).unwrap();
+        assert!(ar.contains("units/ai.TXT"));
+        assert_eq!(ar.read_file(name).unwrap(), b"AIAIAIAIAIAIA");
+        assert_eq!(ar.file_names().unwrap(), Vec::<String>::new());
+        assert_eq!(ar.read_file("Units\\none.txt").unwrap_err().kind, ErrorKind::Io);
+    }
+
+    fn sample() -> (Vec<u8>, Vec<u8>) {
+        let big: Vec<u8> = (0..20_000u32)
+            .map(|i| (i / 7) as u8)
+            .collect();
+        // не сжимается: сектора хранятся как есть
+        let noise: Vec<u8> = (0..5000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        (big, noise)
+    }
+
+    #[test]
+    fn write_and_read() {
+        let (big, noise) = sample();
+        let mut w = MpqWriter::new();
+        w.add_file("Textures/Big.blp", big.clone())
+            .add_file("noise.bin", noise.clone())
+            .add_file("empty.txt", Vec::new())
+            .add_file("NOISE.BIN", noise.clone());
+        let buf = w.to_bytes().unwrap();
+        assert!(buf.len() < big.len());
+
+        let mut ar = MpqArchive::new(Cursor::new(buf)).unwrap();
+        assert_eq!(ar.read_file("textures\\big.blp").unwrap(), big);
+        assert_eq!(ar.read_file("noise.bin").unwrap(), noise);
+        assert_eq!(ar.read_file("empty.txt").unwrap(), b"");
+        assert_eq!(ar.file_names().unwrap(), ["Textures\\Big.blp", "empty.txt", "NOISE.BIN"]);
+    }
+
+    #[test]
+    fn import_into_map() {
+        let (big, noise) = sample();
+        let mut w = MpqWriter::new();
+        w.add_file("war3map.j", b"function main takes nothing returns nothing\nendfunction\n".to_vec())
+            .add_file("(attributes)", vec![0; 16])
+            .add_file("war3mapImported\\icon.blp", noise.clone());
+
+        // карта .w3x: 512 байт заголовка HM3W перед архивом
+        let mut map = b"HM3W".to_vec();
+        map.resize(512, 0);
+        map.extend(w.to_bytes().unwrap());
+
+        let out = MpqArchive::import_into(&map, &[("war3mapImported/icon.blp", &big), ("war3mapImported\\new.blp", b"BLP2")]).unwrap();
+        assert_eq!(&out[..4], b"HM3W");
+
+        let mut ar = MpqArchive::new(Cursor::new(out)).unwrap();
+        assert_eq!(ar.read_file("war3mapImported\\icon.blp").unwrap(), big);
+        assert_eq!(ar.read_file("war3mapImported\\new.blp").unwrap(), b"BLP2");
+        assert!(ar.read_file("war3map.j").unwrap().starts_with(b"function main"));
+        assert!(!ar.contains("(attributes)"));
+        assert_eq!(ar.file_names().unwrap(), ["war3map.j", "war3mapImported\\icon.blp", "war3mapImported\\new.blp"]);
+    }
+
+    #[test]
+    fn import_into_truncated_hash_table() {
+        let mut w = MpqWriter::new();
+        w.add_file("war3map.j", b"main".to_vec());
+        let mut buf = w.to_bytes().unwrap();
+        let u32_at = |b: &[u8], p: usize| u32::from_le_bytes(b[p..p + 4].try_into().unwrap());
+        assert_eq!(u32_at(&buf, 24), 16);
+
+        // хэш-таблица переезжает в конец файла и обрезается до 12 записей (как у защищённых карт)
+        let pos = u32_at(&buf, 16) as usize;
+        let table = buf[pos..pos + 12 * 16].to_vec();
+        let end = buf.len() as u32;
+        buf[16..20].copy_from_slice(&end.to_le_bytes());
+        buf.extend(table);
+
+        let out = MpqArchive::import_into(&buf, &[("war3mapImported\\a.blp", b"BLP1")]).unwrap();
+        assert_eq!(u32_at(&out, 24), 16);
+        let mut ar = MpqArchive::new(Cursor::new(out)).unwrap();
+        assert_eq!(ar.read_file("war3mapImported\\a.blp").unwrap(), b"BLP1");
+
+        buf[24..28].copy_from_slice(&12u32.to_le_bytes());
+        let err = MpqArchive::import_into(&buf, &[("a.blp", b"BLP1")]).unwrap_err();
+        assert_eq!(err.key, "error-mpq-hash-table-size");
+    }
+
+    #[test]
+    fn import_file_and_read_path() {
+        let dir = std::env::temp_dir().join(format!("blp-mpq-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("test.w3x");
+        std::fs::write(&archive, MpqWriter::new().to_bytes().unwrap()).unwrap();
+
+        let ap = ArchivePath::parse(&PathBuf::from(format!("{}:war3mapImported/a.blp", archive.display()))).unwrap();
+        ap.write(b"first").unwrap();
+        ap.write(b"second").unwrap();
+        assert_eq!(ap.read().unwrap(), b"second");
+
+        let missing = ArchivePath { archive: archive.clone(), inner: "nope.blp".into() };
+        assert_eq!(missing.read().unwrap_err().kind, ErrorKind::Io);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn archive_path() {
+        let ap = ArchivePath::parse(Path::new("C:\\Games\\War3.MPQ:Textures/Foo.blp")).unwrap();
+        assert_eq!(ap.archive, PathBuf::from("C:\\Games\\War3.MPQ"));
+        assert_eq!((ap.inner.as_str(), ap.file_name()), ("Textures\\Foo.blp", "Foo.blp"));
+        assert_eq!(ap.to_string(), "C:\\Games\\War3.MPQ:Textures\\Foo.blp");
+
+        for plain in ["C:\\tex\\foo.blp", "maps/a.w3x", "maps/a.w3x:", "notes.mpq.txt:x"] {
+            assert_eq!(ArchivePath::parse(Path::new(plain)), None, "{plain}");
+        }
+    }
+
+    #[test]
+    fn not_an_archive() {
+        let e = MpqArchive::new(Cursor::new(vec![0u8; 2048])).err().unwrap();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidArchive, "error-mpq-no-header"));
+    }
+}
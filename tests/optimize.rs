@@ -0,0 +1,80 @@
+mod common;
+
+#[cfg(test)]
+mod optimize {
+    use crate::common::test_data;
+    use blp::core::image::ImageBlp;
+    use blp::error::error_kind::ErrorKind;
+
+    #[cfg(feature = "turbojpeg")]
+    fn pixels(buf: &[u8]) -> Vec<Option<Vec<u8>>> {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode(buf, &[true; 16]).unwrap();
+        img.mipmaps
+            .iter()
+            .map(|m| m.image.as_ref().map(|i| i.as_raw().clone()))
+            .collect()
+    }
+
+    #[cfg(feature = "turbojpeg")]
+    #[test]
+    fn own_encoder() {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true; 16]).unwrap();
+        let buf = img.encode_blp(85, &[true; 16]).unwrap().bytes;
+
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        let o = img.optimize_jpeg(&buf).unwrap();
+        assert!(o.bytes.len() < buf.len());
+        assert!(o.optimized.contains(&0));
+        assert_eq!(o.optimized.len() + o.kept.len(), 10);
+        assert_eq!(pixels(&o.bytes), pixels(&buf));
+
+        // чанк метаданных кодировщика переносится
+        let out = ImageBlp::from_buf(&o.bytes).unwrap();
+        assert!(out.metadata.is_some());
+        assert_eq!(out.metadata, img.metadata);
+        assert_eq!(o.metadata, img.metadata.as_ref().unwrap().to_bytes().unwrap().len());
+
+        // второй проход ничего не выигрывает
+        let again = ImageBlp::from_buf(&o.bytes)
+            .unwrap()
+            .optimize_jpeg(&o.bytes)
+            .unwrap();
+        assert!(again.bytes.len() <= o.bytes.len());
+    }
+
+    #[cfg(feature = "turbojpeg")]
+    #[test]
+    fn blizzard_strips_holes() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        let o = img.optimize_jpeg(&buf).unwrap();
+        assert!(o.bytes.len() <= buf.len() - img.holes);
+        let out = ImageBlp::from_buf(&o.bytes).unwrap();
+        assert!(out.hole_ranges.is_empty());
+        assert_eq!((out.width, out.height, out.alpha_bits), (img.width, img.height, img.alpha_bits));
+    }
+
+    #[cfg(not(feature = "turbojpeg"))]
+    #[test]
+    fn unavailable_without_turbojpeg() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let e = ImageBlp::from_buf(&buf)
+            .unwrap()
+            .optimize_jpeg(&buf)
+            .unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::Encode, "error-optimize-unavailable"));
+    }
+
+    #[test]
+    fn not_jpeg() {
+        let buf = test_data("to-png/a.blp");
+        let e = ImageBlp::from_buf(&buf)
+            .unwrap()
+            .optimize_jpeg(&buf)
+            .unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidArgument, "error-optimize-not-jpeg"));
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod parallel {
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::encode::jpeg_backend::JpegBackend;
+    use blp::core::image::ImageBlp;
+    use blp::error::error::BlpError;
+    use image::{Rgba, RgbaImage};
+    use std::path::PathBuf;
+
+    fn data(rel: &str) -> Vec<u8> {
+        std::fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(rel)).unwrap()
+    }
+
+    #[test]
+    fn encode_is_byte_identical_to_serial() -> Result<(), BlpError> {
+        for alpha in [false, true] {
+            let src = RgbaImage::from_fn(256, 128, |x, y| Rgba([x as u8, y as u8, (x * y) as u8, if alpha { (x ^ y) as u8 } else { 255 }]));
+            let blp = ImageBlp::from_rgba(src, &[])?;
+            let mask = [true, true, false, true];
+
+            for &backend in JpegBackend::available() {
+                let serial = blp.encode_blp_with(&EncodeOptions { quality: 85, backend, threads: 1, ..Default::default() }, &mask)?;
+                for threads in [0, 2, 5] {
+                    let par = blp.encode_blp_with(&EncodeOptions { quality: 85, backend, threads, ..Default::default() }, &mask)?;
+                    assert!(par.bytes == serial.bytes, "{backend:?} alpha={alpha} threads={threads}");
+                    assert_eq!(par.mips.len(), serial.mips.len());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn decode_matches_serial() -> Result<(), BlpError> {
+        // JPEG и DIRECT
+        for rel in ["test-data/to-blp/WarcraftIIIFTLogo.blp", "test-data/to-png/a.blp"] {
+            let buf = data(rel);
+            let decode = |threads| -> Result<ImageBlp, BlpError> {
+                let mut img = ImageBlp::from_buf(&buf)?;
+                img.decode_with(&buf, &[], &DecodeOptions { threads, ..Default::default() })?;
+                Ok(img)
+            };
+            let serial = decode(1)?;
+            let par = decode(0)?;
+            assert!(serial.mipmaps[0].image.is_some(), "{rel}");
+            for (a, b) in serial.mipmaps.iter().zip(&par.mipmaps) {
+                assert_eq!(a.image, b.image, "{rel}");
+            }
+        }
+        Ok(())
+    }
+}
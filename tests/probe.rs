@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod probe {
+    use blp::core::image::ImageBlp;
+    use blp::core::probe::{ProbeFormat, ProbeInfo};
+    use blp::core::types::{SourceKind, Version};
+    use blp::error::error::BlpError;
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+    use std::path::PathBuf;
+
+    /// Считает прочитанные байты.
+    struct Counting<R> {
+        inner: R,
+        read: usize,
+    }
+
+    impl<R: Read> Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    impl<R: Seek> Seek for Counting<R> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn matches_from_buf() -> Result<(), BlpError> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data");
+        for entry in walkdir::WalkDir::new(&root) {
+            let path = entry.unwrap().into_path();
+            if !path.is_file() || path.extension().is_some_and(|e| e == "csv") {
+                continue;
+            }
+            let buf = std::fs::read(&path).unwrap();
+            let info = ImageBlp::probe(&buf)?;
+            assert_eq!(info, ImageBlp::probe_reader(std::fs::File::open(&path).unwrap())?, "{path:?}");
+
+            let full = ImageBlp::from_buf(&buf)?;
+            match full.source {
+                SourceKind::Blp => {
+                    assert_eq!(info.format, ProbeFormat::Blp(full.version), "{path:?}");
+                    assert_eq!((info.width, info.height), (full.width, full.height), "{path:?}");
+                    assert_eq!(info.texture_type, Some(full.texture_type), "{path:?}");
+                    let mips = full
+                        .mipmaps
+                        .iter()
+                        .filter(|m| m.length > 0)
+                        .count();
+                    assert_eq!(info.mip_count, mips, "{path:?}");
+
+                    // только заголовок
+                    let mut r = Counting { inner: Cursor::new(&buf), read: 0 };
+                    ImageBlp::probe_reader(&mut r)?;
+                    assert!(r.read <= 3 + 156, "{path:?}: {} bytes", r.read);
+                }
+                SourceKind::Image => {
+                    assert!(matches!(info.format, ProbeFormat::Image(image::ImageFormat::Png)), "{path:?}");
+                    assert_eq!(info.texture_type, None);
+                    assert_eq!(info.mip_count, 1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn blp2_and_garbage() {
+        let mut b = b"BLP2".to_vec();
+        b.extend_from_slice(&1u32.to_le_bytes()); // DIRECT
+        b.extend_from_slice(&[1, 8, 0, 1]); // compression, alpha_bits, alpha_type, has_mips
+        b.extend_from_slice(&64u32.to_le_bytes());
+        b.extend_from_slice(&32u32.to_le_bytes());
+        b.extend_from_slice(&[0; 64]);
+        b.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0]);
+        b.extend_from_slice(&[0; 56]);
+        assert_eq!(
+            ImageBlp::probe(&b).unwrap(),
+            ProbeInfo {
+                format: ProbeFormat::Blp(Version::BLP2),
+                width: 64,
+                height: 32,
+                texture_type: Some(blp::core::types::TextureType::DIRECT),
+                mip_count: 2,
+            }
+        );
+
+        assert!(ImageBlp::probe(&b[..100]).is_err());
+        assert!(ImageBlp::probe(b"BL").is_err());
+        assert!(ImageBlp::probe(b"not an image at all").is_err());
+    }
+}
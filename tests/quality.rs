@@ -0,0 +1,70 @@
+mod common;
+
+#[cfg(test)]
+mod quality {
+    use crate::common::test_data;
+    use blp::core::image::ImageBlp;
+    use blp::core::inspect::quality_estimate::QualityEstimate;
+    use blp::core::inspect::quant_table::QuantTable;
+
+    fn encode(quality: u8) -> Vec<u8> {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true, true]).unwrap();
+        img.encode_blp(quality, &[true, true]).unwrap().bytes
+    }
+
+    #[test]
+    fn own_encoder_exact() {
+        for q in [10, 50, 75, 85, 95, 100] {
+            let buf = encode(q);
+            let e = ImageBlp::from_buf(&buf)
+                .unwrap()
+                .quality_estimate
+                .unwrap();
+            assert_eq!((e.quality, e.exact), (q, true), "q{q}");
+        }
+    }
+
+    #[test]
+    fn blizzard() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let e = ImageBlp::from_buf(&buf)
+            .unwrap()
+            .quality_estimate
+            .unwrap();
+        // яркость и цветность — таблицы IJG для 80
+        assert_eq!((e.quality, e.exact), (80, true));
+        assert_eq!(
+            e.tables
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert!(ImageBlp::from_buf(&test_data("to-png/a.blp")).unwrap().quality_estimate.is_none());
+    }
+
+    #[test]
+    fn parse_dqt() {
+        // SOI, DQT (8 бит, Tq=0: 1..=64 в зигзаге), DQT (16 бит, Tq=1: все 300), SOS
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xDB, 0, 67, 0x00];
+        jpeg.extend(1..=64u8);
+        jpeg.extend([0xFF, 0xDB, 0, 131, 0x11]);
+        for _ in 0..64 {
+            jpeg.extend(300u16.to_be_bytes());
+        }
+        jpeg.extend([0xFF, 0xDA, 0, 2]);
+        let t = QuantTable::parse_all(&jpeg).unwrap();
+        assert_eq!(t.len(), 2);
+        assert_eq!((t[0].id, t[0].wide, t[1].id, t[1].wide), (0, false, 1, true));
+        // естественный порядок: (0,0)=1, (0,1)=2, (1,0)=3, (2,0)=4, (1,1)=5
+        assert_eq!([t[0].values[0], t[0].values[1], t[0].values[8], t[0].values[16], t[0].values[9]], [1, 2, 3, 4, 5]);
+        assert_eq!(t[1].values, [300; 64]);
+
+        let e = QualityEstimate::from_tables(t).unwrap();
+        assert!(!e.exact);
+        assert!(QualityEstimate::from_tables(Vec::new()).is_none());
+        assert!(QuantTable::parse_all(&[0xFF, 0xD8, 0xFF, 0xDB, 0, 67, 0]).is_err());
+    }
+}
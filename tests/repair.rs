@@ -0,0 +1,103 @@
+mod common;
+
+#[cfg(test)]
+mod repair {
+    use crate::common::test_data;
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::image::ImageBlp;
+    use blp::core::inspect::jpeg_header_info::JpegHeaderInfo;
+    use blp::error::error_kind::ErrorKind;
+
+    /// Раскладка Blizzard: весь заголовок мипа 0 (с его SOF) — общий, у мипов только энтропийные данные.
+    fn blizzard_style(buf: &[u8]) -> Vec<u8> {
+        let img = ImageBlp::from_buf(buf).unwrap();
+        let shared = &buf[img.header_offset..img.header_offset + img.header_length];
+        let mut head0 = Vec::new();
+        let mut tails = Vec::new();
+        for m in img.mipmaps.iter().filter(|m| m.length > 0) {
+            let full = [shared, &buf[m.offset..m.offset + m.length]].concat();
+            let end = JpegHeaderInfo::parse(&full).unwrap().header_end;
+            if head0.is_empty() {
+                head0 = full[..end].to_vec();
+            }
+            tails.push(full[end..].to_vec());
+        }
+
+        let mut out = buf[..156].to_vec();
+        out.extend((head0.len() as u32).to_le_bytes());
+        out.extend(&head0);
+        for (i, t) in tails.iter().enumerate() {
+            let off = out.len() as u32;
+            out[28 + i * 4..32 + i * 4].copy_from_slice(&off.to_le_bytes());
+            out[92 + i * 4..96 + i * 4].copy_from_slice(&(t.len() as u32).to_le_bytes());
+            out.extend(t);
+        }
+        out
+    }
+
+    fn mip_dims(img: &ImageBlp) -> Vec<Option<(u32, u32)>> {
+        img.mipmaps
+            .iter()
+            .map(|m| m.image.as_ref().map(|i| i.dimensions()))
+            .collect()
+    }
+
+    #[test]
+    fn fix_sof_dims_on_decode() {
+        let buf = blizzard_style(&test_data("to-blp/WarcraftIIIFTLogo.blp"));
+        let mut img = ImageBlp::from_buf(&buf).unwrap();
+        assert_eq!(img.jpeg_inspect(&buf).unwrap().sof_mismatches(), 9);
+
+        let opts = DecodeOptions { fix_sof_dims: true, ..Default::default() };
+        img.decode_with(&buf, &[], &opts).unwrap();
+        let want: Vec<_> = img
+            .mipmaps
+            .iter()
+            .map(|m| (m.width > 0).then_some((m.width, m.height)))
+            .collect();
+        assert_eq!(mip_dims(&img)[..10], want[..10]);
+    }
+
+    #[test]
+    fn repair_blizzard_layout() {
+        let orig = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let buf = blizzard_style(&orig);
+        let r = ImageBlp::from_buf(&buf).unwrap().repair_jpeg(&buf).unwrap();
+        assert_eq!(r.sof_fixed, (1..10).collect::<Vec<_>>());
+        assert!(r.dropped.is_empty());
+        assert_eq!(r.shared_header_len, (660, 627));
+
+        let mut fixed = ImageBlp::from_buf(&r.bytes).unwrap();
+        let inspect = fixed.jpeg_inspect(&r.bytes).unwrap();
+        assert!(inspect.all_equal_except_dims() && inspect.sof_mismatches() == 0);
+
+        // без SOF-правки декодер видит исходные размеры мипов
+        fixed.decode(&r.bytes, &[]).unwrap();
+        let mut reference = ImageBlp::from_buf(&orig).unwrap();
+        reference.decode(&orig, &[]).unwrap();
+        assert_eq!(mip_dims(&fixed), mip_dims(&reference));
+        assert_eq!(fixed.mipmaps[4].image, reference.mipmaps[4].image);
+    }
+
+    #[test]
+    fn repair_standard_is_stable() {
+        let orig = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let r = ImageBlp::from_buf(&orig).unwrap().repair_jpeg(&orig).unwrap();
+        assert!(r.sof_fixed.is_empty());
+        assert_eq!(r.shared_header_len, (624, 627));
+
+        let again = ImageBlp::from_buf(&r.bytes).unwrap().repair_jpeg(&r.bytes).unwrap();
+        assert_eq!(again.bytes, r.bytes);
+    }
+
+    #[test]
+    fn repair_errors() {
+        let direct = test_data("to-png/a.blp");
+        let e = ImageBlp::from_buf(&direct).unwrap().repair_jpeg(&direct).unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidArgument, "error-repair-not-jpeg"));
+
+        let broken = test_data("to-blp/a.blp");
+        let e = ImageBlp::from_buf(&broken).unwrap().repair_jpeg(&broken).unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidJpeg, "error-repair-mip"));
+    }
+}
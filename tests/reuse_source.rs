@@ -0,0 +1,134 @@
+mod common;
+
+#[cfg(test)]
+mod reuse_source {
+    use crate::common::{mip_bytes, test_data};
+    use blp::core::decode::decode_options::DecodeOptions;
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::encode::jpeg_backend::JpegBackend;
+    use blp::core::image::ImageBlp;
+
+    fn decode(buf: &[u8], keep_jpeg: bool) -> ImageBlp {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode_with(buf, &[true; 16], &DecodeOptions { keep_jpeg, ..Default::default() })
+            .unwrap();
+        img
+    }
+
+    /// Данные скана каждого мипа (после JPEG-заголовка): совпадают, только если мип скопирован.
+    fn scans(buf: &[u8]) -> Vec<Option<Vec<u8>>> {
+        let img = ImageBlp::from_buf(buf).unwrap();
+        let r = img.jpeg_inspect(buf).unwrap();
+        let mips = mip_bytes(&img, buf);
+        (0..16)
+            .map(|i| {
+                r.mips
+                    .iter()
+                    .find(|m| m.mip == i)
+                    .map(|m| mips[i][m.header_len - r.shared_header_len..].to_vec())
+            })
+            .collect()
+    }
+
+    fn quality(buf: &[u8]) -> (u8, bool) {
+        let e = ImageBlp::from_buf(buf)
+            .unwrap()
+            .quality_estimate
+            .unwrap();
+        (e.quality, e.exact)
+    }
+
+    fn reuse(quality: u8) -> EncodeOptions {
+        EncodeOptions { quality, reuse_source: true, ..Default::default() }
+    }
+
+    #[test]
+    fn copies_untouched_mips() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let src = decode(&buf, true);
+        let mut visible = [true; 16];
+        visible[3] = false;
+        let ctx = src.encode_blp_with(&reuse(50), &visible).unwrap();
+        assert!(ctx.mips[..10].iter().all(|m| m.copied == m.visible));
+
+        let (a, b) = (scans(&buf), scans(&ctx.bytes));
+        let out = decode(&ctx.bytes, false);
+        for i in 0..10 {
+            if i == 3 {
+                assert_eq!(b[i], None);
+                continue;
+            }
+            assert_eq!(a[i], b[i], "mip {i}");
+            assert_eq!(src.mipmaps[i].image, out.mipmaps[i].image, "mip {i}");
+        }
+        // качество ползунка не применилось — оценка та же, что у источника
+        assert_eq!(quality(&ctx.bytes), (80, true));
+    }
+
+    #[test]
+    fn edited_pixels_reencoded() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let mut src = decode(&buf, true);
+        src.mipmaps[0]
+            .image
+            .as_mut()
+            .unwrap()
+            .get_pixel_mut(0, 0)
+            .0[0] ^= 0xFF;
+        let ctx = src.encode_blp_with(&reuse(50), &[true; 16]).unwrap();
+        assert!(!ctx.mips[0].copied);
+        assert!(ctx.mips[1..10].iter().all(|m| m.copied));
+
+        // перекодирован с качеством источника: таблицы те же
+        assert_eq!(quality(&ctx.bytes), (80, true));
+        assert_ne!(scans(&buf)[0], scans(&ctx.bytes)[0]);
+        assert_eq!(scans(&buf)[1], scans(&ctx.bytes)[1]);
+    }
+
+    #[test]
+    fn off_by_default() {
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        // без reuse_source — всё с `quality`; без keep_jpeg копировать нечего, но качество — источника
+        for (keep_jpeg, opts, want) in [(true, EncodeOptions { quality: 50, ..Default::default() }, 50), (false, reuse(50), 80)] {
+            let ctx = decode(&buf, keep_jpeg)
+                .encode_blp_with(&opts, &[true; 16])
+                .unwrap();
+            assert!(ctx.mips.iter().all(|m| !m.copied));
+            assert_eq!(quality(&ctx.bytes), (want, true));
+        }
+    }
+
+    #[test]
+    fn non_ijg_tables() {
+        let mut buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        // один коэффициент первой DQT общего заголовка: таблицы больше не IJG
+        let dqt = 160 + buf[160..784]
+            .windows(2)
+            .position(|w| w == [0xFF, 0xDB])
+            .unwrap();
+        buf[dqt + 5 + 10] += 1;
+        let src = ImageBlp::from_buf(&buf)
+            .unwrap()
+            .quality_estimate
+            .unwrap();
+        assert!(!src.exact);
+
+        for &backend in JpegBackend::available() {
+            let ctx = decode(&buf, false)
+                .encode_blp_with(&EncodeOptions { backend, ..reuse(50) }, &[true; 16])
+                .unwrap();
+            let out = ImageBlp::from_buf(&ctx.bytes)
+                .unwrap()
+                .quality_estimate
+                .unwrap();
+            // чистый Rust берёт таблицу 0 источника как есть, TurboJPEG — только качество
+            let copied = backend.custom_tables();
+            assert_eq!(ctx.source_tables_lost, !copied, "{backend:?}");
+            if copied {
+                assert_eq!(out.main_table(), src.main_table(), "{backend:?}");
+            } else {
+                assert_eq!((out.quality, out.exact), (50, true));
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+mod common;
+
+#[cfg(test)]
+mod salvage {
+    use crate::common::test_data;
+    use blp::core::image::ImageBlp;
+    use blp::core::repair::fixup::Fixup;
+
+    fn set_u32(buf: &mut [u8], pos: usize, v: u32) {
+        buf[pos..pos + 4].copy_from_slice(&v.to_le_bytes());
+    }
+
+    fn get_u32(buf: &[u8], pos: usize) -> u32 {
+        u32::from_le_bytes(buf[pos..pos + 4].try_into().unwrap())
+    }
+
+    fn decoded(buf: &[u8]) -> ImageBlp {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode(buf, &[]).unwrap();
+        img
+    }
+
+    // BLP1: has_mipmaps @24, смещения @28, длины @92
+    #[test]
+    fn protected_jpeg() {
+        let orig = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let mut buf = orig.clone();
+        set_u32(&mut buf, 24, 0x1337);
+        set_u32(&mut buf, 28 + 10 * 4, 200);
+        set_u32(&mut buf, 92 + 10 * 4, 50);
+        let len9 = get_u32(&buf, 92 + 9 * 4);
+        set_u32(&mut buf, 92 + 9 * 4, len9 + 100);
+        buf.extend(b"junk after the last mip");
+
+        let s = ImageBlp::from_buf(&buf).unwrap().salvage(&buf).unwrap();
+        assert_eq!(s.recovered, (0..10).collect::<Vec<_>>());
+        let has = |f: &dyn Fn(&Fixup) -> bool| s.fixups.iter().any(f);
+        assert!(has(&|f| matches!(f, Fixup::EntryDropped { mip: 10 })), "{:?}", s.fixups);
+        assert!(has(&|f| matches!(f, Fixup::RangeClamped { mip: 9, .. })));
+        assert!(has(&|f| matches!(f, Fixup::JunkTrimmed { mip: 9, bytes: 23 })));
+        assert!(has(&|f| matches!(f, Fixup::MipmapFlag { was: 0x1337, now: 1 })));
+        assert!(has(&|f| matches!(f, Fixup::HolesDropped { .. })));
+
+        let (fixed, reference) = (decoded(&s.bytes), decoded(&orig));
+        assert_eq!(fixed.holes, 0);
+        assert_eq!(get_u32(&s.bytes, 24), 1);
+        for i in 0..10 {
+            assert_eq!(fixed.mipmaps[i].image, reference.mipmaps[i].image, "mip {i}");
+        }
+    }
+
+    #[test]
+    fn broken_first_mip_promoted() {
+        let mut buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let off = get_u32(&buf, 28) as usize;
+        buf[off..off + 64].fill(0x5A);
+
+        let s = ImageBlp::from_buf(&buf).unwrap().salvage(&buf).unwrap();
+        assert!(matches!(s.fixups.iter().find(|f| matches!(f, Fixup::Undecodable { .. })), Some(Fixup::Undecodable { mip: 0, .. })), "{:?}", s.fixups);
+        assert!(s.fixups.iter().any(|f| matches!(f, Fixup::Promoted { from: 1 })));
+        assert_eq!(s.recovered, (1..10).collect::<Vec<_>>());
+
+        let fixed = decoded(&s.bytes);
+        assert_eq!((fixed.width, fixed.height), (256, 128));
+        assert_eq!(fixed.mipmaps[0].image.as_ref().unwrap().dimensions(), (256, 128));
+        assert_eq!(fixed.mipmaps[8].image.as_ref().unwrap().dimensions(), (1, 1));
+    }
+
+    #[test]
+    fn truncated_direct() {
+        let orig = test_data("to-png/a.blp");
+        let img = ImageBlp::from_buf(&orig).unwrap();
+        let last = img
+            .mipmaps
+            .iter()
+            .rposition(|m| m.length > 0)
+            .unwrap();
+        let cut = img.mipmaps[last].offset + img.mipmaps[last].length / 2;
+        let buf = &orig[..cut];
+
+        let s = ImageBlp::from_buf(buf).unwrap().salvage(buf).unwrap();
+        assert!(s.fixups.iter().any(|f| matches!(f, Fixup::TruncatedPadded { mip, .. } if *mip == last)), "{:?}", s.fixups);
+        assert_eq!(s.recovered.len(), last + 1);
+
+        let (fixed, reference) = (decoded(&s.bytes), decoded(&orig));
+        assert_eq!(fixed.mipmaps[0].image, reference.mipmaps[0].image);
+        assert!(fixed.mipmaps[last].image.is_some());
+    }
+
+    #[test]
+    fn clean_file_needs_nothing_but_holes() {
+        let orig = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let s = ImageBlp::from_buf(&orig).unwrap().salvage(&orig).unwrap();
+        assert!(matches!(s.fixups.as_slice(), [Fixup::HolesDropped { bytes: 396 }]), "{:?}", s.fixups);
+        assert_eq!(s.recovered, (0..10).collect::<Vec<_>>());
+    }
+}
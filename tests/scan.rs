@@ -0,0 +1,80 @@
+mod common;
+
+#[cfg(test)]
+mod scan {
+    use crate::common::test_data;
+    use blp::core::scan::passport_scan::PassportScan;
+    use blp::core::scan::scan_dir::{ScanOptions, scan_dir};
+    use blp::core::types::{TextureType, Version};
+    use blp::error::error_kind::ErrorKind;
+    use std::fs;
+
+    #[test]
+    fn passports() {
+        let jpeg = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let direct = test_data("to-png/a.blp");
+
+        let mut scan = PassportScan::new(2);
+        assert!(scan.add("a.blp", &jpeg).is_some());
+        assert!(scan.add("b.blp", &jpeg).is_none());
+        assert!(scan.add("c.blp", &jpeg).is_none());
+        assert!(scan.add("d.blp", &direct).is_some());
+        assert!(scan.add("broken.blp", &jpeg[..10]).is_none());
+        let report = scan.finish();
+
+        assert_eq!((report.files, report.groups.len(), report.failed.len()), (5, 2, 1));
+        assert_eq!(report.failed[0].error.kind, ErrorKind::Truncated);
+
+        let g = &report.groups[0];
+        assert_eq!((g.passport.version, g.passport.texture_type), (Version::BLP1, TextureType::JPEG));
+        assert_eq!(g.passport.jpeg_header_len, Some(624));
+        assert_eq!((g.count, g.examples.as_slice()), (3, &["a.blp".to_string(), "b.blp".to_string()][..]));
+        assert_eq!(report.groups[1].passport.jpeg_header_len, None);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains(",JPEG,8,4,1,10,624,3,a.blp;b.blp\n"), "{csv}");
+
+        let json = report.to_json();
+        assert!(json.starts_with("{\"files\":5,\"groups\":[{\"key\":\"BLP1_JPEG_a8_e4_m1_n10_h624\""), "{json}");
+        assert!(json.ends_with("\"failed\":[{\"name\":\"broken.blp\",\"kind\":\"Truncated\",\"key\":\"error-truncated\"}]}"), "{json}");
+    }
+
+    #[test]
+    fn dir_with_samples() {
+        let root = std::env::temp_dir().join(format!("blp-scan-{}", std::process::id()));
+        let (src, samples) = (root.join("src"), root.join("samples"));
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("one.blp"), test_data("to-png/a.blp")).unwrap();
+        fs::write(src.join("sub/TWO.BLP"), test_data("to-png/a.blp")).unwrap();
+        fs::write(src.join("skip.png"), b"not scanned").unwrap();
+
+        let opts = ScanOptions { samples_dir: Some(samples.clone()), ..Default::default() };
+        let report = scan_dir(&src, &opts).unwrap();
+        assert_eq!((report.files, report.groups.len(), report.groups[0].count), (2, 1, 2));
+
+        let key = report.groups[0].passport.key();
+        assert_eq!(fs::read(samples.join(&key).join("one.blp")).unwrap(), test_data("to-png/a.blp"));
+        assert!(fs::read_to_string(samples.join(&key).join("origin.txt")).unwrap().ends_with("one.blp"));
+
+        assert_eq!(scan_dir(&src.join("one.blp"), &opts).unwrap_err().kind, ErrorKind::Io);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(feature = "mpq")]
+    #[test]
+    fn archive() {
+        use blp::core::scan::scan_dir::scan_archive;
+        use blp::mpq::writer::MpqWriter;
+
+        let path = std::env::temp_dir().join(format!("blp-scan-{}.mpq", std::process::id()));
+        let mut w = MpqWriter::new();
+        w.add_file("Textures\\a.blp", test_data("to-png/a.blp"))
+            .add_file("readme.txt", b"skip".to_vec());
+        fs::write(&path, w.to_bytes().unwrap()).unwrap();
+
+        let report = scan_archive(&path, &ScanOptions::default()).unwrap();
+        assert_eq!((report.files, report.groups[0].examples[0].clone()), (1, format!("{}:Textures\\a.blp", path.display())));
+        fs::remove_file(&path).unwrap();
+    }
+}
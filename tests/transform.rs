@@ -0,0 +1,126 @@
+mod common;
+
+#[cfg(test)]
+mod transform {
+    use crate::common::test_data;
+    use blp::core::encode::blp::EncodeOptions;
+    use blp::core::image::ImageBlp;
+    use blp::core::transform::orientation::{Flip, Orientation, Rotation};
+    use blp::error::error_kind::ErrorKind;
+    use image::RgbaImage;
+
+    fn decode(buf: &[u8]) -> ImageBlp {
+        let mut img = ImageBlp::from_buf(buf).unwrap();
+        img.decode(buf, &[true; 16]).unwrap();
+        img
+    }
+
+    fn mip(img: &ImageBlp, i: usize) -> &RgbaImage {
+        img.mipmaps[i].image.as_ref().unwrap()
+    }
+
+    /// Наибольшее расхождение по каналу.
+    fn max_diff(a: &RgbaImage, b: &RgbaImage) -> u8 {
+        assert_eq!(a.dimensions(), b.dimensions());
+        a.as_raw()
+            .iter()
+            .zip(b.as_raw())
+            .map(|(x, y)| x.abs_diff(*y))
+            .max()
+            .unwrap()
+    }
+
+    fn own_blp() -> Vec<u8> {
+        let png = test_data("to-blp/WarcraftIIIFTLogo.png");
+        let mut img = ImageBlp::from_buf(&png).unwrap();
+        img.decode(&png, &[true; 16]).unwrap();
+        img.encode_blp(90, &[true; 16]).unwrap().bytes
+    }
+
+    #[test]
+    fn rotate_lossless_and_fallback() {
+        let buf = own_blp();
+        let src = decode(&buf);
+        let o = Orientation { rotate: Some(Rotation::Rot90), flip: None };
+        let t = src.transform_jpeg(&buf, o, &EncodeOptions::default()).unwrap();
+
+        // 512×256: нижний край кратен MCU 8×8 до мипа 5 (16×8)
+        #[cfg(feature = "turbojpeg")]
+        {
+            assert_eq!(t.lossless, [0, 1, 2, 3, 4, 5]);
+            assert_eq!(t.reencoded, [6, 7, 8, 9]);
+        }
+        assert!(t.dropped.is_empty());
+
+        let out = decode(&t.bytes);
+        assert_eq!((out.width, out.height), (256, 512));
+        for i in 0..10 {
+            let want = o.apply(mip(&src, i));
+            // без потерь — почти побайтно (IDCT повёрнутых блоков), перекодированные — с качеством 100
+            assert!(max_diff(mip(&out, i), &want) <= 4, "mip {i}");
+        }
+    }
+
+    #[test]
+    fn flip_and_compose() {
+        let buf = own_blp();
+        let src = decode(&buf);
+        for o in [
+            Orientation { rotate: None, flip: Some(Flip::Horizontal) },
+            Orientation { rotate: Some(Rotation::Rot180), flip: Some(Flip::Vertical) },
+            Orientation { rotate: Some(Rotation::Rot270), flip: Some(Flip::Vertical) },
+        ] {
+            let t = src.transform_jpeg(&buf, o, &EncodeOptions::default()).unwrap();
+            #[cfg(feature = "turbojpeg")]
+            assert!(t.lossless.contains(&0), "{o:?}");
+            let out = decode(&t.bytes);
+            assert!(max_diff(mip(&out, 0), &o.apply(mip(&src, 0))) <= 4, "{o:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_metadata() {
+        let buf = own_blp();
+        let src = decode(&buf);
+        let o = Orientation { rotate: Some(Rotation::Rot90), flip: None };
+        let t = src.transform_jpeg(&buf, o, &EncodeOptions::default()).unwrap();
+        let out = decode(&t.bytes);
+        assert!(src.metadata.is_some());
+        assert_eq!(out.metadata, src.metadata);
+    }
+
+    #[test]
+    fn blizzard_shared_header() {
+        // общий заголовок с SOF мипа 0: размеры правятся перед преобразованием
+        let buf = test_data("to-blp/WarcraftIIIFTLogo.blp");
+        let src = decode(&buf);
+        let o = Orientation { rotate: Some(Rotation::Rot180), flip: None };
+        let t = src.transform_jpeg(&buf, o, &EncodeOptions::default()).unwrap();
+        #[cfg(feature = "turbojpeg")]
+        assert!(t.lossless.contains(&0));
+        let out = decode(&t.bytes);
+        assert!(max_diff(mip(&out, 0), &o.apply(mip(&src, 0))) <= 4);
+    }
+
+    #[cfg(not(feature = "turbojpeg"))]
+    #[test]
+    fn reencoded_without_turbojpeg() {
+        // без tj3Transform преобразования без потерь нет: перекодируется каждый мип
+        let buf = own_blp();
+        let src = decode(&buf);
+        let o = Orientation { rotate: Some(Rotation::Rot90), flip: None };
+        let t = src.transform_jpeg(&buf, o, &EncodeOptions::default()).unwrap();
+        assert!(t.lossless.is_empty());
+        assert_eq!(t.reencoded, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn not_jpeg() {
+        let buf = test_data("to-png/a.blp");
+        let img = ImageBlp::from_buf(&buf).unwrap();
+        let e = img
+            .transform_jpeg(&buf, Orientation::default(), &EncodeOptions::default())
+            .unwrap_err();
+        assert_eq!((e.kind, e.key), (ErrorKind::InvalidArgument, "error-transform-not-jpeg"));
+    }
+}